@@ -3,6 +3,8 @@ import {GameCore} from "./GameCore.js";
 
 export function Editor () {
 	this.lastTick; // time since the game last ticked
+	this.grid = 1; // number of beat positions between the editor's grid lines
+	this.showPath = false; // whether to show the player's planned path to each target
 }
 
 Object.setPrototypeOf(Editor.prototype, GameCore.prototype);
@@ -49,23 +51,132 @@ Editor.prototype.tick = function(){
 	this.gameObject.seek(this.gameObject.game_data().time_running + timePassed);
 }
 
+// the game's graphics along with the editor's grid lines, lane separators, selection highlights, playhead, and optionally the planned path
+Editor.prototype.getRenderingInstructions = function(viewport){
+	return this.gameObject.editor_rendering_instructions(viewport, this.grid, this.showPath);
+}
+
+Editor.prototype.setGrid = function(grid){
+	this.grid = grid;
+}
+
+Editor.prototype.toggleShowPath = function(){
+	this.showPath = !this.showPath;
+}
+
 Editor.prototype.seek = function(time){
 	this.gameObject.seek(time);
 }
 
+// the brick editing functions return the wasm.EditOutcome, and throw a wasm.EditError with a code and a message if the edit isn't possible
 Editor.prototype.createDefaultBrick = function(beatPos, xPos){
-	this.gameObject.add_brick(wasm.BrickData.new(0, beatPos, beatPos, xPos, false, false, false, false));
+	return this.gameObject.add_brick(wasm.BrickData.new(0, beatPos, beatPos, xPos, false, false, false, false));
 }
 
 Editor.prototype.createBrick = function(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote){
-	this.gameObject.add_brick(wasm.BrickData.new(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote));
+	return this.gameObject.add_brick(wasm.BrickData.new(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote));
 }
 
 Editor.prototype.removeBrick = function(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote){
-	this.gameObject.remove_brick(wasm.BrickData.new(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote));
+	return this.gameObject.remove_brick(wasm.BrickData.new(brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote));
+}
+
+// replaces the original brick with the brick as one edit. If that isn't possible the original brick stays
+	// both bricks are objects with the fields of a wasm.BrickData
+Editor.prototype.moveBrick = function(original, brick){
+	return this.gameObject.move_brick(toBrickData(original), toBrickData(brick));
+}
+
+// moves the end of the brick to the end beat pos. The brick becomes a tap if the end is at or before its start
+Editor.prototype.resizeHold = function(brick, endBeatPos){
+	return this.gameObject.resize_hold(toBrickData(brick), endBeatPos);
+}
+
+// the number of hold segments the brick would have with its end at the end beat pos
+Editor.prototype.holdSegmentPreview = function(brick, endBeatPos){
+	return this.gameObject.hold_segment_preview(toBrickData(brick), endBeatPos);
+}
+
+// edits between beginEditGroup and endEditGroup are undone and redone together
+Editor.prototype.beginEditGroup = function(){
+	this.gameObject.begin_edit_group();
+}
+
+Editor.prototype.endEditGroup = function(){
+	this.gameObject.end_edit_group();
+}
+
+Editor.prototype.undo = function(){
+	return this.gameObject.undo();
+}
+
+Editor.prototype.redo = function(){
+	return this.gameObject.redo();
+}
+
+// selects the bricks starting within the beat range in the lane range, replacing the selection
+Editor.prototype.selectBricks = function(startBeatPos, endBeatPos, leftXPos, rightXPos){
+	return this.gameObject.select_bricks(startBeatPos, endBeatPos, leftXPos, rightXPos);
+}
+
+Editor.prototype.clearSelection = function(){
+	this.gameObject.clear_selection();
+}
+
+Editor.prototype.selectedBricks = function(){
+	return this.gameObject.selected_bricks();
+}
+
+Editor.prototype.copySelection = function(){
+	return this.gameObject.copy_selection();
+}
+
+Editor.prototype.cutSelection = function(){
+	return this.gameObject.cut_selection();
+}
+
+Editor.prototype.deleteSelection = function(){
+	return this.gameObject.delete_selection();
+}
+
+// pastes the copied bricks at the beat pos. Returns a SelectionEdit describing any collisions if the paste failed
+Editor.prototype.paste = function(beatPos){
+	return this.gameObject.paste(beatPos);
+}
+
+// moves the selected bricks. Returns a SelectionEdit describing any collisions if the move failed
+Editor.prototype.moveSelection = function(beatOffset, laneOffset){
+	return this.gameObject.move_selection(beatOffset, laneOffset);
+}
+
+// applies a bulk transform to the bricks starting within the beat pos range. Returns a SelectionEdit describing any collisions if it failed
+	// arg is the brick type for "set-type", the lane offset for "shift-lanes", the ratio for "stretch", 
+	// the hold length in beat positions for "taps-to-holds" and the grid in beat positions for "requantize"
+Editor.prototype.transformNotes = function(operation, startBeatPos, endBeatPos, arg){
+	switch(operation){
+		case "set-type": return this.gameObject.set_brick_types(startBeatPos, endBeatPos, arg);
+		case "cycle-types": return this.gameObject.cycle_brick_types(startBeatPos, endBeatPos);
+		case "mirror": return this.gameObject.mirror_lanes(startBeatPos, endBeatPos);
+		case "shift-lanes": return this.gameObject.shift_lanes(startBeatPos, endBeatPos, arg);
+		case "stretch": return this.gameObject.stretch_timing(startBeatPos, endBeatPos, arg);
+		case "taps-to-holds": return this.gameObject.taps_to_holds(startBeatPos, endBeatPos, arg);
+		case "holds-to-taps": return this.gameObject.holds_to_taps(startBeatPos, endBeatPos);
+		case "requantize": return this.gameObject.requantize(startBeatPos, endBeatPos, arg);
+		default: throw Error("unknown transform: " + operation);
+	}
+}
+
+// what is under the point on the screen, in game coordinates. Returns a wasm.HitTest with the kind of hit, 
+	// the brick hit if any, and the beat pos and lane the point snaps to
+Editor.prototype.hitTest = function(x, y){
+	return this.gameObject.hit_test(x, y);
 }
 
 // !!! does not account for is_trailing is_leading or is_triplet. Ambiguities in the brick selected can lead to bugs.
 Editor.prototype.selectBrick = function(beatPos, xPos){
 	return this.gameObject.select_brick(beatPos, xPos);
-}
\ No newline at end of file
+}
+function toBrickData(brick){
+	return wasm.BrickData.new(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
+		brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+}
@@ -16,8 +16,7 @@ export function EventPropagator(){
 	
 	// screen where everything is added
 	this.screenDiv;
-	this.xFactor;
-	this.yFactor;
+	this.viewport; // maps the game's world onto the screen div
 
 	this.resumeEvents; // events to fire once the game resumes
 	
@@ -58,10 +57,8 @@ EventPropagator.prototype.init = async function(wasmMemoryObj, game, overlay, co
 	this.fps = "fps: ";
 
 	// screen div
-	let gameDim = wasm.game_dimensions();
 	this.screenDiv = document.getElementById("screen");
-	this.xFactor = this.screenDiv.clientWidth / gameDim.x;
-	this.yFactor = this.screenDiv.clientHeight / gameDim.y;
+	this.viewport = wasm.Viewport.new(this.screenDiv.clientWidth, this.screenDiv.clientHeight);
 
 	// initialize loader and load graphics
 	// TODO error handling when it takes too long
@@ -135,7 +132,7 @@ EventPropagator.prototype.gameLoop = function(){
 			// if the game is pre-rendered, dispatch an event saying that the render occurred (triggering another pre-render)
 			// (for performance, instead of prerendering before the repaint, prerender when the gameRender event or some other event triggers one)
 			if(this.isPreRendered){
-				this.overlay.update({fps: this.fps, xFactor: this.xFactor, yFactor: this.yFactor});
+				this.overlay.update({fps: this.fps, viewport: this.viewport});
 				
 				this.isPreRendered = false;
 				let evt = new Event("gameRender");
@@ -219,7 +216,7 @@ EventPropagator.prototype.runInstruction = function(instruction){
 	} 
 	
 	else if(instruction == "pre-render"){
-		this.overlay.update({fps: this.fps, xFactor: this.xFactor, yFactor: this.yFactor});
+		this.overlay.update({fps: this.fps, viewport: this.viewport});
 	} 
 	
 	else if(instruction == "download-log"){
@@ -264,7 +261,7 @@ EventPropagator.prototype.waitSongLoad = function(){
 }
 
 EventPropagator.prototype.preRender = function(){
-	let instructions = this.game.getRenderingInstructions();
+	let instructions = this.game.getRenderingInstructions(this.viewport);
 	
 	this.preRenderTimeTracker.startTime(performance.now());
 	this.graphics.preRender(instructions, this.wasmMemoryObj);
@@ -277,13 +274,12 @@ EventPropagator.prototype.handleResize = function(evt){
 		requestAnimationFrame(() => {
 			let width = this.screenDiv.clientWidth;
 			let height = this.screenDiv.clientHeight;
-			let gameDim = wasm.game_dimensions();
 			
-			this.xFactor = width / gameDim.x;
-			this.yFactor = height / gameDim.y;
-			this.graphics.resize(this.xFactor, this.yFactor);
+			this.viewport.free();
+			this.viewport = wasm.Viewport.new(width, height);
+			this.graphics.resize(this.viewport);
 			
-			this.overlay.update({fps: this.fps, xFactor: this.xFactor, yFactor: this.yFactor});
+			this.overlay.update({fps: this.fps, viewport: this.viewport});
 
 			this.preRender();
 			this.resizeRefresher = true;
@@ -4,6 +4,7 @@ import {GameCore} from "./GameCore.js";
 
 export function Game () {
 	this.lastTick; // time since the game last ticked
+	this.songStartAudioTime; // the audio clock time at which the song's time 0 plays, so inputs can be timed by the audio clock
 }
 
 Object.setPrototypeOf(Game.prototype, GameCore.prototype);
@@ -20,7 +21,9 @@ Game.prototype.start = function (callback) {
 	this.audioSource.connect(this.audioContext.destination);
 	
 	let switchTime = this.audioContext.currentTime + this.audioTimeSafetyBuffer;
-	this.audioSource.start(switchTime, this.gameObject.game_data().time_running + this.songData.startOffset);
+	let timeRunning = this.gameObject.game_data().time_running;
+	this.audioSource.start(switchTime, timeRunning + this.songData.startOffset);
+	this.songStartAudioTime = switchTime - timeRunning;
 	
 	// set the last tick time to when the moment the game is set to start
 	this.lastTick = performance.now() + this.audioTimeSafetyBuffer * 1000; 
@@ -53,6 +56,7 @@ Game.prototype.tick = function(){
 	let audioInstructions = this.gameObject.audio_instructions();
 	let numInstructions = audioInstructions.num_instructions;
 	let u8buf = new Uint8Array(this.wasmMemoryObj.buffer, audioInstructions.instructions_ptr, numInstructions);
+	let delays = new Float32Array(this.wasmMemoryObj.buffer, audioInstructions.delays_ptr, numInstructions);
 	let i = 0;
 	
 	while(i < numInstructions){
@@ -64,18 +68,19 @@ Game.prototype.tick = function(){
 			// you can use these nodes in a "fire and forget" manner" - MDN
 		let audioSource = new AudioBufferSourceNode(this.audioContext, {buffer: this.soundBuffers[key]}); 
 		audioSource.connect(this.audioContext.destination);
-		audioSource.start();
+		audioSource.start(this.audioContext.currentTime + delays[i]); // delayed for contacts known ahead of time, like hold segments
 		
 		++i;
 	}
 }
 
+// inputs are queued at their time in the song according to the audio clock, so they line up with the music that was heard
 Game.prototype.startControl = function(cntrl){
-	let now = performance.now();
-	this.gameObject.input_command(cntrl, (now - this.lastTick) / 1000);
+	let songTime = this.audioContext.currentTime - this.songStartAudioTime;
+	this.gameObject.queue_input(cntrl, songTime);
 }
 
 Game.prototype.stopControl = function(cntrl){
-	let now = performance.now();
-	this.gameObject.stop_command(cntrl, (now - this.lastTick) / 1000);
+	let songTime = this.audioContext.currentTime - this.songStartAudioTime;
+	this.gameObject.queue_release(cntrl, songTime);
 }
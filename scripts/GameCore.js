@@ -8,6 +8,7 @@ import {Game} from "./Game.js";
 export function GameCore () {
 	// members
 	this.gameObject;
+	this.gameConfig; // gameplay tuning of the games created, can be changed to tune the game without recompiling
 	this.wasmMemoryObj;
 	this.database; 
 	this.songData;
@@ -62,7 +63,8 @@ GameCore.prototype.init = async function (wasmMemoryObj) {
 	// !!! can happen same time as graphics are loading
 	this.database = await loader.loadDatabase();
 	
-	this.gameObject = wasm.Game.new();
+	this.gameConfig = wasm.GameConfig.new();
+	this.gameObject = wasm.Game.new(0, 0, 0, this.gameConfig);
     this.songData = {};
 	this.songMP3Buffers = {};
 	
@@ -73,8 +75,9 @@ GameCore.prototype.getSongLoaded = function(){
 	return this.isSongLoaded;
 }
 
-GameCore.prototype.getRenderingInstructions = function(){
-	return this.gameObject.rendering_instructions();
+// the game's graphics, placed on the screen of the viewport
+GameCore.prototype.getRenderingInstructions = function(viewport){
+	return this.gameObject.rendering_instructions(viewport);
 }
 
 GameCore.prototype.getScore = function(){
@@ -89,19 +92,35 @@ GameCore.prototype.getGameData = function(){
 	return this.gameObject.game_data();
 }
 
+// the size of the song's bricks, which are as wide as its lanes
+GameCore.prototype.getBrickDimensions = function(){
+	return this.gameObject.brick_dimensions();
+}
+
 GameCore.prototype.getSongBuffer = function(){
 	return this.songBuffer;
 }
 
+// returns the song data of all songs, each with a difficulty rating calculated from its notes
+	// songs saved with a rating keep it, and the rating of others is calculated once and kept with the loaded song
 GameCore.prototype.songs = function(){
 	let songs = this.database.searchSong();
 	
+	songs.forEach( songData => {
+		if(songData.rating === undefined){
+			let songObject = this.database.loadSong(songData);
+			songObject.rating = rateSong(songObject, this.gameConfig);
+			songData.rating = songObject.rating;
+		}
+	});
+	
 	return songs;
 }
 
 // !!! confirmation on deleted data, this and other song modification methods
-GameCore.prototype.newSong = function(name, artist, difficulty, bpm, brickSpeed, duration, songStartOffset, songFileName, jsonFileName){
-	this.gameObject = wasm.Game.new(bpm, brickSpeed, duration);
+GameCore.prototype.newSong = function(name, artist, difficulty, bpm, brickSpeed, brickTypes, lanes, duration, songStartOffset, songFileName, jsonFileName){
+	this.gameObject = wasm.Game.new(bpm, brickSpeed, duration, this.gameConfig);
+	this.gameObject.initial_load_song_settings(brickTypes, lanes);
 	this.songData = {
 		name: name,
 		artist: artist,
@@ -117,11 +136,24 @@ GameCore.prototype.newSong = function(name, artist, difficulty, bpm, brickSpeed,
 	}
 }
 
-GameCore.prototype.modifySong = function(name, artist, difficulty, bpm, brickSpeed, duration, songStartOffset, songFileName, jsonFileName){
+// throws if a brick of the song has a brick type beyond the number of brick types or is beyond the lanes, leaving the song unchanged
+GameCore.prototype.modifySong = function(name, artist, difficulty, bpm, brickSpeed, brickTypes, lanes, duration, songStartOffset, songFileName, jsonFileName){
+	let gameData = this.gameObject.game_data();
+	this.gameObject.set_num_brick_types(brickTypes);
+	try {
+		this.gameObject.set_lanes(lanes);
+	}
+	catch(err){
+		if(gameData.num_brick_types != brickTypes){
+			this.gameObject.undo(); // the brick types change
+		}
+		throw err;
+	}
 	let notes = this.gameObject.bricks();
 
 	// !!! no need to create a whole new game object when modifying some metadata
-	this.gameObject = wasm.Game.new(bpm, brickSpeed, duration);
+	this.gameObject = wasm.Game.new(bpm, brickSpeed, duration, this.gameConfig);
+	this.gameObject.initial_load_song_settings(brickTypes, lanes);
 
 	notes.forEach( note =>{
 		let brickType = note.brick_type;
@@ -161,22 +193,7 @@ GameCore.prototype.loadSong = function(songData){
 	// !!! creating a new game to load a new song? Or create a load_song method in src? wasm garbage collection?
 	let songObject = this.database.loadSong(songData);
 	
-	this.gameObject = wasm.Game.new(songObject.bpm, songObject.brickSpeed, songObject.duration);
-	
-	songObject.notes.forEach( note => {
-		let brickType = note[0];
-		let beatPos = note[1];
-		let endBeatPos = note[2];
-		let xPos = note[3];
-		let isTriplet = note[4];
-		let isTrailing = note[5];
-		let isLeading = note[6];
-		let isHoldNote = note[7];
-		this.gameObject.initial_load_add_brick(wasm.BrickData.new( 
-			brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote)); 
-	});
-	
-	this.gameObject.seek(0);
+	this.gameObject = createGameObject(songObject, this.gameConfig);
 	
 	this.songData = {
 		name: songObject.name,
@@ -234,22 +251,7 @@ GameCore.prototype.userLoadSong = async function(songAudioFile, songJsonFile){
 	await songJsonFile.text()
 		.then(res => songObject = JSON.parse(res));
 	
-	this.gameObject = wasm.Game.new(songObject.bpm, songObject.brickSpeed, songObject.duration);
-	
-	songObject.notes.forEach( note => {
-		let brickType = note[0];
-		let beatPos = note[1];
-		let endBeatPos = note[2];
-		let xPos = note[3];
-		let isTriplet = note[4];
-		let isTrailing = note[5];
-		let isLeading = note[6];
-		let isHoldNote = note[7];
-		this.gameObject.initial_load_add_brick(wasm.BrickData.new( 
-			brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote)); 
-	});
-	
-	this.gameObject.seek(0);
+	this.gameObject = createGameObject(songObject, this.gameConfig);
 	
 	this.songData = {
 		name: songObject.name,
@@ -274,14 +276,203 @@ GameCore.prototype.userLoadSong = async function(songAudioFile, songJsonFile){
 	this.isSongLoaded = true;
 }
 
+// for user uploads of song audio (mp3 and wav) with notes generated from a standard MIDI file
+GameCore.prototype.userLoadMidi = async function(songAudioFile, midiFile, midiImportOptions = wasm.MidiImportOptions.new()){
+	this.isSongLoaded = false;
+
+	let midiBytes;
+	await midiFile.arrayBuffer()
+		.then(res => midiBytes = new Uint8Array(res));
+	
+	// !!! add error handling
+	let songBuffer;
+	await songAudioFile.arrayBuffer()
+		.then(res => this.audioContext.decodeAudioData(res))
+		.then(res => { songBuffer = res; }
+	);
+	
+	// the MIDI file is read before anything is replaced, so a bad file leaves the current song loaded
+	let bpm;
+	let brickSpeed = 600;
+	let duration = Math.ceil(songBuffer.duration);
+	let gameObject;
+	let importCount;
+	try{
+		bpm = wasm.midi_bpm(midiBytes);
+		gameObject = wasm.Game.new(bpm, brickSpeed, duration, this.gameConfig);
+		importCount = gameObject.load_midi(midiBytes, midiImportOptions);
+	}
+	catch(err){
+		if(gameObject){
+			gameObject.free();
+		}
+		alert("The MIDI file could not be loaded. " + err);
+		this.isSongLoaded = true;
+		return;
+	}
+	if(importCount.rejected > 0){
+		alert(importCount.rejected + " notes overlapped a hold note or were outside the song and were left out");
+	}
+	importCount.free();
+	
+	this.songBuffer = songBuffer;
+	this.gameObject = gameObject;
+	this.gameObject.seek(0);
+	
+	let jsonName = midiFile.name.replace(/\.midi?$/i, "") + ".json";
+	this.songData = {
+		name: midiFile.name,
+		artist: "",
+		difficulty: 0,
+		bpm: bpm,
+		brickSpeed: brickSpeed,
+		duration: duration,
+		startOffset: 0,
+		timeCreated: 0,
+		timeModified: 0,
+		filename: songAudioFile.name,
+		jsonname: jsonName
+	}
+	
+	this.isSongLoaded = true;
+}
+
 GameCore.prototype.saveSong = function(songData){
 	let notes = this.gameObject.bricks();
 	
 	notes.forEach( note => {
 		note.approx_time = wasm.BrickData.approx_time(note.beat_pos, this.songData.bpm);
 	});
+	songData.brickTypes = this.gameObject.game_data().num_brick_types;
+	songData.lanes = this.gameObject.game_data().lanes;
+	let difficultyRating = this.gameObject.difficulty_rating();
+	songData.rating = difficultyRating.rating;
+	difficultyRating.free();
 	
 	this.database.saveSong(songData, notes);
+	this.gameObject.mark_saved();
+}
+
+// for user uploads of song audio (mp3 and wav) with notes converted from an osu!mania (.osu) or StepMania (.sm) chart
+GameCore.prototype.userLoadChart = async function(songAudioFile, chartFile, columnMapping = wasm.ColumnMapping.new()){
+	this.isSongLoaded = false;
+
+	let chartText;
+	await chartFile.text()
+		.then(res => chartText = res);
+	
+	// !!! add error handling
+	let songBuffer;
+	await songAudioFile.arrayBuffer()
+		.then(res => this.audioContext.decodeAudioData(res))
+		.then(res => { songBuffer = res; }
+	);
+	
+	// the chart is converted before anything is replaced, so a bad chart leaves the current song loaded
+	let brickSpeed = 600;
+	let duration = Math.ceil(songBuffer.duration);
+	let chart;
+	let gameObject;
+	try{
+		if(/\.osu$/i.test(chartFile.name)){
+			chart = wasm.import_osu_chart(chartText, columnMapping);
+		}
+		else{
+			chart = wasm.import_sm_chart(chartText, 0, columnMapping);
+		}
+		gameObject = chart.to_game(brickSpeed, duration, this.gameConfig);
+	}
+	catch(err){
+		if(chart){
+			chart.free();
+		}
+		alert("The chart could not be loaded. " + err);
+		this.isSongLoaded = true;
+		return;
+	}
+	
+	let warnings = chart.warnings();
+	if(warnings.length > 0){
+		alert("Some of the chart could not be converted:\n" + warnings.join("\n"));
+	}
+	
+	this.songBuffer = songBuffer;
+	this.gameObject = gameObject;
+	
+	let jsonName = chartFile.name.replace(/\.(osu|sm)$/i, "") + ".json";
+	this.songData = {
+		name: chartFile.name,
+		artist: "",
+		difficulty: 0,
+		bpm: chart.bpm,
+		brickSpeed: brickSpeed,
+		duration: duration,
+		startOffset: chart.start_offset,
+		timeCreated: 0,
+		timeModified: 0,
+		filename: songAudioFile.name,
+		jsonname: jsonName
+	}
+	
+	this.isSongLoaded = true;
+}
+
+// decodes an audio file (mp3 and wav) without loading it as the song, for analysis before a song is created
+GameCore.prototype.decodeAudio = async function(file){
+	let buffer;
+	await file.arrayBuffer()
+		.then(res => this.audioContext.decodeAudioData(res))
+		.then(res => { buffer = res; }
+	);
+	
+	return buffer;
+}
+
+// estimates the tempo of decoded audio, returning an array of candidates (bpm, start_offset, confidence), most confident first
+GameCore.prototype.detectTempo = function(audioBuffer){
+	return wasm.detect_tempo(monoSamples(audioBuffer), audioBuffer.sampleRate);
+}
+
+// replaces the song's notes, bpm, and start offset with a draft chart generated from the song's audio.
+	// without settings, the chart uses the defaults and the song's brick types and lanes
+GameCore.prototype.generateChart = function(settings){
+	if(!settings){
+		settings = wasm.ChartGeneratorSettings.new();
+		settings.num_brick_types = this.gameObject.game_data().num_brick_types;
+		settings.lanes = this.gameObject.game_data().lanes;
+	}
+	let samples = monoSamples(this.songBuffer);
+	let chart = wasm.generate_chart(samples, this.songBuffer.sampleRate, settings);
+	let warnings = chart.warnings();
+	if(warnings.length > 0){
+		alert(warnings.join("\n"));
+	}
+	
+	this.gameObject = chart.to_game(this.songData.brickSpeed, this.songData.duration, this.gameConfig);
+	this.songData.bpm = chart.bpm;
+	this.songData.startOffset = chart.start_offset;
+}
+
+// downloads the song's notes as a MIDI file, to audition the chart against the song in other software
+GameCore.prototype.exportMidi = function(){
+	let bytes;
+	try{
+		bytes = this.gameObject.export_midi();
+	}
+	catch(err){
+		alert("The notes could not be exported. " + err);
+		return;
+	}
+	let file = new Blob([bytes], { type: 'audio/midi' });
+	
+	let fileName = this.songData.jsonname ? this.songData.jsonname.replace(/\.json$/, "") : "song";
+	let a = document.createElement('a')
+	a.href = URL.createObjectURL(file);
+	a.download = fileName + ".mid";
+	
+	document.body.appendChild(a)
+	a.click()
+	document.body.removeChild(a)
 }
 
 GameCore.prototype.toEditor = function(){
@@ -310,4 +501,53 @@ GameCore.prototype.toGame = function(){
 	Object.setPrototypeOf(this, Game.prototype);
 	
 	return this;
-}
\ No newline at end of file
+}
+
+// creates a wasm game holding the notes of a song object (song data and notes, as stored in the database)
+function createGameObject(songObject, gameConfig){
+	let gameObject = wasm.Game.new(songObject.bpm, songObject.brickSpeed, songObject.duration, gameConfig);
+	// songs saved before brick types could be chosen use the default 3, and songs saved before lanes could be chosen the default 32
+	let gameData = gameObject.game_data();
+	gameObject.initial_load_song_settings(songObject.brickTypes || gameData.num_brick_types, songObject.lanes || gameData.lanes);
+	
+	songObject.notes.forEach( note => {
+		let brickType = note[0];
+		let beatPos = note[1];
+		let endBeatPos = note[2];
+		let xPos = note[3];
+		let isTriplet = note[4];
+		let isTrailing = note[5];
+		let isLeading = note[6];
+		let isHoldNote = note[7];
+		gameObject.initial_load_add_brick(wasm.BrickData.new( 
+			brickType, beatPos, endBeatPos, xPos, isTriplet, isTrailing, isLeading, isHoldNote)); 
+	});
+	
+	gameObject.seek(0);
+	
+	return gameObject;
+}
+
+// the difficulty rating of a song's notes
+function rateSong(songObject, gameConfig){
+	let gameObject = createGameObject(songObject, gameConfig);
+	let difficultyRating = gameObject.difficulty_rating();
+	let rating = difficultyRating.rating;
+	difficultyRating.free();
+	gameObject.free();
+	
+	return rating;
+}
+
+// mixes all channels of decoded audio down to one channel of samples
+function monoSamples(audioBuffer){
+	let samples = new Float32Array(audioBuffer.length);
+	for(let i = 0; i < audioBuffer.numberOfChannels; ++i){
+		let channelData = audioBuffer.getChannelData(i);
+		for(let j = 0; j < samples.length; ++j){
+			samples[j] += channelData[j] / audioBuffer.numberOfChannels;
+		}
+	}
+	
+	return samples;
+}
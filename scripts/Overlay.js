@@ -103,12 +103,14 @@ function HomeSelection(songData){
 	this.name;
 	this.artist;
 	this.difficulty;
+	this.rating;
 	this.duration;
 	
 	this.songData = songData;
 	this.name = songData.name;
 	this.artist = songData.artist;
 	this.difficulty = songData.difficulty;
+	this.rating = songData.rating;
 	this.duration = songData.duration;
 	
 	this.div = document.createElement("div");
@@ -120,7 +122,8 @@ function HomeSelection(songData){
 	
 	this.nameField.innerHTML = this.name;
 	this.artistField.innerHTML = this.artist;
-	this.infoField.innerHTML = `Difficulty: ${this.difficulty} -- Duration: ${this.duration}`;
+	let rating = typeof this.rating == "number" ? this.rating.toFixed(2) : "-";
+	this.infoField.innerHTML = `Difficulty: ${this.difficulty} -- Rating: ${rating} -- Duration: ${this.duration}`;
 	
 	this.div.appendChild(this.nameField);
 	this.div.appendChild(this.artistField);
@@ -185,14 +188,17 @@ function EditorOverlay(overlayParent){
 	this.broadRange;
 	this.preciseRange;
 	this.playPauseButton;
+	this.statusLabel; // shows why the last edit failed
 
 	// necessary data
 	this.groundPosOffset;
 	this.mouseDown; // boolean describing if the mouse is down
 	this.changeBrickType; // boolean describing whether to change the brick type
+	this.resizingHold; // boolean describing whether the mouse is dragging the end of the selected brick
 	this.selectedBrick; // current selected brick
-	this.xFactor;
-	this.yFactor;
+	this.selectionStart; // beat pos and x pos where a rectangle selection of multiple bricks started, while the mouse is down
+	this.mouseEditEnder; // while a mouse edit group is open, the window listener that ends it if the mouse up doesn't reach the overlay
+	this.viewport; // where the game's world is on the screen, which the overlay canvas covers
 	this.songTranscriptWidth;
 	
 	// initialize
@@ -225,10 +231,14 @@ function EditorOverlay(overlayParent){
 	
 	this.playPauseButton = document.createElement("button");
 	this.playPauseButton.innerHTML = "Play/Pause";
+	
+	this.statusLabel = document.createElement("span");
+	this.statusLabel.className = "editor-status";
 
 	this.rangesDiv.appendChild(this.preciseRange);
 	this.rangesDiv.appendChild(this.broadRange);
 	this.buttonDiv.appendChild(this.playPauseButton);
+	this.buttonDiv.appendChild(this.statusLabel);
 	this.controlsDiv.appendChild(this.rangesDiv);
 	this.controlsDiv.appendChild(this.buttonDiv);
 
@@ -243,9 +253,10 @@ function EditorOverlay(overlayParent){
 	this.groundPosOffset = wasm.ground_pos();
 	this.mouseDown = false;
 	this.changeBrickType = false;
+	this.resizingHold = false;
 	this.selectedBrick = null;
-	this.xFactor = 1;
-	this.yFactor = 1;
+	this.selectionStart = null;
+	this.viewport = wasm.Viewport.new(dims.x, dims.y);
 
 	this.songTranscriptWidth = 280;
 	
@@ -417,6 +428,21 @@ function SaveLoadMenu(overlayParent){
 		this.openDialog(new LoadSongDialog(this.overlayParent, this));
 		return null;
 	}, "Load Song");
+
+	this.addSelection(() => { 
+		this.overlayParent.getGame().exportMidi();
+		return null;
+	}, "Export MIDI");
+
+	this.addSelection(() => { 
+		this.openDialog(new GenerateChartDialog(this.overlayParent, this));
+		return null;
+	}, "Generate Notes From Audio");
+
+	this.addSelection(() => { 
+		this.openDialog(new TransformDialog(this.overlayParent, this));
+		return null;
+	}, "Transform Notes");
 }
 Object.setPrototypeOf(SaveLoadMenu.prototype, Menu.prototype);
 
@@ -578,6 +604,10 @@ function NewSongDialog(overlayParent, menuParent){
 	this.bpmField;
 	this.brickSpeedLabel;
 	this.brickSpeedField;
+	this.brickTypesLabel;
+	this.brickTypesField;
+	this.lanesLabel;
+	this.lanesField;
 	this.durationLabel;
 	this.durationField;
 	this.songStartOffsetLabel;
@@ -585,6 +615,12 @@ function NewSongDialog(overlayParent, menuParent){
 	this.jsonNameLabel;
 	this.jsonNameField;
 	this.fileInput;
+	this.tempoLabel;
+	this.tapButton;
+	this.audioBuffer; // the uploaded audio, decoded to detect its tempo and to tap along to
+	this.previewSource; // plays the uploaded audio while tapping the tempo
+	this.previewStartTime;
+	this.taps; // times (in seconds into the audio) of taps of the tap button
 
 	this.formTitle.innerHTML = "New Song";
 
@@ -602,6 +638,10 @@ function NewSongDialog(overlayParent, menuParent){
 	this.bpmField = document.createElement("input");
 	this.brickSpeedLabel = document.createElement("label");
 	this.brickSpeedField = document.createElement("input");
+	this.brickTypesLabel = document.createElement("label");
+	this.brickTypesField = document.createElement("input");
+	this.lanesLabel = document.createElement("label");
+	this.lanesField = document.createElement("input");
 	this.durationLabel = document.createElement("label");
 	this.durationField = document.createElement("input");
 	this.songStartOffsetLabel = document.createElement("label");
@@ -614,6 +654,8 @@ function NewSongDialog(overlayParent, menuParent){
 	this.difficultyLabel.innerHTML = "Difficulty(0-10): ";
 	this.bpmLabel.innerHTML = "BPM(40-160): ";
 	this.brickSpeedLabel.innerHTML = "Brick Speed(100-5000): ";
+	this.brickTypesLabel.innerHTML = "Brick colors(1-" + wasm.max_brick_types() + "): ";
+	this.lanesLabel.innerHTML = "Lanes(" + wasm.min_lanes() + "-" + wasm.max_lanes() + "): ";
 	this.durationLabel.innerHTML = "Duration(0-600): ";
 	this.songStartOffsetLabel.innerHTML = "Song start offset (0-6, use 0 if unknown): ";
 	this.jsonNameLabel.innerHTML = "Song data file (json): ";
@@ -628,6 +670,10 @@ function NewSongDialog(overlayParent, menuParent){
 	this.bpmField.type = "text";
 	this.brickSpeedField.defaultValue = "";
 	this.brickSpeedField.type = "text";
+	this.brickTypesField.defaultValue = "3";
+	this.brickTypesField.type = "text";
+	this.lanesField.defaultValue = "32";
+	this.lanesField.type = "text";
 	this.durationField.defaultValue = "";
 	this.durationField.type = "text";
 	this.songStartOffsetField.defaultValue = "";
@@ -638,6 +684,42 @@ function NewSongDialog(overlayParent, menuParent){
 	this.fileInput = document.createElement("input");
 	this.fileInput.innerHTML = "song"
 	this.fileInput.type = "file"
+	this.tempoLabel = document.createElement("label");
+	this.tapButton = document.createElement("button");
+	this.tapButton.innerHTML = "Tap Tempo";
+	this.tapButton.disabled = true;
+	this.taps = [];
+
+	// prefill bpm, start offset, and duration from the uploaded audio
+	this.fileInput.addEventListener("change", async () => {
+		this.stopPreview();
+		this.audioBuffer = null;
+		this.tapButton.disabled = true;
+		let songFile = this.fileInput.files[0];
+		if(!songFile){
+			return;
+		}
+		
+		this.tempoLabel.innerHTML = "Detecting tempo...";
+		let audioBuffer = await game.decodeAudio(songFile);
+		if(songFile != this.fileInput.files[0]){
+			return;
+		}
+		this.audioBuffer = audioBuffer;
+		this.tapButton.disabled = false;
+		this.durationField.value = Math.ceil(audioBuffer.duration);
+		
+		let candidates = game.detectTempo(audioBuffer);
+		if(candidates.length == 0){
+			this.tempoLabel.innerHTML = "No beat detected. Use tap tempo or enter the BPM manually";
+			return;
+		}
+		// the most confident candidate within the allowed range of bpms
+		let best = candidates.find(c => c.bpm >= 40 && c.bpm <= 160) || candidates[0];
+		this.setTempo(best.bpm, best.start_offset);
+		this.tempoLabel.innerHTML = "Detected BPM: " + candidates.map(c => 
+			c.bpm.toFixed(2) + " (" + Math.round(c.confidence * 100) + "%)").join(", ");
+	});
 
 	this.submitFunction = () => {
 		// validate data
@@ -650,7 +732,7 @@ function NewSongDialog(overlayParent, menuParent){
 			else if(difficulty > 10){
 				difficulty = 10;
 			}
-		let bpm = parseInt(this.bpmField.value);
+		let bpm = parseFloat(this.bpmField.value);
 			if(isNaN(bpm) || bpm < 40){
 				bpm = 40;
 			}
@@ -664,6 +746,20 @@ function NewSongDialog(overlayParent, menuParent){
 			else if(brickSpeed > 5000){
 				brickSpeed = 5000;
 			}
+		let brickTypes = parseInt(this.brickTypesField.value);
+			if(isNaN(brickTypes) || brickTypes < 1){
+				brickTypes = 1;
+			}
+			else if(brickTypes > wasm.max_brick_types()){
+				brickTypes = wasm.max_brick_types();
+			}
+		let lanes = parseInt(this.lanesField.value);
+			if(isNaN(lanes) || lanes < wasm.min_lanes()){
+				lanes = wasm.min_lanes();
+			}
+			else if(lanes > wasm.max_lanes()){
+				lanes = wasm.max_lanes();
+			}
 		let duration = parseInt(this.durationField.value);
 			if(isNaN(duration) || duration < 0){
 				duration = 0;
@@ -686,10 +782,10 @@ function NewSongDialog(overlayParent, menuParent){
 		
 		if(!songFile){
 			alert("no audio was uploaded. The previous song's audio will be used.")
-			game.newSong(name, artist, difficulty, bpm, brickSpeed, duration, songStartOffset, "", jsonFileName);
+			game.newSong(name, artist, difficulty, bpm, brickSpeed, brickTypes, lanes, duration, songStartOffset, "", jsonFileName);
 		}
 		else{
-			game.newSong(name, artist, difficulty, bpm, brickSpeed, duration, songStartOffset, songFile.name, jsonFileName);
+			game.newSong(name, artist, difficulty, bpm, brickSpeed, brickTypes, lanes, duration, songStartOffset, songFile.name, jsonFileName);
 			game.loadNewAudio(songFile);
 		}
 
@@ -711,6 +807,12 @@ function NewSongDialog(overlayParent, menuParent){
 	this.formDiv.appendChild(this.brickSpeedLabel);
 	this.formDiv.appendChild(this.brickSpeedField);
 	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.brickTypesLabel);
+	this.formDiv.appendChild(this.brickTypesField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.lanesLabel);
+	this.formDiv.appendChild(this.lanesField);
+	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.durationLabel);
 	this.formDiv.appendChild(this.durationField);
 	this.formDiv.appendChild(newLine());
@@ -721,6 +823,9 @@ function NewSongDialog(overlayParent, menuParent){
 	this.formDiv.appendChild(this.jsonNameField);
 	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.fileInput);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.tapButton);
+	this.formDiv.appendChild(this.tempoLabel);
 }
 Object.setPrototypeOf(NewSongDialog.prototype, GetInputDialog.prototype);
 
@@ -738,6 +843,10 @@ function ModifySongDialog(overlayParent, menuParent){
 	this.bpmField;
 	this.brickSpeedLabel;
 	this.brickSpeedField;
+	this.brickTypesLabel;
+	this.brickTypesField;
+	this.lanesLabel;
+	this.lanesField;
 	this.durationLabel;
 	this.durationField;
 	this.songStartOffsetLabel;
@@ -763,6 +872,10 @@ function ModifySongDialog(overlayParent, menuParent){
 	this.bpmField = document.createElement("input");
 	this.brickSpeedLabel = document.createElement("label");
 	this.brickSpeedField = document.createElement("input");
+	this.brickTypesLabel = document.createElement("label");
+	this.brickTypesField = document.createElement("input");
+	this.lanesLabel = document.createElement("label");
+	this.lanesField = document.createElement("input");
 	this.durationLabel = document.createElement("label");
 	this.durationField = document.createElement("input");
 	this.songStartOffsetLabel = document.createElement("label");
@@ -777,6 +890,8 @@ function ModifySongDialog(overlayParent, menuParent){
 	this.difficultyLabel.innerHTML = "Difficulty(0-10): ";
 	this.bpmLabel.innerHTML = "BPM(40-160): ";
 	this.brickSpeedLabel.innerHTML = "Brick Speed(100-5000): ";
+	this.brickTypesLabel.innerHTML = "Brick colors(1-" + wasm.max_brick_types() + "): ";
+	this.lanesLabel.innerHTML = "Lanes(" + wasm.min_lanes() + "-" + wasm.max_lanes() + "): ";
 	this.durationLabel.innerHTML = "Duration(0-600): ";
 	this.songStartOffsetLabel.innerHTML = "Song start offset (0-6): ";
 	this.jsonNameLabel.innerHTML = "Song data file (json): ";
@@ -791,6 +906,10 @@ function ModifySongDialog(overlayParent, menuParent){
 	this.bpmField.type = "text";
 	this.brickSpeedField.defaultValue = gameData.brick_speed;
 	this.brickSpeedField.type = "text";
+	this.brickTypesField.defaultValue = gameData.num_brick_types;
+	this.brickTypesField.type = "text";
+	this.lanesField.defaultValue = gameData.lanes;
+	this.lanesField.type = "text";
 	this.durationField.defaultValue = songData.duration;
 	this.durationField.type = "text";
 	this.songStartOffsetField.defaultValue = songData.startOffset;
@@ -826,6 +945,20 @@ function ModifySongDialog(overlayParent, menuParent){
 			else if(brickSpeed > 5000){
 				brickSpeed = 5000;
 			}
+		let brickTypes = parseInt(this.brickTypesField.value);
+			if(isNaN(brickTypes) || brickTypes < 1){
+				brickTypes = 1;
+			}
+			else if(brickTypes > wasm.max_brick_types()){
+				brickTypes = wasm.max_brick_types();
+			}
+		let lanes = parseInt(this.lanesField.value);
+			if(isNaN(lanes) || lanes < wasm.min_lanes()){
+				lanes = wasm.min_lanes();
+			}
+			else if(lanes > wasm.max_lanes()){
+				lanes = wasm.max_lanes();
+			}
 		let duration = parseInt(this.durationField.value);
 			if(isNaN(duration) || duration < 0){
 				duration = 0;
@@ -854,7 +987,12 @@ function ModifySongDialog(overlayParent, menuParent){
 			this.fileInputLabel.innerHTML = "Modified ";
 		}
 		
-		game.modifySong(name, artist, difficulty, bpm, brickSpeed, duration, songStartOffset, songFileName, jsonFileName);
+		try{
+			game.modifySong(name, artist, difficulty, bpm, brickSpeed, brickTypes, lanes, duration, songStartOffset, songFileName, jsonFileName);
+		}
+		catch(err){
+			alert(err);
+		}
 
 		return null;
 	}
@@ -874,6 +1012,12 @@ function ModifySongDialog(overlayParent, menuParent){
 	this.formDiv.appendChild(this.brickSpeedLabel);
 	this.formDiv.appendChild(this.brickSpeedField);
 	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.brickTypesLabel);
+	this.formDiv.appendChild(this.brickTypesField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.lanesLabel);
+	this.formDiv.appendChild(this.lanesField);
+	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.durationLabel);
 	this.formDiv.appendChild(this.durationField);
 	this.formDiv.appendChild(newLine());
@@ -897,6 +1041,8 @@ function SaveSongDialog(overlayParent, menuParent){
 	this.difficultyLabel;
 	this.bpmLabel;
 	this.brickSpeedLabel;
+	this.brickTypesLabel;
+	this.lanesLabel;
 	this.durationLabel;
 	this.songStartOffsetLabel;
 	this.jsonNameLabel;
@@ -913,6 +1059,8 @@ function SaveSongDialog(overlayParent, menuParent){
 	this.difficultyLabel = document.createElement("label");
 	this.bpmLabel = document.createElement("label");
 	this.brickSpeedLabel = document.createElement("label");
+	this.brickTypesLabel = document.createElement("label");
+	this.lanesLabel = document.createElement("label");
 	this.durationLabel = document.createElement("label");
 	this.songStartOffsetLabel = document.createElement("label");
 	this.jsonNameLabel = document.createElement("label");
@@ -922,6 +1070,8 @@ function SaveSongDialog(overlayParent, menuParent){
 	this.difficultyLabel.innerHTML = "Difficulty(0-10): " + songData.difficulty;
 	this.bpmLabel.innerHTML = "BPM(40-160): " + gameData.bpm;
 	this.brickSpeedLabel.innerHTML = "Brick Speed(100-5000): " + gameData.brick_speed;
+	this.brickTypesLabel.innerHTML = "Brick colors(1-" + wasm.max_brick_types() + "): " + gameData.num_brick_types;
+	this.lanesLabel.innerHTML = "Lanes(" + wasm.min_lanes() + "-" + wasm.max_lanes() + "): " + gameData.lanes;
 	this.durationLabel.innerHTML = "Duration(0-600): " + songData.duration;
 	this.songStartOffsetLabel.innerHTML = "Song start offset (0-6): " + songData.startOffset;
 	this.jsonNameLabel.innerHTML = "Song data file (json): " + songData.jsonname;
@@ -941,6 +1091,10 @@ function SaveSongDialog(overlayParent, menuParent){
 	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.brickSpeedLabel);
 	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.brickTypesLabel);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.lanesLabel);
+	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.durationLabel);
 	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.songStartOffsetLabel);
@@ -949,6 +1103,195 @@ function SaveSongDialog(overlayParent, menuParent){
 }
 Object.setPrototypeOf(SaveSongDialog.prototype, GetInputDialog.prototype);
 
+// class for creating a dialog to transform all notes within a range of beats. extends GetInputDialog
+function TransformDialog(overlayParent, menuParent){
+	GetInputDialog.call(this, overlayParent, menuParent);
+	
+	this.operationLabel;
+	this.operationField;
+	this.startBeatLabel;
+	this.startBeatField;
+	this.endBeatLabel;
+	this.endBeatField;
+	this.argLabel;
+	this.argField;
+
+	this.formTitle.innerHTML = "Transform Notes";
+
+	let game = overlayParent.getGame();
+	let gameData = game.getGameData();
+	let newLine = () => { return document.createElement("br"); }
+
+	this.operationLabel = document.createElement("label");
+	this.operationField = document.createElement("select");
+	this.startBeatLabel = document.createElement("label");
+	this.startBeatField = document.createElement("input");
+	this.endBeatLabel = document.createElement("label");
+	this.endBeatField = document.createElement("input");
+	this.argLabel = document.createElement("label");
+	this.argField = document.createElement("input");
+	
+	let operations = [
+		["set-type", "Set brick type", "Brick type (1-3): ", "1"],
+		["cycle-types", "Cycle brick types", "", ""],
+		["mirror", "Mirror lanes", "", ""],
+		["shift-lanes", "Shift lanes", "Lane offset: ", "1"],
+		["stretch", "Stretch timing", "Ratio: ", "2"],
+		["taps-to-holds", "Taps to holds", "Hold length (beats): ", "1"],
+		["holds-to-taps", "Holds to taps", "", ""],
+		["requantize", "Requantize", "Grid (beats): ", "0.5"]
+	];
+	operations.forEach( operation => {
+		let option = document.createElement("option");
+		option.value = operation[0];
+		option.innerHTML = operation[1];
+		this.operationField.appendChild(option);
+	});
+	let updateArg = () => {
+		let operation = operations.find(o => o[0] == this.operationField.value);
+		this.argLabel.innerHTML = operation[2];
+		this.argField.value = operation[3];
+		this.argField.hidden = operation[2] == "";
+	}
+	this.operationField.addEventListener("change", updateArg);
+	updateArg();
+
+	// default to the range of the selected bricks, or the next 4 measures
+	let isEditor = typeof(game.transformNotes) === "function";
+	let selected = isEditor ? game.selectedBricks() : [];
+	let startBeatPos = wasm.BrickData.closest_beat_pos(gameData.time_running, gameData.bpm);
+	let endBeatPos = startBeatPos + 64;
+	if(selected.length > 0){
+		startBeatPos = Math.min(...selected.map(brick => brick.beat_pos));
+		endBeatPos = Math.max(...selected.map(brick => brick.beat_pos));
+	}
+
+	this.operationLabel.innerHTML = "Operation: ";
+	this.startBeatLabel.innerHTML = "From beat: ";
+	this.endBeatLabel.innerHTML = "To beat (inclusive): ";
+	this.startBeatField.type = "text";
+	this.startBeatField.defaultValue = startBeatPos / 4;
+	this.endBeatField.type = "text";
+	this.endBeatField.defaultValue = endBeatPos / 4;
+	this.argField.type = "text";
+
+	this.submitFunction = () => {
+		if(!isEditor){
+			alert("Enable the editor to transform notes");
+			return null;
+		}
+		
+		let operation = this.operationField.value;
+		let startBeatPos = Math.round(parseFloat(this.startBeatField.value) * 4);
+		let endBeatPos = Math.round(parseFloat(this.endBeatField.value) * 4);
+		if(isNaN(startBeatPos) || isNaN(endBeatPos)){
+			alert("The beat range is invalid");
+			return null;
+		}
+		
+		let arg = parseFloat(this.argField.value);
+		if(operation == "set-type"){
+			arg = Math.min(Math.max(Math.round(arg) - 1, 0), game.getGameData().num_brick_types - 1);
+		} else if(operation == "taps-to-holds" || operation == "requantize"){
+			arg = Math.round(arg * 4); // beats to beat positions
+		} else if(operation == "shift-lanes"){
+			arg = Math.round(arg);
+		}
+		
+		try{
+			let edit = game.transformNotes(operation, startBeatPos, endBeatPos, arg);
+			if(!edit.applied){
+				let collisions = edit.collisions();
+				alert("The notes were not changed. " + (edit.out_of_range ? "The bricks would go past the edge of the song or use a brick color the song doesn't have. " : "") 
+					+ (collisions.length > 0 ? collisions.length + " bricks would overlap." : ""));
+			}
+		}
+		catch(err){
+			alert(err);
+		}
+		
+		return "pre-render";
+	}
+
+	this.formDiv.appendChild(this.operationLabel);
+	this.formDiv.appendChild(this.operationField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.startBeatLabel);
+	this.formDiv.appendChild(this.startBeatField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.endBeatLabel);
+	this.formDiv.appendChild(this.endBeatField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.argLabel);
+	this.formDiv.appendChild(this.argField);
+}
+Object.setPrototypeOf(TransformDialog.prototype, GetInputDialog.prototype);
+
+// class for creating a dialog to generate the song's notes from its audio. extends GetInputDialog
+function GenerateChartDialog(overlayParent, menuParent){
+	GetInputDialog.call(this, overlayParent, menuParent);
+	
+	this.densityLabel;
+	this.densityField;
+	this.difficultyLabel;
+	this.difficultyField;
+	
+	this.formTitle.innerHTML = "Generate Notes From Audio (replaces the song's notes)";
+	
+	let game = overlayParent.getGame();
+	let defaults = wasm.ChartGeneratorSettings.new();
+	let newLine = () => { return document.createElement("br"); }
+	
+	this.densityLabel = document.createElement("label");
+	this.densityLabel.innerHTML = "Density (0 to 1): ";
+	this.densityField = document.createElement("input");
+	this.densityField.type = "text";
+	this.densityField.defaultValue = defaults.density.toFixed(2);
+	
+	this.difficultyLabel = document.createElement("label");
+	this.difficultyLabel.innerHTML = "Difficulty (1 to 10): ";
+	this.difficultyField = document.createElement("input");
+	this.difficultyField.type = "text";
+	this.difficultyField.defaultValue = defaults.difficulty;
+	defaults.free();
+	
+	this.submitFunction = () => {
+		let density = parseFloat(this.densityField.value);
+		let difficulty = parseInt(this.difficultyField.value);
+		if(isNaN(density) || density < 0 || density > 1){
+			alert("Density must be from 0 to 1");
+			return null;
+		}
+		if(isNaN(difficulty) || difficulty < 1 || difficulty > 10){
+			alert("Difficulty must be from 1 to 10");
+			return null;
+		}
+		
+		let gameData = game.getGameData();
+		let settings = wasm.ChartGeneratorSettings.new();
+		settings.density = density;
+		settings.difficulty = difficulty;
+		settings.num_brick_types = gameData.num_brick_types;
+		settings.lanes = gameData.lanes;
+		
+		try{
+			game.generateChart(settings);
+		}
+		catch(err){
+			alert(err);
+		}
+		
+		return "pre-render";
+	}
+	
+	this.formDiv.appendChild(this.densityLabel);
+	this.formDiv.appendChild(this.densityField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.difficultyLabel);
+	this.formDiv.appendChild(this.difficultyField);
+}
+Object.setPrototypeOf(GenerateChartDialog.prototype, GetInputDialog.prototype);
+
 // class for creating a dialog to load song data from the database. extends GetInputDialog
 function LoadSongDialog(overlayParent, menuParent){
 	GetInputDialog.call(this, overlayParent, menuParent);
@@ -967,14 +1310,56 @@ function LoadSongDialog(overlayParent, menuParent){
 	this.jsonFileInput.innerHTML = "Song data file (json)";
 	this.jsonFileInput.type = "file";
 	
+	this.midiFileLabel = document.createElement("label");
+	this.midiFileLabel.innerHTML = "Or MIDI / osu!mania / StepMania File To Generate Notes From: ";
+	this.midiFileInput = document.createElement("input");
+	this.midiFileInput.innerHTML = "Notes file (mid/osu/sm)";
+	this.midiFileInput.type = "file";
+	
+	this.channelsLabel = document.createElement("label");
+	this.channelsLabel.innerHTML = "MIDI Channels (1-16, comma separated, blank for all): ";
+	this.channelsField = document.createElement("input");
+	
+	this.programLabel = document.createElement("label");
+	this.programLabel.innerHTML = "MIDI Instrument Program (1-128, blank for all): ";
+	this.programField = document.createElement("input");
+	
+	this.maxNotesLabel = document.createElement("label");
+	this.maxNotesLabel.innerHTML = "Max Notes Per Beat (0 for no cap): ";
+	this.maxNotesField = document.createElement("input");
+	this.maxNotesField.value = "0";
+	
+	this.quantizationLabel = document.createElement("label");
+	this.quantizationLabel.innerHTML = "Quantization (beats): ";
+	this.quantizationField = document.createElement("input");
+	this.quantizationField.value = "0.25";
+	
+	this.exportedChartLabel = document.createElement("label");
+	this.exportedChartLabel.innerHTML = "MIDI File Is An Exported Chart: ";
+	this.exportedChartInput = document.createElement("input");
+	this.exportedChartInput.type = "checkbox";
+	
 	let newLine = () => { return document.createElement("br"); }
 	
 	this.submitFunction = () => {
 		let songFile = this.songFileInput.files[0];
 		
 		let jsonFile = this.jsonFileInput.files[0];
+		let midiFile = this.midiFileInput.files[0];
 		
-		game.userLoadSong(songFile, jsonFile);
+		if(!jsonFile && midiFile && /\.(osu|sm)$/i.test(midiFile.name)){
+			game.userLoadChart(songFile, midiFile);
+		}
+		else if(!jsonFile && midiFile){
+			let midiImportOptions = this.midiImportOptions();
+			if(midiImportOptions === null){
+				return null;
+			}
+			game.userLoadMidi(songFile, midiFile, midiImportOptions);
+		}
+		else{
+			game.userLoadSong(songFile, jsonFile);
+		}
 		return "wait-song-load";
 	};
 	
@@ -983,9 +1368,75 @@ function LoadSongDialog(overlayParent, menuParent){
 	this.formDiv.appendChild(newLine());
 	this.formDiv.appendChild(this.jsonFileLabel);
 	this.formDiv.appendChild(this.jsonFileInput);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.midiFileLabel);
+	this.formDiv.appendChild(this.midiFileInput);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.channelsLabel);
+	this.formDiv.appendChild(this.channelsField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.programLabel);
+	this.formDiv.appendChild(this.programField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.maxNotesLabel);
+	this.formDiv.appendChild(this.maxNotesField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.quantizationLabel);
+	this.formDiv.appendChild(this.quantizationField);
+	this.formDiv.appendChild(newLine());
+	this.formDiv.appendChild(this.exportedChartLabel);
+	this.formDiv.appendChild(this.exportedChartInput);
 }
 Object.setPrototypeOf(LoadSongDialog.prototype, GetInputDialog.prototype);
 
+// the MIDI import options entered in the dialog, or null after alerting the user if any are invalid
+LoadSongDialog.prototype.midiImportOptions = function(){
+	// charts exported from the editor are read back into the same bricks, so the other options don't apply
+	if(this.exportedChartInput.checked){
+		return wasm.MidiImportOptions.chart();
+	}
+	
+	let options = wasm.MidiImportOptions.new();
+	
+	let channels = this.channelsField.value.split(",").map(c => c.trim()).filter(c => c != "");
+	if(channels.length > 0){
+		options.channels = 0;
+		for(let c of channels){
+			let channel = parseInt(c);
+			if(isNaN(channel) || channel < 1 || channel > 16){
+				alert("MIDI channels must be from 1 to 16");
+				return null;
+			}
+			options.channels |= 1 << (channel - 1);
+		}
+	}
+	
+	if(this.programField.value.trim() != ""){
+		let program = parseInt(this.programField.value);
+		if(isNaN(program) || program < 1 || program > 128){
+			alert("The MIDI program must be from 1 to 128");
+			return null;
+		}
+		options.program = program - 1;
+	}
+	
+	let maxNotes = parseInt(this.maxNotesField.value);
+	if(isNaN(maxNotes) || maxNotes < 0 || maxNotes > 255){
+		alert("Max notes per beat must be from 0 to 255");
+		return null;
+	}
+	options.max_notes_per_beat = maxNotes;
+	
+	let quantization = Math.round(parseFloat(this.quantizationField.value) * 4); // beats to beat positions
+	if(isNaN(quantization) || quantization < 1){
+		alert("Quantization must be at least 0.25 beats");
+		return null;
+	}
+	options.quantization = quantization;
+	
+	return options;
+}
+
 Overlay.prototype.preInit = function(initFunction){
 	this.preInitScreen = document.createElement("div");
 	this.preInitScreen.id = "pre-init-screen";
@@ -1180,6 +1631,13 @@ Overlay.prototype.passEvent = function(evt){
 
 // TODO updating song data should update score?
 Overlay.prototype.update = function(data=null){
+	if(this.currentOverlay instanceof EditorOverlay){
+		if(!data){
+			throw Error("No data to update overlay");
+		}
+		this.currentOverlay.updateViewport(data.viewport);
+	}
+
 	this.currentOverlay.update();
 
 	if(this.currentOverlay instanceof GameOverlay){
@@ -1187,13 +1645,6 @@ Overlay.prototype.update = function(data=null){
 			this.currentOverlay.updateFPS(data.fps);
 		}
 	}
-
-	if(this.currentOverlay instanceof EditorOverlay){
-		if(!data){
-			throw Error("No data to update overlay");
-		}
-		this.currentOverlay.updateDimFactors(data.xFactor, data.yFactor);
-	}
 }
 
 Overlay.prototype.getGame = function(){
@@ -1281,6 +1732,9 @@ HomeScreen.prototype.update = function(){
 	let timeCreatedIDX;
 	let timeModifiedIDX;
 	
+	// easiest songs first
+	songs.sort((a, b) => a.rating - b.rating);
+	
 	if(songs.length != 0){
 		songs.forEach( songData => {
 			let selection = new HomeSelection(songData);
@@ -1347,9 +1801,23 @@ EditorOverlay.prototype.update = function(){
 	this.draw();
 }
 
-EditorOverlay.prototype.updateDimFactors = function(xFactor, yFactor){
-	this.xFactor = xFactor;
-	this.yFactor = yFactor;
+// sizes the canvas to the screen of the viewport, keeping a copy of the viewport to place drawings and read mouse positions with
+EditorOverlay.prototype.updateViewport = function(viewport){
+	if(viewport.screen_width == this.viewport.screen_width && viewport.screen_height == this.viewport.screen_height){
+		return;
+	}
+	
+	this.viewport.free();
+	this.viewport = wasm.Viewport.new(viewport.screen_width, viewport.screen_height);
+	this.canvas.width = this.viewport.screen_width;
+	this.canvas.height = this.viewport.screen_height;
+}
+
+// the world position of a mouse event
+EditorOverlay.prototype.eventToWorld = function(evt){
+	let x = evt.clientX - this.canvas.offsetLeft;
+	let y = evt.clientY - this.canvas.offsetTop;
+	return this.viewport.world_position(x, y);
 }
 
 EditorOverlay.prototype.domElement = function(){
@@ -1380,11 +1848,11 @@ EditorOverlay.prototype.yToTime = function(y){
 }
 
 EditorOverlay.prototype.xToNotePos = function(x){
-	return Math.floor(x / wasm.brick_dimensions().x);
+	return Math.floor(x / this.overlayParent.getGame().getBrickDimensions().x);
 }
 
 EditorOverlay.prototype.notePosToX = function(notePos){
-	return x * wasm.brick_dimensions().x;
+	return x * this.overlayParent.getGame().getBrickDimensions().x;
 }
 
 EditorOverlay.prototype.draw = function(){
@@ -1392,84 +1860,31 @@ EditorOverlay.prototype.draw = function(){
 	let songData = game.getSongData();
 	let gameData = game.getGameData();
 
+	let viewport = this.viewport;
 	let ctx = this.canvas.getContext("2d");
+	ctx.setTransform(1, 0, 0, 1, 0, 0);
 	ctx.clearRect(0, 0, this.canvas.width, this.canvas.height);
 	
-	let time = gameData.time_running;
-	
-	// get the beat positions that mark when lines should start and stop being drawn
-	let topScreenTime = time - (wasm.ground_pos() / gameData.brick_speed);
-	let bottomScreenTime = time + (wasm.game_dimensions().y / gameData.brick_speed);
-	let beginningBeatPos = wasm.BrickData.closest_beat_pos(topScreenTime, gameData.bpm);
-	let endBeatPos = wasm.BrickData.closest_beat_pos(bottomScreenTime, gameData.bpm);
-	let screenWidth = wasm.game_dimensions().x;
-	
-	// draw lines at the specified beat positions
-	for(let i = beginningBeatPos; i < endBeatPos; ++i) {
-		let beatTime = wasm.BrickData.approx_time(i, gameData.bpm);
-		let y = this.timeToY(beatTime) + wasm.brick_dimensions().y / 2;
-		if(i % 4 == 0){
-			ctx.fillRect(0, y-1, this.canvas.width, 3);
-			for(let x = 0; x <= screenWidth; x+=wasm.brick_dimensions().x){
-				ctx.fillRect(x-1, y-4, 3, 8);
-			}
-		} else if(i % 2 == 0){
-			ctx.fillRect(0, y, this.canvas.width, 1);
-		} else {
-			ctx.beginPath();
-			ctx.setLineDash([6, 16]);
-			ctx.lineWidth = 1;
-			ctx.moveTo(0, y);
-			ctx.lineTo(this.canvas.width, y);
-			ctx.stroke();
-		}
-	}
-	
-	// draw a highlight box around the selected brick
+	// draw a highlight box around the selected brick, in world units like the game. The grid and bricks selected together are drawn by the game
 	if(this.selectedBrick){
-		let brickDims = wasm.brick_dimensions();
-		let startX = this.selectedBrick.x_pos * brickDims.x;
-		let startTime = wasm.BrickData.approx_time(this.selectedBrick.beat_pos, gameData.bpm);
-		let endTime = wasm.BrickData.approx_time(this.selectedBrick.end_beat_pos, gameData.bpm);
-		let startY = this.timeToY(startTime);
-		let endY = this.timeToY(endTime) + wasm.brick_dimensions().y;
-		if(this.selectedBrick.is_leading || this.selectedBrick.is_trailing){
-			let minutesPerBeat = 1 / gameData.bpm;
-			let secondsPerBeat = 60 * minutesPerBeat;
-			let secondsPerEighthBeat = secondsPerBeat / 8;
-			let difference = secondsPerEighthBeat * gameData.brick_speed;
-			
-			if(this.selectedBrick.is_leading){
-				startY -= difference;
-				if(!this.selectedBrick.is_hold_note){
-					endY -= difference;
-				}
-			}
-			if(this.selectedBrick.is_trailing){
-				startY += difference;
-				if(!this.selectedBrick.is_hold_note){
-					endY += difference;
-				}
-			}
-		}
-		
-		ctx.setLineDash([]);
+		ctx.setTransform(viewport.scale, 0, 0, viewport.scale, viewport.offset_x, viewport.offset_y);
 		ctx.lineWidth = 3;
-		ctx.strokeRect(startX, startY, brickDims.x, endY - startY);
+		this.strokeBrick(ctx, this.selectedBrick);
+		ctx.setTransform(1, 0, 0, 1, 0, 0);
 	}
 	
 	// draw the song data buffer / song transcript
 	{
 		let songBuffer = game.getSongBuffer();
 		let imageDataWidth = this.songTranscriptWidth;
-		let imageDataHeight = wasm.game_dimensions().y;
+		let imageDataHeight = Math.floor(wasm.game_dimensions().y * viewport.scale); // in pixels, as image data isn't transformed
 		let songTranscriptData = new Uint8ClampedArray(imageDataWidth * imageDataHeight * 4);
 		
 		let bufferData = songBuffer.getChannelData(0);
 		let startingSample = Math.floor(
 			gameData.time_running * songBuffer.sampleRate // time of the song
 			+ songData.startOffset * songBuffer.sampleRate // plus start offset
-			- (wasm.time_zero_brick_pos() + wasm.brick_dimensions().y / 2)  / songData.brickSpeed * songBuffer.sampleRate); // adjusted to notes start position
+			- (wasm.time_zero_brick_pos() + game.getBrickDimensions().y / 2)  / songData.brickSpeed * songBuffer.sampleRate); // adjusted to notes start position
 
 		let endingSample = Math.floor(startingSample + (wasm.game_dimensions().y / songData.brickSpeed) * songBuffer.sampleRate);
 		
@@ -1485,7 +1900,7 @@ EditorOverlay.prototype.draw = function(){
 			let timeFromTop = (i - startingSample) / songBuffer.sampleRate;
 			
 			let x = Math.floor(bufferData[i] * imageDataWidth / 2.0 + (imageDataWidth / 2.0)); // amplitude of the sample set in range 0 - imageDataWidth
-			let y = Math.floor(timeFromTop * songData.brickSpeed); // time of the sample transcribed onto game
+			let y = Math.floor(timeFromTop * songData.brickSpeed * viewport.scale); // time of the sample transcribed onto game
 			let dataIdx = Math.floor((y * imageDataWidth * 4) + (x * 4));
 			
 			// set RGBA to 40,40,40,255 (solid black)
@@ -1497,10 +1912,67 @@ EditorOverlay.prototype.draw = function(){
 		
 		// create a new ImageData object for the song transcript, then draw it on the canvas
 		let songTranscriptImageData = new ImageData(songTranscriptData, imageDataWidth);
-		ctx.putImageData(songTranscriptImageData, 0, 0);
+		ctx.putImageData(songTranscriptImageData, Math.floor(viewport.offset_x), Math.floor(viewport.offset_y));
 	}
 }
 
+// outlines the area of the brick
+EditorOverlay.prototype.strokeBrick = function(ctx, brick){
+	let gameData = this.overlayParent.getGame().getGameData();
+	let brickDims = this.overlayParent.getGame().getBrickDimensions();
+	let startX = brick.x_pos * brickDims.x;
+	let startTime = wasm.BrickData.approx_time(brick.beat_pos, gameData.bpm);
+	let endTime = wasm.BrickData.approx_time(brick.end_beat_pos, gameData.bpm);
+	let startY = this.timeToY(startTime);
+	let endY = this.timeToY(endTime) + brickDims.y;
+	if(brick.is_leading || brick.is_trailing){
+		let minutesPerBeat = 1 / gameData.bpm;
+		let secondsPerBeat = 60 * minutesPerBeat;
+		let secondsPerEighthBeat = secondsPerBeat / 8;
+		let difference = secondsPerEighthBeat * gameData.brick_speed;
+		
+		if(brick.is_leading){
+			startY -= difference;
+			if(!brick.is_hold_note){
+				endY -= difference;
+			}
+		}
+		if(brick.is_trailing){
+			startY += difference;
+			if(!brick.is_hold_note){
+				endY += difference;
+			}
+		}
+	}
+	
+	ctx.strokeRect(startX, startY, brickDims.x, endY - startY);
+}
+
+// runs an editor call, showing the reason in the status label if it throws an edit error. Returns the edit outcome, or null if it failed
+EditorOverlay.prototype.tryEdit = function(edit){
+	try{
+		let outcome = edit();
+		this.statusLabel.innerHTML = "";
+		return outcome;
+	}
+	catch(err){
+		if(!(err instanceof wasm.EditError)){
+			throw err;
+		}
+		this.statusLabel.innerHTML = err.message;
+		return null;
+	}
+}
+
+// replaces the selected brick's original fields with the brick's fields, keeping whichever ends up in the notes selected
+EditorOverlay.prototype.moveSelectedBrick = function(original, brick){
+	let game = this.overlayParent.getGame();
+	if(this.tryEdit( () => game.moveBrick(original, brick) ) === null){
+		brick = original;
+	}
+	this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+}
+
 EditorOverlay.prototype.handleEvent = function(evt){
 	let game = this.overlayParent.getGame();
 	let songData = game.getSongData();
@@ -1547,20 +2019,85 @@ EditorOverlay.prototype.handleKeyDown = function(evt){
 		return("toggle-play");
 	}
 	
+	if(!evt.ctrlKey && evt.keyCode == 80){ // p shows or hides the player's planned path
+		this.overlayParent.getGame().toggleShowPath();
+		return "pre-render";
+	}
+	
+	if(evt.ctrlKey && (evt.keyCode == 90 || evt.keyCode == 89)){ // ctrl+z to undo, ctrl+y or ctrl+shift+z to redo
+		let game = this.overlayParent.getGame();
+		if(evt.keyCode == 89 || evt.shiftKey){
+			game.redo();
+		} else {
+			game.undo();
+		}
+		
+		this.selectedBrick = null;
+		this.draw();
+		return "pre-render";
+	}
+	
+	// multiple selected bricks
+	{
+		let game = this.overlayParent.getGame();
+		let gameData = game.getGameData();
+		let edit = null;
+		let deleted = false;
+		
+		if(evt.ctrlKey && evt.keyCode == 67){ // ctrl+c
+			game.copySelection();
+			return null;
+		}
+		else if(evt.ctrlKey && evt.keyCode == 88){ // ctrl+x
+			game.cutSelection();
+		}
+		else if(evt.ctrlKey && evt.keyCode == 86){ // ctrl+v. paste at the current time
+			this.selectedBrick = null;
+			edit = game.paste(wasm.BrickData.closest_beat_pos(gameData.time_running, gameData.bpm));
+		}
+		else if(game.selectedBricks().length > 0){
+			if(evt.keyCode == 46 || evt.keyCode == 8){ // delete or backspace
+				game.deleteSelection();
+				deleted = true;
+			}
+			else if(evt.shiftKey && evt.keyCode >= 37 && evt.keyCode <= 40){ // shift+arrow keys move the selection
+				let beatOffset = evt.keyCode == 38 ? -1 : evt.keyCode == 40 ? 1 : 0;
+				let laneOffset = evt.keyCode == 37 ? -1 : evt.keyCode == 39 ? 1 : 0;
+				edit = game.moveSelection(beatOffset, laneOffset);
+			}
+			else{
+				return null;
+			}
+		}
+		else if(evt.ctrlKey){
+			return null;
+		}
+		
+		if(edit && !edit.applied){
+			let collisions = edit.collisions();
+			alert((edit.out_of_range ? "The bricks would go past the edge of the song or use a brick color the song doesn't have. " : "") 
+				+ (collisions.length > 0 ? collisions.length + " bricks are in the way." : ""));
+		}
+		
+		// other keys fall through to the single selected brick
+		if(evt.ctrlKey || edit || deleted){
+			this.draw();
+			return "pre-render";
+		}
+	}
+	
 	if(this.selectedBrick){
 		let brick = this.selectedBrick;
 		let game = this.overlayParent.getGame();
 		
 		if(evt.keyCode == 46 || evt.keyCode == 8) { // delete or backspace
-			let game = this.overlayParent.getGame();
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			this.tryEdit( () => game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
+				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note) );
 			this.selectedBrick = null;
 		}
 		
 		if(evt.keyCode == 38){ // up arrow. delete the old brick, move the brick up and recreate
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			let original = copyBrick(brick);
 			
 			if(brick.is_trailing){
 				brick.is_trailing = false;
@@ -1572,14 +2109,11 @@ EditorOverlay.prototype.handleKeyDown = function(evt){
 				brick.is_leading = true;
 			}
 			
-			game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-			this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+			this.moveSelectedBrick(original, brick);
 		}
 		
 		if(evt.keyCode == 40){ // down arrow. delete the old brick, move the brick down and recreate
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			let original = copyBrick(brick);
 				
 			if(brick.is_leading){
 				brick.is_leading = false;
@@ -1591,31 +2125,23 @@ EditorOverlay.prototype.handleKeyDown = function(evt){
 				brick.is_trailing = true;
 			}
 			
-			game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-			this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+			this.moveSelectedBrick(original, brick);
 		}
 		
 		if(evt.keyCode == 37){ // left arrow. delete the old brick, move the brick to the left and recreate
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			let original = copyBrick(brick);
 				
 			brick.x_pos -= brick.x_pos > 0 ? 1 : 0;
 			
-			game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-			this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+			this.moveSelectedBrick(original, brick);
 		}
 		
 		if(evt.keyCode == 39){ // right arrow. delete the old brick, move the brick to the right and recreate
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			let original = copyBrick(brick);
 				
-			brick.x_pos += brick.x_pos + 1 < wasm.max_notes_per_screen_width() ? 1 : 0;
+			brick.x_pos += brick.x_pos + 1 < this.overlayParent.getGame().getGameData().lanes ? 1 : 0;
 			
-			game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-			this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+			this.moveSelectedBrick(original, brick);
 		}
 		
 		this.draw();
@@ -1633,24 +2159,34 @@ EditorOverlay.prototype.handleMouseDown = function(evt){
 	let game = this.overlayParent.getGame();
 	let songData = game.getSongData();
 	let gameData = game.getGameData();
-	let x = evt.clientX - this.canvas.offsetLeft;
-	let y = evt.clientY - this.canvas.offsetTop;
+	let pos = this.eventToWorld(evt);
+	let hit = game.hitTest(pos.x, pos.y);
+	let xPos = hit.x_pos;
+	let beatPos = hit.beat_pos;
 	
-	x = x / this.xFactor;
-	y = y / this.yFactor;
-	let approxTime = this.yToTime(y);
-	let xPos = this.xToNotePos(x);
-	let beatPos = wasm.BrickData.closest_beat_pos(approxTime, gameData.bpm);
+	// shift+drag selects the bricks in a rectangle
+	if(evt.shiftKey){
+		this.selectedBrick = null;
+		this.selectionStart = { beatPos: beatPos, xPos: xPos };
+		return "stop-loop";
+	}
+	game.clearSelection();
 	
-	let brick = game.selectBrick(beatPos, xPos);
+	let brick = hit.brick();
 	
-	if(brick){
+	// creating, dragging, and changing the type of a brick with the mouse is one edit, ended on mouse up
+	this.beginMouseEdit();
+	
+	if(brick && hit.kind == wasm.HitKind.HoldSegment){
+		// dragging a hold note's segments moves its end
+		this.resizingHold = true;
+	} else if(brick){
 		// if clicking on an already selected brick, indicate to change the brick type
 		if(this.selectedBrick && this.selectedBrick.beat_pos == brick.beat_pos && this.selectedBrick.x_pos == brick.x_pos){
 			this.changeBrickType = true;
 		}
 	} else {
-		game.createDefaultBrick(beatPos, xPos);
+		this.tryEdit( () => game.createDefaultBrick(beatPos, xPos) );
 		brick = game.selectBrick(beatPos, xPos);
 	}
 	
@@ -1663,23 +2199,60 @@ EditorOverlay.prototype.handleMouseDown = function(evt){
 	return "stop-loop";
 }
 
+// opens the edit group of a mouse edit. The mouse up ends it, or if the mouse is released where the overlay doesn't get the event
+	// (like over a menu) or the window loses focus, the window listener does, so later edits aren't merged into it
+EditorOverlay.prototype.beginMouseEdit = function(){
+	this.endMouseEdit();
+	this.overlayParent.getGame().beginEditGroup();
+
+	this.mouseEditEnder = () => {
+		this.endMouseEdit();
+		this.mouseDown = false;
+		this.resizingHold = false;
+		this.changeBrickType = false;
+	};
+	window.addEventListener("mouseup", this.mouseEditEnder);
+	window.addEventListener("blur", this.mouseEditEnder);
+}
+
+EditorOverlay.prototype.endMouseEdit = function(){
+	if(!this.mouseEditEnder){
+		return;
+	}
+	window.removeEventListener("mouseup", this.mouseEditEnder);
+	window.removeEventListener("blur", this.mouseEditEnder);
+	this.mouseEditEnder = null;
+	this.overlayParent.getGame().endEditGroup();
+}
+
 EditorOverlay.prototype.handleMouseUp = function(evt){
+	if(this.selectionStart){
+		let game = this.overlayParent.getGame();
+		let gameData = game.getGameData();
+		let pos = this.eventToWorld(evt);
+		let hit = game.hitTest(pos.x, pos.y);
+		
+		game.selectBricks(this.selectionStart.beatPos, hit.beat_pos, this.selectionStart.xPos, hit.x_pos);
+		this.selectionStart = null;
+		
+		this.draw();
+		return "pre-render";
+	}
+	
+	if(this.mouseDown && !this.changeBrickType){
+		this.endMouseEdit();
+	}
 	this.mouseDown = false;
+	this.resizingHold = false;
 	if(this.changeBrickType){
 		let game = this.overlayParent.getGame();
 		let brick = this.selectedBrick;
-		game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-			brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+		let original = copyBrick(brick);
 		
-		if(brick.brick_type < 2){
-			brick.brick_type += 1;
-		} else {
-			brick.brick_type = 0;
-		}
+		brick.brick_type = (brick.brick_type + 1) % game.getGameData().num_brick_types;
 		
-		game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-			brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-		this.selectedBrick = game.selectBrick(brick.beat_pos, brick.x_pos);
+		this.moveSelectedBrick(original, brick);
+		this.endMouseEdit();
 
 		this.changeBrickType = false;
 
@@ -1699,22 +2272,35 @@ EditorOverlay.prototype.handleMouseMove = function(evt){
 		let game = this.overlayParent.getGame();
 		let songData = game.getSongData();
 		let gameData = game.getGameData();
-		let x = evt.clientX - this.canvas.offsetLeft;
-		let y = evt.clientY - this.canvas.offsetTop;
+		let pos = this.eventToWorld(evt);
+		let hit = game.hitTest(pos.x, pos.y);
+		let xPos = hit.x_pos;
+		let beatPos = hit.beat_pos;
 		
-		x = x / this.xFactor;
-		y = y / this.yFactor;
-		let approxTime = this.yToTime(y);
-		let xPos = this.xToNotePos(x);
-		let beatPos = wasm.BrickData.closest_beat_pos(approxTime, gameData.bpm);
+		// move the end of the hold note, showing how many segments it will have
+		if(this.resizingHold){
+			let brick = this.selectedBrick;
+			if(beatPos == brick.end_beat_pos){
+				return null;
+			}
+			
+			let original = copyBrick(brick);
+			let numSegments = game.holdSegmentPreview(original, beatPos);
+			if(this.tryEdit( () => game.resizeHold(original, beatPos) ) !== null){
+				this.statusLabel.innerHTML = numSegments + " hold segments";
+			}
+			this.selectedBrick = game.selectBrick(original.beat_pos, original.x_pos);
+			
+			this.draw();
+			return "pre-render";
+		}
 		
 		// if the beat pos or the x pos has changed, move the brick
 		if(beatPos != this.selectedBrick.end_beat_pos || xPos != this.selectedBrick.x_pos){
 			this.changeBrickType = false;
 			
 			let brick = this.selectedBrick;
-			game.removeBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
+			let original = copyBrick(brick);
 			
 			if(beatPos > brick.beat_pos){
 				brick.is_hold_note = true;
@@ -1729,10 +2315,7 @@ EditorOverlay.prototype.handleMouseMove = function(evt){
 				brick.x_pos = xPos;
 			}
 			
-			game.createBrick(brick.brick_type, brick.beat_pos, brick.end_beat_pos, brick.x_pos, 
-				brick.is_triplet, brick.is_trailing, brick.is_leading, brick.is_hold_note);
-				
-			this.selectedBrick = game.selectBrick(beatPos, xPos);
+			this.moveSelectedBrick(original, brick);
 			
 			this.draw();
 			return "pre-render";
@@ -1950,3 +2533,67 @@ ChangeControlDialog.prototype.handleEvent = function(evt){
 
 	GetInputDialog.prototype.handleEvent.call(this, evt);
 }
+
+NewSongDialog.prototype.handleEvent = function(evt){
+	if(evt.type == "click" && evt.target == this.tapButton){
+		this.tap();
+		return null;
+	}
+
+	if((evt.type == "keydown" && evt.keyCode == 27) 
+	|| (evt.type == "click" && (evt.target == this.submitButton || evt.target == this.cancelButton))){
+		this.stopPreview();
+	}
+
+	return GetInputDialog.prototype.handleEvent.call(this, evt);
+}
+
+// the first tap starts playing the uploaded audio, following taps are timed against it to estimate the tempo
+NewSongDialog.prototype.tap = function(){
+	let audioContext = this.overlayParent.getGame().audioContext;
+	if(!this.previewSource){
+		this.previewSource = audioContext.createBufferSource();
+		this.previewSource.buffer = this.audioBuffer;
+		this.previewSource.connect(audioContext.destination);
+		this.previewSource.start();
+		this.previewStartTime = audioContext.currentTime;
+		this.taps = [];
+		this.tapButton.innerHTML = "Tap Along To The Beat";
+		return;
+	}
+
+	this.taps.push(audioContext.currentTime - this.previewStartTime);
+	if(this.taps.length < 2){
+		return;
+	}
+
+	try{
+		let estimate = wasm.tap_tempo(new Float32Array(this.taps));
+		this.setTempo(estimate.bpm, estimate.start_offset);
+		this.tempoLabel.innerHTML = "Tapped BPM: " + estimate.bpm.toFixed(2) + " (" + Math.round(estimate.confidence * 100) + "% steady)";
+	}
+	catch(err){
+		this.tempoLabel.innerHTML = err;
+	}
+}
+
+NewSongDialog.prototype.stopPreview = function(){
+	if(this.previewSource){
+		this.previewSource.stop();
+		this.previewSource = null;
+	}
+	this.tapButton.innerHTML = "Tap Tempo";
+}
+
+NewSongDialog.prototype.setTempo = function(bpm, startOffset){
+	this.bpmField.value = bpm.toFixed(2);
+	this.songStartOffsetField.value = startOffset.toFixed(3);
+}
+
+// a plain copy of a brick's fields, unaffected by later changes to the brick
+function copyBrick(brick){
+	return {
+		brick_type: brick.brick_type, beat_pos: brick.beat_pos, end_beat_pos: brick.end_beat_pos, x_pos: brick.x_pos, 
+		is_triplet: brick.is_triplet, is_trailing: brick.is_trailing, is_leading: brick.is_leading, is_hold_note: brick.is_hold_note
+	};
+}
@@ -13,6 +13,9 @@ function SizedTexture(texture, width, height){
 	this.height = height;
 }
 
+// drawn in this order, each pass over the graphics of its layers
+const renderPasses = [wasm.RenderPass.Background, wasm.RenderPass.Gameplay, wasm.RenderPass.Hud];
+
 let vertexShaderSource;
 let fragementShaderSource;
 
@@ -25,6 +28,7 @@ export function WebGLGraphics(images, screenDiv){
 	this.texCoordBuffer;
 	this.vao;
 	this.textures;
+	this.resolutionUniformLocation;
 
 	this.canvas = document.createElement("canvas");;
 	this.gl = this.canvas.getContext("webgl2", { premultipliedAlpha: false });
@@ -53,8 +57,8 @@ export function WebGLGraphics(images, screenDiv){
 	gl.useProgram(program);
 
 	// set view port and resolution
-	let resolutionUniformLocation = gl.getUniformLocation(program, "u_resolution");
-	gl.uniform2f(resolutionUniformLocation, gameDim.x, gameDim.y);
+	this.resolutionUniformLocation = gl.getUniformLocation(program, "u_resolution");
+	gl.uniform2f(this.resolutionUniformLocation, gameDim.x, gameDim.y);
 	gl.viewport(0, 0, gl.canvas.width, gl.canvas.height);
 	
 	// enable alpha channel
@@ -117,10 +121,11 @@ export function WebGLGraphics(images, screenDiv){
 	});
 }
 
-WebGLGraphics.prototype.resize = function(xFactor, yFactor){
-	let gameDim = wasm.game_dimensions();
-	this.canvas.width = gameDim.x * xFactor;
-	this.canvas.height = gameDim.y * yFactor;
+// sizes the canvas to the viewport's screen. Graphics are placed on the screen by the game, so the resolution is the screen's size
+WebGLGraphics.prototype.resize = function(viewport){
+	this.canvas.width = viewport.screen_width;
+	this.canvas.height = viewport.screen_height;
+	this.gl.uniform2f(this.resolutionUniformLocation, this.canvas.width, this.canvas.height);
 	this.gl.viewport(0, 0, this.canvas.width, this.canvas.height);
 }
 
@@ -135,69 +140,77 @@ WebGLGraphics.prototype.preRender = function(instructions, wasmMemoryObj){
 	let positions = new Float32Array(12);
 	let texCoordinates = new Float32Array(18);
 
-	// move and make textures visible
-	let len = instructions.num_graphics * 3;
+	// move and make textures visible, one render pass at a time from the background to the HUD
+	const stride = 6; // floats per positioned graphic: the graphic, x, y, width, height, and the layer
+	let len = instructions.num_graphics * stride;
 	let f32buf = new Float32Array(wasmMemoryObj.buffer, instructions.graphics_ptr, len);
 	let u8buf = new Uint8Array(wasmMemoryObj.buffer, instructions.graphics_ptr, len*4);
-	let i = 0;
-	while(i < len){
-		let graphicIdx = u8buf[i*4];
-		let graphicFrame = u8buf[i*4 + 1];
-		let graphicFlags = u8buf[i*4 + 2];
-		let graphicArg = u8buf[i*4 + 3];
+	renderPasses.forEach( pass => {
+		let i = instructions.pass_start(pass) * stride;
+		let passEnd = instructions.pass_end(pass) * stride;
+		while(i < passEnd){
+			let graphicIdx = u8buf[i*4];
+			let graphicFrame = u8buf[i*4 + 1];
+			let graphicFlags = u8buf[i*4 + 2];
+			let graphicArg = u8buf[i*4 + 3];
 
-		++i;
-		let x = f32buf[i];
-		++i;
-		let y = f32buf[i];
-		++i;
+			++i;
+			let x = f32buf[i];
+			++i;
+			let y = f32buf[i];
+			++i;
+			let width = f32buf[i];
+			++i;
+			let height = f32buf[i];
+			i += 2; // past the layer, which the pass ranges already account for
 		
-		let numFrames = textures[graphicIdx].length;
-		let graphicSubID = graphicFrame % numFrames;
-		let sizedTexture = textures[graphicIdx][graphicSubID];
+			let numFrames = textures[graphicIdx].length;
+			let graphicSubID = graphicFrame % numFrames;
+			let sizedTexture = textures[graphicIdx][graphicSubID];
 		
-		let startX = x;
-		let startY = y;
-		let endX = x + sizedTexture.width;
-		let endY = y + sizedTexture.height;
+			let startX = x;
+			let startY = y;
+			let endX = x + width;
+			let endY = y + height;
 		
-		if(graphicFlags & wasm.GraphicFlags.HorizontalFlip){
-			startX = endX;
-			endX = x;
-		}
-		if(graphicFlags & wasm.GraphicFlags.VerticalFlip){
-			startY = endY;
-			endY = y;
-		}
+			if(graphicFlags & wasm.GraphicFlags.HorizontalFlip){
+				startX = endX;
+				endX = x;
+			}
+			if(graphicFlags & wasm.GraphicFlags.VerticalFlip){
+				startY = endY;
+				endY = y;
+			}
 
-		// set the data of the texture coordinate buffer
-		let alpha = 1.0;
-		if(graphicFlags & wasm.GraphicFlags.Opacity){
-			alpha = graphicArg / 255;
-		}
-		gl.bindBuffer(gl.ARRAY_BUFFER, this.texCoordBuffer);
-		texCoordinates[0] = 0.0; texCoordinates[1] = 0.0; texCoordinates[2] = alpha;
-		texCoordinates[3] = 1.0; texCoordinates[4] = 0.0; texCoordinates[5] = alpha;
-		texCoordinates[6] = 0.0; texCoordinates[7] = 1.0; texCoordinates[8] = alpha;
-		texCoordinates[9] = 0.0; texCoordinates[10] = 1.0; texCoordinates[11] = alpha;
-		texCoordinates[12] = 1.0; texCoordinates[13] = 0.0; texCoordinates[14] = alpha;
-		texCoordinates[15] = 1.0; texCoordinates[16] = 1.0; texCoordinates[17] = alpha;
-		gl.bufferData(gl.ARRAY_BUFFER, texCoordinates, gl.STATIC_DRAW);
+			// set the data of the texture coordinate buffer
+			let alpha = 1.0;
+			if(graphicFlags & wasm.GraphicFlags.Opacity){
+				alpha = graphicArg / 255;
+			}
+			gl.bindBuffer(gl.ARRAY_BUFFER, this.texCoordBuffer);
+			texCoordinates[0] = 0.0; texCoordinates[1] = 0.0; texCoordinates[2] = alpha;
+			texCoordinates[3] = 1.0; texCoordinates[4] = 0.0; texCoordinates[5] = alpha;
+			texCoordinates[6] = 0.0; texCoordinates[7] = 1.0; texCoordinates[8] = alpha;
+			texCoordinates[9] = 0.0; texCoordinates[10] = 1.0; texCoordinates[11] = alpha;
+			texCoordinates[12] = 1.0; texCoordinates[13] = 0.0; texCoordinates[14] = alpha;
+			texCoordinates[15] = 1.0; texCoordinates[16] = 1.0; texCoordinates[17] = alpha;
+			gl.bufferData(gl.ARRAY_BUFFER, texCoordinates, gl.STATIC_DRAW);
 
-		// set the data of the position buffer
-		gl.bindBuffer(gl.ARRAY_BUFFER, positionBuffer);
-		positions[0] = startX; positions[1] = startY;
-		positions[2] = endX;   positions[3] = startY;
-		positions[4] = startX; positions[5] = endY;
-		positions[6] = startX; positions[7] = endY;
-		positions[8] = endX;   positions[9] = startY;
-		positions[10] = endX;  positions[11] = endY;
-		gl.bufferData(gl.ARRAY_BUFFER, positions, gl.STATIC_DRAW);
+			// set the data of the position buffer
+			gl.bindBuffer(gl.ARRAY_BUFFER, positionBuffer);
+			positions[0] = startX; positions[1] = startY;
+			positions[2] = endX;   positions[3] = startY;
+			positions[4] = startX; positions[5] = endY;
+			positions[6] = startX; positions[7] = endY;
+			positions[8] = endX;   positions[9] = startY;
+			positions[10] = endX;  positions[11] = endY;
+			gl.bufferData(gl.ARRAY_BUFFER, positions, gl.STATIC_DRAW);
 
-		gl.bindTexture(gl.TEXTURE_2D, sizedTexture.texture);
+			gl.bindTexture(gl.TEXTURE_2D, sizedTexture.texture);
 		
-		gl.drawArrays(gl.TRIANGLES, 0, pointCount);
-	}
+			gl.drawArrays(gl.TRIANGLES, 0, pointCount);
+		}
+	});
 }
 
 vertexShaderSource = `#version 300 es
@@ -35,6 +35,9 @@ MMDatabase.prototype.saveSong = function(songData, notes){
 		difficulty: songData.difficulty, 
 		bpm: songData.bpm, 
 		brickSpeed: songData.brickSpeed, 
+		brickTypes: songData.brickTypes, 
+		lanes: songData.lanes, 
+		rating: songData.rating, 
 		duration: songData.duration, 
 		startOffset: songData.startOffset, 
 		timeCreated: songData.timeCreated,
@@ -81,6 +84,9 @@ MMDatabase.prototype.searchSong = function(){
 			difficulty: songObject.difficulty,
 			bpm: songObject.bpm,
 			brickSpeed: songObject.brickSpeed,
+			brickTypes: songObject.brickTypes,
+			lanes: songObject.lanes,
+			rating: songObject.rating,
 			duration: songObject.duration,
 			startOffset: songObject.startOffset,
 			timeCreated: songObject.timeCreated,
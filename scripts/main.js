@@ -30,6 +30,9 @@ async function initFunction(){
 	g_controls[81] = wasm.Input.Slash1; // q
 	g_controls[87] = wasm.Input.Slash2; // w
 	g_controls[69] = wasm.Input.Slash3; // e
+	g_controls[82] = wasm.Input.Slash4; // r
+	g_controls[84] = wasm.Input.Slash5; // t
+	g_controls[89] = wasm.Input.Slash6; // y
 	
 	// initialize game
 	g_game = new GameCore();
@@ -0,0 +1,586 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Array;
+
+use crate::BrickData;
+use crate::chart_import::ImportedChart;
+use crate::objects::BrickType;
+use crate::objects::DEFAULT_BRICK_TYPES;
+use crate::objects::MAX_BRICK_TYPES;
+use crate::objects::DEFAULT_LANES;
+
+const HOP_SIZE: usize = 256; // samples per frame of the onset envelope
+const FLUX_HISTORY: usize = 4; // number of previous frames a frame's loudness is compared against
+const LOUDNESS_COMPRESSION: f32 = 1000.0;
+const THRESHOLD_WINDOW: f32 = 0.1; // seconds on each side of a frame averaged for its onset threshold
+const THRESHOLD_MULTIPLIER: f32 = 1.5;
+const THRESHOLD_OFFSET: f32 = 0.5; // proportion of the mean of the whole envelope added to the onset threshold
+const MIN_ONSET_GAP: f32 = 0.05; // seconds. an onset must be the strongest within this time on either side
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+const PREFERRED_BPM: f32 = 120.0; // tempo estimates are weighted towards this bpm
+const PREFERRED_BPM_OCTAVES: f32 = 1.0; // spread (in octaves) of the weighting towards the preferred bpm
+const DOUBLE_PERIOD_WEIGHT: f32 = 0.5;
+const MAX_TEMPO_CANDIDATES: usize = 5;
+const BPM_REFINE_RANGE: f32 = 0.02; // proportion of a candidate bpm searched on each side when refining it
+const BPM_REFINE_STEP: f32 = 0.05;
+const PHASE_STEPS: usize = 64; // number of phases tried when fitting a beat grid
+const CHORD_STRENGTH: f32 = 2.0; // onsets this many times stronger than the median may become groups of bricks
+const MIN_HOLD_GAP: i32 = 8; // beat positions to the next note before a note may become a hold note
+const RNG_SEED: u32 = 0x9E3779B9;
+
+// controls how many bricks a generated chart has and how hard they are to play
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct ChartGeneratorSettings {
+	pub density: f32, // proportion (0 to 1) of detected onsets that become bricks, strongest first
+	pub difficulty: u8, // 1 to 10. Higher difficulties use finer beat grids, more brick types, wider movement, groups, and holds
+	pub num_brick_types: u8, // the song's brick types. Lower difficulties use only some of them
+	pub lanes: u8 // the song's lanes
+}
+
+// how strongly the audio's loudness rises at each frame. Peaks are likely onsets (the starts of notes/hits)
+pub struct OnsetEnvelope {
+	values: Vec<f32>,
+	frame_rate: f32 // frames per second
+}
+
+#[derive(Clone, Copy)]
+pub struct Onset {
+	pub time: f32, // in seconds from the start of the audio
+	pub strength: f32
+}
+
+// an estimated tempo, along with the time of the first beat of its beat grid
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct TempoCandidate {
+	pub bpm: f32,
+	pub start_offset: f32, // time in seconds into the audio of the first beat
+	pub confidence: f32 // 0 to 1. For detected tempos, the confidences of all candidates add up to 1
+}
+
+// deterministic xorshift random numbers, so generated charts are the same for the same input
+struct Rng {
+	state: u32
+}
+
+#[wasm_bindgen]
+impl ChartGeneratorSettings {
+	pub fn new() -> ChartGeneratorSettings {
+		return ChartGeneratorSettings {
+			density: 0.6,
+			difficulty: 5,
+			num_brick_types: DEFAULT_BRICK_TYPES,
+			lanes: DEFAULT_LANES
+		};
+	}
+}
+
+// generates a draft chart from decoded mono audio samples: onsets are detected, the bpm and offset are estimated,
+	// and bricks are placed on the beat grid at the density and difficulty of the settings
+#[wasm_bindgen]
+pub fn generate_chart(samples: &[f32], sample_rate: f32, settings: &ChartGeneratorSettings) -> ImportedChart {
+	let envelope = onset_envelope(samples, sample_rate);
+	let onsets = detect_onsets(&envelope);
+	let candidates = tempo_candidates(&envelope);
+
+	let mut warnings = Vec::new();
+	let bpm;
+	let start_offset;
+	if let Some(best) = candidates.first() {
+		bpm = best.bpm;
+		start_offset = best.start_offset;
+	} else {
+		bpm = PREFERRED_BPM;
+		start_offset = 0.0;
+		warnings.push(format!("No beat was detected, the chart uses {} bpm", PREFERRED_BPM));
+	}
+
+	let notes = generate_bricks(&onsets, bpm, start_offset, settings);
+	return ImportedChart::new(bpm, start_offset, settings.num_brick_types, settings.lanes, notes, warnings);
+}
+
+// estimates the tempo of decoded mono audio samples, returning an array of TempoCandidates, most confident first
+	// the array is empty if no beat is detected
+#[wasm_bindgen]
+pub fn detect_tempo(samples: &[f32], sample_rate: f32) -> Array {
+	let envelope = onset_envelope(samples, sample_rate);
+	let candidates = tempo_candidates(&envelope);
+
+	let array = Array::new_with_length(candidates.len() as u32);
+	for (i, candidate) in candidates.iter().enumerate() {
+		array.set(i as u32, JsValue::from(*candidate));
+	}
+	return array;
+}
+
+// estimates a tempo from times in seconds (into the audio) at which the user tapped along to the beat
+	// taps may skip beats. The confidence is how evenly the taps line up with the estimated beats
+#[wasm_bindgen]
+pub fn tap_tempo(taps: &[f32]) -> Result<TempoCandidate, String> {
+	if taps.len() < 2 {
+		return Err("At least 2 taps are needed to estimate a tempo".to_string());
+	}
+
+	// the median time between taps is taken as the rough time between beats
+	let mut intervals: Vec<f32> = taps.windows(2).map(|pair| pair[1] - pair[0]).collect();
+	intervals.sort_by(|a, b| a.total_cmp(b));
+	let rough_period = intervals[intervals.len() / 2];
+	if rough_period <= 0.0 {
+		return Err("Taps must be in increasing order of time".to_string());
+	}
+
+	// number each tap by the beat it lands on, then fit a line through (beat, time)
+	let beats: Vec<f32> = taps.iter().map(|tap| ((tap - taps[0]) / rough_period).round()).collect();
+	let n = taps.len() as f32;
+	let mean_beat = beats.iter().sum::<f32>() / n;
+	let mean_time = taps.iter().sum::<f32>() / n;
+	let mut covariance = 0.0;
+	let mut beat_variance = 0.0;
+	for (beat, tap) in beats.iter().zip(taps) {
+		covariance += (beat - mean_beat) * (tap - mean_time);
+		beat_variance += (beat - mean_beat) * (beat - mean_beat);
+	}
+	if beat_variance == 0.0 {
+		return Err("Taps must be in increasing order of time".to_string());
+	}
+	let period = covariance / beat_variance;
+	let first_tap_beat_time = mean_time - period * mean_beat;
+
+	// confidence falls as the taps stray from the fitted beats, reaching 0 when they're off by a quarter of a beat on average
+	let mut squared_error = 0.0;
+	for (beat, tap) in beats.iter().zip(taps) {
+		let error = tap - (first_tap_beat_time + period * beat);
+		squared_error += error * error;
+	}
+	let rms_error = (squared_error / n).sqrt();
+	let confidence = (1.0 - rms_error / (period / 4.0)).clamp(0.0, 1.0);
+
+	return Ok(TempoCandidate {
+		bpm: (60.0 / period * 100.0).round() / 100.0,
+		start_offset: first_tap_beat_time.rem_euclid(period),
+		confidence
+	});
+}
+
+// computes the onset envelope: the rise in compressed loudness of each frame over the frames before it,
+	// in both the full signal and its first difference (which emphasizes high, percussive frequencies)
+pub fn onset_envelope(samples: &[f32], sample_rate: f32) -> OnsetEnvelope {
+	let num_frames = samples.len() / HOP_SIZE;
+	let mut loudness: Vec<[f32; 2]> = Vec::with_capacity(num_frames);
+
+	let mut prev_sample = 0.0;
+	for frame in samples.chunks_exact(HOP_SIZE) {
+		let mut energy = 0.0;
+		let mut diff_energy = 0.0;
+		for sample in frame {
+			energy += sample * sample;
+			diff_energy += (sample - prev_sample) * (sample - prev_sample);
+			prev_sample = *sample;
+		}
+		loudness.push([
+			(1.0 + LOUDNESS_COMPRESSION * energy / HOP_SIZE as f32).ln(),
+			(1.0 + LOUDNESS_COMPRESSION * diff_energy / HOP_SIZE as f32).ln()
+		]);
+	}
+
+	let mut values = Vec::with_capacity(num_frames);
+	for i in 0 .. loudness.len() {
+		let history = &loudness[i.saturating_sub(FLUX_HISTORY) .. i];
+		let mut flux = 0.0;
+		for band in 0 .. 2 {
+			let prev = if history.is_empty() { 0.0 } else { history.iter().map(|l| l[band]).sum::<f32>() / history.len() as f32 };
+			flux += (loudness[i][band] - prev).max(0.0);
+		}
+		values.push(flux);
+	}
+
+	return OnsetEnvelope { values, frame_rate: sample_rate / HOP_SIZE as f32 };
+}
+
+// picks the peaks of the onset envelope which stand out above the surrounding envelope
+pub fn detect_onsets(envelope: &OnsetEnvelope) -> Vec<Onset> {
+	let values = &envelope.values;
+	let mut onsets = Vec::new();
+	if values.is_empty() {
+		return onsets;
+	}
+
+	let global_mean = values.iter().sum::<f32>() / values.len() as f32;
+	let threshold_window = (THRESHOLD_WINDOW * envelope.frame_rate).ceil() as usize;
+	let peak_window = (MIN_ONSET_GAP * envelope.frame_rate).ceil() as usize;
+
+	for i in 0 .. values.len() {
+		let window = &values[i.saturating_sub(threshold_window) .. (i + threshold_window + 1).min(values.len())];
+		let local_mean = window.iter().sum::<f32>() / window.len() as f32;
+		let threshold = local_mean * THRESHOLD_MULTIPLIER + global_mean * THRESHOLD_OFFSET;
+		if values[i] <= threshold {
+			continue;
+		}
+
+		// must be the first largest value in its neighborhood
+		let neighborhood_start = i.saturating_sub(peak_window);
+		let neighborhood_end = (i + peak_window + 1).min(values.len());
+		let is_peak = (neighborhood_start .. neighborhood_end).all(|j| {
+			return if j < i { values[j] < values[i] } else { values[j] <= values[i] };
+		});
+
+		if is_peak {
+			onsets.push(Onset { time: envelope.frame_time(i), strength: values[i] });
+		}
+	}
+
+	return onsets;
+}
+
+// estimates the most likely bpms of the audio, most confident first
+	// candidates come from the peaks of the envelope's autocorrelation, and are refined by fitting a beat grid to the whole envelope
+pub fn tempo_candidates(envelope: &OnsetEnvelope) -> Vec<TempoCandidate> {
+	let values = &envelope.values;
+	let min_lag = (envelope.frame_rate * 60.0 / MAX_BPM).floor() as usize;
+	let max_lag = (envelope.frame_rate * 60.0 / MIN_BPM).ceil() as usize;
+	if min_lag < 1 || values.len() <= max_lag + 1 {
+		return Vec::new();
+	}
+
+	let mean = values.iter().sum::<f32>() / values.len() as f32;
+	let centered: Vec<f32> = values.iter().map(|v| v - mean).collect();
+	// computed up to twice the longest lag, for the double period of each lag
+	let num_lags = (2 * max_lag + 2).min(centered.len());
+	let mut autocorrelation = vec![0.0; num_lags];
+	for lag in min_lag - 1 .. num_lags {
+		let mut sum = 0.0;
+		for i in 0 .. centered.len() - lag {
+			sum += centered[i] * centered[i + lag];
+		}
+		autocorrelation[lag] = sum / (centered.len() - lag) as f32;
+	}
+
+	// local maxima of the autocorrelation, reinforced by the autocorrelation at double their period
+		// (so a beat period scores above periods that only line up with every few off beats) and weighted towards the preferred bpm
+	let mut peaks: Vec<(f32, f32)> = Vec::new(); // (bpm, score)
+	for lag in min_lag ..= max_lag {
+		let (prev, val, next) = (autocorrelation[lag - 1], autocorrelation[lag], autocorrelation[lag + 1]);
+		if val <= 0.0 || val < prev || val < next {
+			continue;
+		}
+
+		// parabolic interpolation for a lag between frames
+		let curvature = prev - 2.0 * val + next;
+		let lag_offset = if curvature < 0.0 { 0.5 * (prev - next) / curvature } else { 0.0 };
+		let bpm = envelope.frame_rate * 60.0 / (lag as f32 + lag_offset);
+		let octaves_from_preferred = (bpm / PREFERRED_BPM).log2() / PREFERRED_BPM_OCTAVES;
+		let weight = (-0.5 * octaves_from_preferred * octaves_from_preferred).exp();
+		let double_period = autocorrelation[(2 * lag - 1).min(num_lags - 1) .. (2 * lag + 2).min(num_lags)].iter()
+			.fold(0.0f32, |max, r| max.max(*r));
+		peaks.push((bpm, (val + DOUBLE_PERIOD_WEIGHT * double_period) * weight));
+	}
+	peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+	// refine the best peaks, skipping any that refine to a bpm already found
+	let mut candidates: Vec<TempoCandidate> = Vec::new();
+	for (bpm, score) in peaks {
+		if candidates.len() >= MAX_TEMPO_CANDIDATES {
+			break;
+		}
+
+		let bpm = refine_bpm(envelope, bpm);
+		if candidates.iter().any(|c| (c.bpm - bpm).abs() < 1.0) {
+			continue;
+		}
+		candidates.push(TempoCandidate { bpm, start_offset: beat_offset(envelope, bpm), confidence: score });
+	}
+
+	let total_score: f32 = candidates.iter().map(|c| c.confidence).sum();
+	for candidate in &mut candidates {
+		candidate.confidence /= total_score;
+	}
+
+	return candidates;
+}
+
+// the time in seconds of the first beat of the beat grid of the given bpm that best fits the envelope
+pub fn beat_offset(envelope: &OnsetEnvelope, bpm: f32) -> f32 {
+	let (_, phase) = envelope.beat_grid_fit(bpm);
+	return envelope.frame_time(0) + phase / envelope.frame_rate;
+}
+
+// searches around a bpm for the one whose beat grid best fits the envelope
+fn refine_bpm(envelope: &OnsetEnvelope, bpm: f32) -> f32 {
+	let mut best_bpm = bpm;
+	let mut best_score = f32::MIN;
+
+	let num_steps = (bpm * BPM_REFINE_RANGE / BPM_REFINE_STEP).ceil() as i32;
+	for step in -num_steps ..= num_steps {
+		let test_bpm = bpm + step as f32 * BPM_REFINE_STEP;
+		let (score, _) = envelope.beat_grid_fit(test_bpm);
+		if score > best_score {
+			best_score = score;
+			best_bpm = test_bpm;
+		}
+	}
+
+	return (best_bpm * 100.0).round() / 100.0;
+}
+
+// places bricks on onsets, snapped to the beat grid with beat pos 0 at start_offset seconds
+pub fn generate_bricks(onsets: &[Onset], bpm: f32, start_offset: f32, settings: &ChartGeneratorSettings) -> Vec<BrickData> {
+	let difficulty = settings.difficulty.clamp(1, 10);
+	let grid = if difficulty <= 3 { 4 } else if difficulty <= 6 { 2 } else { 1 };
+	let song_brick_types = settings.num_brick_types.clamp(1, MAX_BRICK_TYPES as u8) as u32;
+	let tier = if difficulty <= 2 { 1 } else if difficulty <= 5 { 2 } else { 3 }; // thirds of the song's brick types used
+	let num_brick_types = (song_brick_types * tier).div_ceil(3);
+	let max_lane_change = 2 + difficulty as i32;
+	let lanes = settings.lanes.max(1) as i32;
+
+	// the strongest onset of each grid position
+	let mut positioned: Vec<(i32, f32)> = Vec::new(); // (beat pos, strength)
+	for onset in onsets {
+		// onsets just before the start offset still snap to beat pos 0
+		let beat_pos = BrickData::quantized_beat_pos(onset.time - start_offset, bpm, grid);
+		if beat_pos < 0 {
+			continue;
+		}
+		match positioned.last_mut() {
+			Some(last) if last.0 == beat_pos => {
+				if onset.strength > last.1 {
+					last.1 = onset.strength;
+				}
+			},
+			_ => { positioned.push((beat_pos, onset.strength)); }
+		}
+	}
+	if positioned.is_empty() {
+		return Vec::new();
+	}
+
+	let mut strengths: Vec<f32> = positioned.iter().map(|p| p.1).collect();
+	strengths.sort_by(|a, b| b.total_cmp(a));
+	let median_strength = strengths[strengths.len() / 2];
+
+	// keep the strongest onsets according to the density
+	let density = settings.density.clamp(0.0, 1.0);
+	let num_kept = (density * positioned.len() as f32).ceil() as usize;
+	if num_kept == 0 {
+		return Vec::new();
+	}
+	let min_kept_strength = strengths[num_kept - 1];
+	let mut kept: Vec<(i32, f32)> = Vec::with_capacity(num_kept);
+	for p in &positioned {
+		if p.1 >= min_kept_strength && kept.len() < num_kept {
+			kept.push(*p);
+		}
+	}
+
+	let mut rng = Rng { state: RNG_SEED ^ kept.len() as u32 };
+	let mut bricks = BTreeSet::new();
+	let mut x_pos = lanes / 2;
+	let mut dir = 1;
+	let mut type_idx = 0;
+
+	for (i, (beat_pos, strength)) in kept.iter().enumerate() {
+		// move a distance limited by the difficulty and the time since the last note, occasionally changing direction
+		let gap = if i > 0 { beat_pos - kept[i - 1].0 } else { 0 };
+		let lane_change = 1 + rng.next(max_lane_change.min(gap.max(1)) as u32) as i32;
+		if rng.next(4) == 0 {
+			dir = -dir;
+		}
+		if x_pos + dir * lane_change < 0 || x_pos + dir * lane_change > lanes - 1 {
+			dir = -dir;
+		}
+		x_pos = (x_pos + dir * lane_change).clamp(0, lanes - 1);
+
+		// change brick type more often at higher difficulties
+		if num_brick_types > 1 && rng.next(10) < difficulty as u32 {
+			type_idx = (type_idx + 1 + rng.next(num_brick_types - 1)) % num_brick_types;
+		}
+		let brick_type = BrickType::from_index(type_idx as usize).unwrap();
+
+		// hold until a beat before the next note when there's a long enough gap
+		let next_gap = if i + 1 < kept.len() { kept[i + 1].0 - beat_pos } else { 0 };
+		let is_hold_note = difficulty >= 2 && next_gap >= MIN_HOLD_GAP;
+		let end_beat_pos = if is_hold_note { beat_pos + next_gap - 4 } else { *beat_pos };
+
+		bricks.insert(BrickData {
+			brick_type,
+			beat_pos: *beat_pos,
+			end_beat_pos,
+			x_pos,
+			is_triplet: false,
+			is_trailing: false,
+			is_leading: false,
+			is_hold_note
+		});
+
+		// strong onsets at high difficulties become groups
+		if difficulty >= 7 && *strength >= median_strength * CHORD_STRENGTH {
+			let chord_x_pos = if x_pos + dir >= 0 && x_pos + dir < lanes { x_pos + dir } else { x_pos - dir };
+			bricks.insert(BrickData {
+				brick_type,
+				beat_pos: *beat_pos,
+				end_beat_pos,
+				x_pos: chord_x_pos,
+				is_triplet: false,
+				is_trailing: false,
+				is_leading: false,
+				is_hold_note
+			});
+		}
+	}
+
+	return bricks.into_iter().collect();
+}
+
+impl OnsetEnvelope {
+	// the time in seconds at the middle of a frame
+	fn frame_time(&self, frame: usize) -> f32 {
+		return (frame as f32 + 0.5) / self.frame_rate;
+	}
+
+	// fits a beat grid of the given bpm to the envelope, returning the mean envelope value on the grid's beats
+		// and the grid's phase (in frames, less than one beat)
+	fn beat_grid_fit(&self, bpm: f32) -> (f32, f32) {
+		let period = self.frame_rate * 60.0 / bpm;
+		let mut best_score = f32::MIN;
+		let mut best_phase = 0.0;
+
+		for step in 0 .. PHASE_STEPS {
+			let phase = period * step as f32 / PHASE_STEPS as f32;
+			let mut sum = 0.0;
+			let mut num_beats = 0;
+			let mut frame = phase;
+			while (frame.round() as usize) < self.values.len() {
+				sum += self.values[frame.round() as usize];
+				num_beats += 1;
+				frame += period;
+			}
+
+			let score = if num_beats > 0 { sum / num_beats as f32 } else { 0.0 };
+			if score > best_score {
+				best_score = score;
+				best_phase = phase;
+			}
+		}
+
+		return (best_score, best_phase);
+	}
+}
+
+impl Rng {
+	// a number from 0 up to (not including) max
+	fn next(&mut self, max: u32) -> u32 {
+		self.state ^= self.state << 13;
+		self.state ^= self.state >> 17;
+		self.state ^= self.state << 5;
+		return if max == 0 { 0 } else { self.state % max };
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const SAMPLE_RATE: f32 = 44100.0;
+	const CLICK_BPM: f32 = 120.0;
+	const FIRST_CLICK: f32 = 0.25;
+	const NUM_CLICKS: usize = 24;
+
+	fn click_track() -> Vec<f32> {
+		return clicks(CLICK_BPM, NUM_CLICKS);
+	}
+
+	// a short decaying tone on every beat starting at FIRST_CLICK, with silence between
+	fn clicks(bpm: f32, num_clicks: usize) -> Vec<f32> {
+		let seconds_per_beat = 60.0 / bpm;
+		let len = ((FIRST_CLICK + seconds_per_beat * (num_clicks as f32 + 1.0)) * SAMPLE_RATE) as usize;
+		let mut samples = vec![0.0; len];
+		for click in 0 .. num_clicks {
+			let start = ((FIRST_CLICK + click as f32 * seconds_per_beat) * SAMPLE_RATE) as usize;
+			for i in 0 .. (0.02 * SAMPLE_RATE) as usize {
+				let t = i as f32 / SAMPLE_RATE;
+				samples[start + i] = (t * 1000.0 * 2.0 * std::f32::consts::PI).sin() * (-t * 200.0).exp();
+			}
+		}
+		return samples;
+	}
+
+	fn settings() -> ChartGeneratorSettings {
+		let mut settings = ChartGeneratorSettings::new();
+		settings.density = 1.0;
+		return settings;
+	}
+
+	#[test]
+	fn detects_an_onset_on_every_click() {
+		let envelope = onset_envelope(&click_track(), SAMPLE_RATE);
+		let onsets = detect_onsets(&envelope);
+		assert_eq!(onsets.len(), NUM_CLICKS);
+		for (click, onset) in onsets.iter().enumerate() {
+			let click_time = FIRST_CLICK + click as f32 * 60.0 / CLICK_BPM;
+			assert!((onset.time - click_time).abs() < 0.02, "onset at {} for the click at {}", onset.time, click_time);
+		}
+	}
+
+	#[test]
+	fn generated_chart_is_on_the_beat_grid_of_the_click_track() {
+		let chart = generate_chart(&click_track(), SAMPLE_RATE, &settings());
+		assert!((chart.bpm - CLICK_BPM).abs() < 1.0, "bpm {}", chart.bpm);
+		assert!((chart.start_offset - FIRST_CLICK).abs() < 0.02, "start offset {}", chart.start_offset);
+
+		// every click is a beat, so every brick is on a whole beat
+		assert!(!chart.notes.is_empty());
+		for brick_data in &chart.notes {
+			assert_eq!(brick_data.beat_pos % 4, 0, "brick at beat pos {}", brick_data.beat_pos);
+		}
+		let beats: BTreeSet<i32> = chart.notes.iter().map(|b| b.beat_pos / 4).collect();
+		assert_eq!(beats, (0 .. NUM_CLICKS as i32).collect());
+	}
+
+	#[test]
+	fn best_tempo_candidate_is_the_click_track_tempo() {
+		let bpm = 137.0;
+		let envelope = onset_envelope(&clicks(bpm, 32), SAMPLE_RATE);
+		let candidates = tempo_candidates(&envelope);
+
+		assert!((candidates[0].bpm - bpm).abs() < 0.5, "bpm {}", candidates[0].bpm);
+		assert!((candidates[0].start_offset - FIRST_CLICK).abs() < 0.02, "start offset {}", candidates[0].start_offset);
+		assert!(candidates.iter().all(|c| c.confidence > 0.0 && c.confidence <= candidates[0].confidence));
+		let total_confidence: f32 = candidates.iter().map(|c| c.confidence).sum();
+		assert!((total_confidence - 1.0).abs() < 0.001);
+	}
+
+	#[test]
+	fn tap_tempo_fits_jittered_taps_that_skip_beats() {
+		// 100 bpm from 0.3 seconds, each tap up to 15 ms off the beat, with beats 3 and 7 not tapped
+		let period = 0.6;
+		let jitter = [0.01, -0.015, 0.0, 0.005, -0.01, 0.015, -0.005, 0.01, -0.01, 0.0];
+		let taps: Vec<f32> = (0 .. 10).filter(|beat| *beat != 3 && *beat != 7)
+			.map(|beat| 0.3 + period * beat as f32 + jitter[beat]).collect();
+		let candidate = tap_tempo(&taps).unwrap();
+
+		assert!((candidate.bpm - 100.0).abs() < 0.5, "bpm {}", candidate.bpm);
+		assert!((candidate.start_offset - 0.3).abs() < 0.015, "start offset {}", candidate.start_offset);
+		assert!(candidate.confidence > 0.8, "confidence {}", candidate.confidence);
+	}
+
+	#[test]
+	fn tap_tempo_needs_increasing_taps() {
+		assert!(tap_tempo(&[1.0]).is_err());
+		assert!(tap_tempo(&[2.0, 1.5, 1.0]).is_err());
+		assert!(tap_tempo(&[1.0, 1.0, 1.0]).is_err());
+	}
+
+	#[test]
+	fn generated_chart_is_the_same_for_the_same_audio() {
+		let samples = click_track();
+		let first = generate_chart(&samples, SAMPLE_RATE, &settings());
+		let second = generate_chart(&samples, SAMPLE_RATE, &settings());
+		let fields = |b: &BrickData| (b.brick_type as u8, b.beat_pos, b.end_beat_pos, b.x_pos, b.is_hold_note);
+		assert_eq!(first.bpm, second.bpm);
+		assert_eq!(first.start_offset, second.start_offset);
+		assert_eq!(first.notes.iter().map(fields).collect::<Vec<_>>(), second.notes.iter().map(fields).collect::<Vec<_>>());
+	}
+}
@@ -2,18 +2,23 @@
 use std::cmp::Ordering;
 
 use crate::PositionedGraphic;
-use crate::resources::GraphicGroup;
+use crate::resources::BRICK_GRAPHICS;
+use crate::resources::BRICK_SEGMENT_GRAPHICS;
 use crate::Graphic;
+use crate::BrickData;
+use crate::GAME_HEIGHT;
 
+use crate::collider::Collider;
 use crate::objects::Object;
 use crate::objects::ObjectBounds;
 use crate::objects::BrickType;
 
 use crate::objects::BRICK_HEIGHT;
-use crate::objects::BRICK_WIDTH;
+use crate::objects::DEFAULT_BRICK_WIDTH;
 use crate::objects::BRICK_SEGMENT_HEIGHT;
 use crate::objects::BRICK_SEGMENT_WIDTH;
 use crate::objects::BRICK_SEGMENT_GAP;
+use crate::objects::BRICK_CORNER_RADIUS;
 
 // struct for bricks that are already in the game with valid hitboxes
 // the bounds describe the bounds of the brick, or the bounds of the topmost segment if it's a hold note
@@ -21,15 +26,17 @@ use crate::objects::BRICK_SEGMENT_GAP;
 pub struct HittableBrick {
 	brick_type: BrickType,
 	x: f32,
-	hold_segments: u8, // how many brick segments follow the brick
-	parts_destroyed: u8 // the number of segments destroyed, with the first part being the brick itself
+	width: f32, // the width of the brick's lane
+	hold_segments: u32, // how many brick segments follow the brick
+	parts_destroyed: u32 // the number of segments destroyed, with the first part being the brick itself
 }
 
 impl HittableBrick {
-	pub fn new (brick_type: BrickType, x: f32, hold_segments: u8) -> HittableBrick {
+	pub fn new (brick_type: BrickType, x: f32, width: f32, hold_segments: u32) -> HittableBrick {
 		return HittableBrick {
 			brick_type,
 			x,
+			width,
 			hold_segments,
 			parts_destroyed: 0
 		};
@@ -41,45 +48,41 @@ impl HittableBrick {
 			return None;
 		}
 		
-		let bounds_left_x;
-		let bounds_top_y;
-		let bounds_right_x;
-		let bounds_bottom_y;
 		if self.parts_destroyed == 0 {
-			bounds_left_x = self.x;
-			bounds_top_y = top_y;
-			bounds_right_x = bounds_left_x + BRICK_WIDTH as f32;
-			bounds_bottom_y = bounds_top_y + BRICK_HEIGHT as f32;
+			return Some( brick_bounds(self.x, self.width, top_y) );
 		}
-		else {
-			bounds_left_x = self.x + (BRICK_WIDTH as f32 - BRICK_SEGMENT_WIDTH as f32) / 2.0;
-			bounds_top_y = top_y + BRICK_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32 
-				+ (BRICK_SEGMENT_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32) * (self.parts_destroyed - 1) as f32;
-			bounds_right_x = bounds_left_x + BRICK_SEGMENT_WIDTH as f32;
-			bounds_bottom_y = bounds_top_y + BRICK_SEGMENT_HEIGHT as f32;
+		return Some( hold_segment_bounds(self.x, self.width, top_y, self.parts_destroyed - 1) );
+	}
+
+	// get the collider of the brick, or of the topmost segment if it's a broken hold note, given the top of the brick
+	pub fn collider(&self, top_y: f32) -> Option<Collider> {
+		if self.parts_destroyed > self.hold_segments {
+			return None;
 		}
-		
-		return Some( ObjectBounds {
-			left_x: bounds_left_x,
-			right_x: bounds_right_x,
-			top_y: bounds_top_y,
-			bottom_y: bounds_bottom_y
-		});
+
+		if self.parts_destroyed == 0 {
+			return Some( brick_collider(self.x, self.width, top_y) );
+		}
+		return Some( hold_segment_collider(self.x, self.width, top_y, self.parts_destroyed - 1) );
 	}
 	
 	pub fn x(&self) -> f32 {
 		return self.x;
 	}
 	
+	pub fn width(&self) -> f32 {
+		return self.width;
+	}
+	
 	pub fn brick_type(&self) -> BrickType {
 		return self.brick_type;
 	}
 	
-	pub fn hold_segments(&self) -> u8 {
+	pub fn hold_segments(&self) -> u32 {
 		return self.hold_segments;
 	}
 
-	pub fn parts_destroyed(&self) -> u8 {
+	pub fn parts_destroyed(&self) -> u32 {
 		return self.parts_destroyed;
 	}
 
@@ -112,36 +115,34 @@ impl HittableBrick {
 	
 	// get the rendering instructions of the brick given the top of the brick (or where the top was if the brick is a broken hold note)
 	pub fn rendering_instructions(&self, top_y: f32) -> Vec<PositionedGraphic> {
-		let mut positioned_graphics = Vec::with_capacity(self.hold_segments as usize + 1);
+		let mut positioned_graphics = Vec::new();
 
 		if self.parts_destroyed > self.hold_segments {
 			return positioned_graphics;
 		}
 		
-		let (brick_graphic_group, segment_graphic_group) = match self.brick_type {
-			BrickType::Type1 => {(GraphicGroup::Brick1, GraphicGroup::Brick1Segment)},
-			BrickType::Type2 => {(GraphicGroup::Brick2, GraphicGroup::Brick2Segment)},
-			BrickType::Type3 => {(GraphicGroup::Brick3, GraphicGroup::Brick3Segment)}
-		};
+		let brick_graphic_group = BRICK_GRAPHICS[self.brick_type as usize];
+		let segment_graphic_group = BRICK_SEGMENT_GRAPHICS[self.brick_type as usize];
 
 		let mut num_brick_segments;
-		let segment_left_x = self.x + (BRICK_WIDTH as f32 - BRICK_SEGMENT_WIDTH as f32) / 2.0;
+		let segment_width = segment_width(self.width);
+		let segment_left_x = self.x + (self.width - segment_width) / 2.0;
 		let mut segment_top_y = top_y + BRICK_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32;
 
 		// push the brick if it hasn't been destroyed
 		if self.parts_destroyed == 0 {
 			let graphic = Graphic { g: brick_graphic_group, frame: 0, flags: 0, arg: 0 };
-			positioned_graphics.push(PositionedGraphic::new(graphic, self.x, top_y));
+			positioned_graphics.push(PositionedGraphic::sized(graphic, self.x, top_y, self.width, BRICK_HEIGHT as f32));
 			num_brick_segments = self.hold_segments;
 		} else {
 			num_brick_segments = self.hold_segments - self.parts_destroyed + 1;
 			segment_top_y += (BRICK_SEGMENT_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32) * (self.parts_destroyed - 1) as f32;
 		}
 		
-		// push any segments
+		// push any segments, up to the bottom of the screen
 		let segment_graphic = Graphic { g: segment_graphic_group, frame: 0, flags: 0, arg: 0 };
-		while num_brick_segments > 0 {
-			positioned_graphics.push(PositionedGraphic::new(segment_graphic, segment_left_x, segment_top_y));
+		while num_brick_segments > 0 && segment_top_y < GAME_HEIGHT {
+			positioned_graphics.push(PositionedGraphic::sized(segment_graphic, segment_left_x, segment_top_y, segment_width, BRICK_SEGMENT_HEIGHT as f32));
 
 			num_brick_segments -= 1;
 			segment_top_y += (BRICK_SEGMENT_HEIGHT + BRICK_SEGMENT_GAP) as f32;
@@ -149,4 +150,56 @@ impl HittableBrick {
 
 		return positioned_graphics;
 	}
-}
\ No newline at end of file
+}
+
+// the number of hold segments following a brick, how many fit between the bottom of the brick and the bottom of a brick at the end beat pos
+pub fn hold_segment_count(brick_data: &BrickData, bpm: f32, brick_speed: f32) -> u32 {
+	if !brick_data.is_hold_note {
+		return 0;
+	}
+	
+	// hold length from the bottom of the starting note 
+		// to the bottom of the ending note, if there was a full note (rather than just a hold segment) at the end beat pos
+	let hold_length_pixels = brick_data.end_appearance_y(bpm, brick_speed) - brick_data.appearance_y(bpm, brick_speed);
+	// how many brick segments fit into the hold length, floored
+	return (hold_length_pixels / (BRICK_SEGMENT_HEIGHT + BRICK_SEGMENT_GAP) as f32) as u32;
+}
+
+// the bounds of a brick of the width with its left at x and its top at top_y
+pub fn brick_bounds(x: f32, width: f32, top_y: f32) -> ObjectBounds {
+	return ObjectBounds {
+		left_x: x,
+		right_x: x + width,
+		top_y,
+		bottom_y: top_y + BRICK_HEIGHT as f32
+	};
+}
+
+// bricks have rounded corners, so a hit on an empty corner of the brick's bounds doesn't count
+pub fn brick_collider(x: f32, width: f32, top_y: f32) -> Collider {
+	return Collider::rounded_rect(brick_bounds(x, width, top_y), BRICK_CORNER_RADIUS);
+}
+
+// the bounds of a hold segment (0 being the segment right below the brick) of a brick of the width with its left at x and its top at top_y
+pub fn hold_segment_bounds(x: f32, width: f32, top_y: f32, segment: u32) -> ObjectBounds {
+	let segment_width = segment_width(width);
+	let left_x = x + (width - segment_width) / 2.0;
+	let segment_top_y = top_y + BRICK_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32 
+		+ (BRICK_SEGMENT_HEIGHT as f32 + BRICK_SEGMENT_GAP as f32) * segment as f32;
+	return ObjectBounds {
+		left_x,
+		right_x: left_x + segment_width,
+		top_y: segment_top_y,
+		bottom_y: segment_top_y + BRICK_SEGMENT_HEIGHT as f32
+	};
+}
+
+// hold segments have rounded corners like bricks
+pub fn hold_segment_collider(x: f32, width: f32, top_y: f32, segment: u32) -> Collider {
+	return Collider::rounded_rect(hold_segment_bounds(x, width, top_y, segment), BRICK_CORNER_RADIUS);
+}
+
+// hold segments are as much narrower than the brick as they are with the default brick width
+fn segment_width(brick_width: f32) -> f32 {
+	return BRICK_SEGMENT_WIDTH as f32 * brick_width / DEFAULT_BRICK_WIDTH as f32;
+}
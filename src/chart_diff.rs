@@ -0,0 +1,327 @@
+
+use std::cmp::Ordering;
+use std::collections::btree_map::BTreeMap;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Array;
+
+use crate::BrickData;
+use crate::selection;
+use crate::song_json;
+use crate::song_json::JsonValue;
+
+// identifies a brick across charts: (beat_pos, x_pos, is_triplet, is_trailing, is_leading). Bricks are matched on all of these,
+	// since BrickData's order only compares where bricks appear and a trailing brick appears where the next beat's leading brick does
+type BrickKey = (i32, i32, bool, bool, bool);
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChangeKind {
+	Added,
+	Removed,
+	Changed // same beat pos, lane, and leading/trailing/triplet flags with a different brick type or hold
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct BrickChange {
+	pub kind: ChangeKind,
+	before: Option<BrickData>,
+	after: Option<BrickData>
+}
+
+// the changes from one chart to another, in order of the bricks' positions
+#[wasm_bindgen]
+pub struct ChartDiff {
+	changes: Vec<BrickChange>
+}
+
+// a brick changed differently in the two merged charts. None for a brick that isn't in that chart
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct MergeConflict {
+	base: Option<BrickData>,
+	ours: Option<BrickData>,
+	theirs: Option<BrickData>
+}
+
+// the result of a three-way merge: the merged song json (with our song data and the merged notes) and the conflicts,
+	// for which our version of the brick was kept
+#[wasm_bindgen]
+pub struct ChartMerge {
+	merged_json: String,
+	conflicts: Vec<MergeConflict>
+}
+
+#[wasm_bindgen]
+impl BrickChange {
+	pub fn before(&self) -> Option<BrickData> {
+		return self.before.clone();
+	}
+
+	pub fn after(&self) -> Option<BrickData> {
+		return self.after.clone();
+	}
+}
+
+#[wasm_bindgen]
+impl ChartDiff {
+	pub fn changes(&self) -> Array {
+		let array = Array::new_with_length(self.changes.len() as u32);
+		for (i, change) in self.changes.iter().enumerate() {
+			array.set(i as u32, JsValue::from(change.clone()));
+		}
+		return array;
+	}
+
+	pub fn num_changes(&self) -> usize {
+		return self.changes.len();
+	}
+}
+
+#[wasm_bindgen]
+impl MergeConflict {
+	pub fn base(&self) -> Option<BrickData> {
+		return self.base.clone();
+	}
+
+	pub fn ours(&self) -> Option<BrickData> {
+		return self.ours.clone();
+	}
+
+	pub fn theirs(&self) -> Option<BrickData> {
+		return self.theirs.clone();
+	}
+}
+
+#[wasm_bindgen]
+impl ChartMerge {
+	pub fn merged_json(&self) -> String {
+		return self.merged_json.clone();
+	}
+
+	pub fn conflicts(&self) -> Array {
+		let array = Array::new_with_length(self.conflicts.len() as u32);
+		for (i, conflict) in self.conflicts.iter().enumerate() {
+			array.set(i as u32, JsValue::from(conflict.clone()));
+		}
+		return array;
+	}
+
+	pub fn has_conflicts(&self) -> bool {
+		return self.conflicts.len() > 0;
+	}
+}
+
+// compares the notes of two song json files
+#[wasm_bindgen]
+pub fn diff_charts(old_json: &str, new_json: &str) -> Result<ChartDiff, String> {
+	let old = chart_bricks(&song_json::parse(old_json)?)?;
+	let new = chart_bricks(&song_json::parse(new_json)?)?;
+	return Ok(ChartDiff { changes: diff(&old, &new) });
+}
+
+// merges the changes from the base song json to ours and to theirs. A brick changed in only one of them takes that change,
+	// and a brick changed differently in both is a conflict. A change of theirs that would overlap a brick in the merged notes,
+	// or take the place of one (a song holds one brick per place, see BrickData's order), is also a conflict
+#[wasm_bindgen]
+pub fn merge_charts(base_json: &str, ours_json: &str, theirs_json: &str) -> Result<ChartMerge, String> {
+	let base = chart_bricks(&song_json::parse(base_json)?)?;
+	let mut ours_song = song_json::parse(ours_json)?;
+	let ours = chart_bricks(&ours_song)?;
+	let theirs = chart_bricks(&song_json::parse(theirs_json)?)?;
+
+	let mut keys: Vec<&BrickKey> = base.keys().chain(ours.keys()).chain(theirs.keys()).collect();
+	keys.sort();
+	keys.dedup();
+
+	let mut merged = BTreeMap::new();
+	let mut taken_from_theirs = Vec::new();
+	let mut conflicts = Vec::new();
+	for key in keys {
+		let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+		let result;
+		if same(o, t) || same(t, b) {
+			result = o;
+		} else if same(o, b) {
+			result = t;
+			taken_from_theirs.push(*key);
+		} else {
+			result = o;
+			conflicts.push(MergeConflict { base: b.cloned(), ours: o.cloned(), theirs: t.cloned() });
+		}
+
+		if let Some(brick_data) = result {
+			merged.insert(*key, brick_data.clone());
+		}
+	}
+
+	// undo changes of theirs that would make bricks overlap or share a place
+	for key in taken_from_theirs {
+		let placed = match merged.get(&key) {
+			Some(placed) => placed.clone(),
+			None => continue
+		};
+		let in_the_way = |other: &BrickData| other.cmp(&placed) == Ordering::Equal || selection::overlaps(other, &placed);
+		if merged.iter().any(|(other_key, other)| *other_key != key && in_the_way(other)) {
+			merged.remove(&key);
+			if let Some(brick_data) = ours.get(&key) {
+				merged.insert(key, brick_data.clone());
+			}
+			conflicts.push(MergeConflict { base: base.get(&key).cloned(), ours: ours.get(&key).cloned(), theirs: Some(placed) });
+		}
+	}
+
+	let bpm = ours_song.get("bpm").and_then(|bpm| bpm.as_f64()).ok_or("Our song has no bpm")? as f32;
+	let merged: Vec<BrickData> = merged.into_values().collect();
+	ours_song.set("notes", song_json::notes_json(&merged, bpm));
+
+	return Ok(ChartMerge { merged_json: ours_song.to_json(), conflicts });
+}
+
+// the changes that turn the old bricks into the new bricks
+fn diff(old: &BTreeMap<BrickKey, BrickData>, new: &BTreeMap<BrickKey, BrickData>) -> Vec<BrickChange> {
+	let mut keys: Vec<&BrickKey> = old.keys().chain(new.keys()).collect();
+	keys.sort();
+	keys.dedup();
+
+	let mut changes = Vec::new();
+	for key in keys {
+		let (before, after) = (old.get(key), new.get(key));
+		let kind = match (before, after) {
+			(None, Some(_)) => ChangeKind::Added,
+			(Some(_), None) => ChangeKind::Removed,
+			_ if !same(before, after) => ChangeKind::Changed,
+			_ => continue
+		};
+		changes.push(BrickChange { kind, before: before.cloned(), after: after.cloned() });
+	}
+
+	return changes;
+}
+
+// the bricks of a song keyed by beat pos, lane, and leading/trailing/triplet flags
+fn chart_bricks(song: &JsonValue) -> Result<BTreeMap<BrickKey, BrickData>, String> {
+	let bricks = song_json::song_notes(song)?;
+	return Ok(bricks.into_iter().map(|brick_data| (brick_key(&brick_data), brick_data)).collect());
+}
+
+fn brick_key(brick_data: &BrickData) -> BrickKey {
+	return (brick_data.beat_pos, brick_data.x_pos, brick_data.is_triplet, brick_data.is_trailing, brick_data.is_leading);
+}
+
+// whether both are missing, or both are the same brick including its type and hold
+fn same(a: Option<&BrickData>, b: Option<&BrickData>) -> bool {
+	match (a, b) {
+		(None, None) => return true,
+		(Some(a), Some(b)) => {
+			return a == b && a.brick_type == b.brick_type && a.is_hold_note == b.is_hold_note
+				&& (!a.is_hold_note || a.end_beat_pos == b.end_beat_pos);
+		},
+		_ => return false
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::objects::BrickType;
+
+	const BPM: f32 = 120.0;
+
+	fn tap(brick_type: BrickType, beat_pos: i32, x_pos: i32) -> BrickData {
+		return BrickData::new(brick_type, beat_pos, beat_pos, x_pos, false, false, false, false);
+	}
+
+	fn song(bricks: &[BrickData]) -> String {
+		let song = JsonValue::Object(vec![
+			("name".to_string(), JsonValue::String("test".to_string())),
+			("bpm".to_string(), JsonValue::Number(BPM as f64)),
+			("notes".to_string(), song_json::notes_json(bricks, BPM))
+		]);
+		return song.to_json();
+	}
+
+	fn merged_notes(merge: &ChartMerge) -> Vec<BrickData> {
+		return song_json::song_notes(&song_json::parse(&merge.merged_json).unwrap()).unwrap();
+	}
+
+	#[test]
+	fn changed_brick_type_is_a_change() {
+		let diff = diff_charts(&song(&[tap(BrickType::Type1, 8, 2)]), &song(&[tap(BrickType::Type2, 8, 2)])).unwrap();
+		assert_eq!(diff.changes.len(), 1);
+		assert_eq!(diff.changes[0].kind, ChangeKind::Changed);
+	}
+
+	#[test]
+	fn changed_flag_is_a_removal_and_an_addition() {
+		let mut triplet = tap(BrickType::Type1, 8, 2);
+		triplet.is_triplet = true;
+		let diff = diff_charts(&song(&[tap(BrickType::Type1, 8, 2)]), &song(&[triplet])).unwrap();
+		let kinds: Vec<ChangeKind> = diff.changes.iter().map(|change| change.kind).collect();
+		assert_eq!(kinds, vec![ChangeKind::Removed, ChangeKind::Added]);
+		assert!(diff.changes[1].after().unwrap().is_triplet);
+	}
+
+	// a trailing brick appears at the same height as a leading brick on the next beat pos, but they're different bricks
+	#[test]
+	fn bricks_at_the_same_height_are_distinct_changes_that_conflict_in_a_merge() {
+		let mut trailing = tap(BrickType::Type1, 4, 3);
+		trailing.is_trailing = true;
+		let mut leading = tap(BrickType::Type2, 5, 3);
+		leading.is_leading = true;
+		assert_eq!(trailing.appearance_y(BPM, 100.0), leading.appearance_y(BPM, 100.0));
+
+		let diff = diff_charts(&song(&[]), &song(&[trailing.clone(), leading.clone()])).unwrap();
+		assert_eq!(diff.changes.len(), 2);
+		assert!(diff.changes.iter().all(|change| change.kind == ChangeKind::Added));
+
+		// a song holds one of them, so their added brick conflicts with ours
+		let merge = merge_charts(&song(&[]), &song(&[leading]), &song(&[trailing])).unwrap();
+		assert_eq!(merge.conflicts.len(), 1);
+		assert!(merge.conflicts[0].theirs.as_ref().unwrap().is_trailing);
+		let notes = merged_notes(&merge);
+		assert_eq!(notes.len(), 1);
+		assert!(notes[0].is_leading && notes[0].brick_type == BrickType::Type2);
+	}
+
+	#[test]
+	fn merge_takes_a_change_made_on_one_side() {
+		let base = song(&[tap(BrickType::Type1, 8, 2), tap(BrickType::Type1, 12, 4)]);
+		let ours = song(&[tap(BrickType::Type1, 8, 2), tap(BrickType::Type1, 12, 4), tap(BrickType::Type1, 16, 6)]);
+		let theirs = song(&[tap(BrickType::Type3, 8, 2)]);
+		let merge = merge_charts(&base, &ours, &theirs).unwrap();
+
+		assert!(merge.conflicts.is_empty());
+		let notes = merged_notes(&merge);
+		assert_eq!(notes.len(), 2);
+		assert_eq!(notes[0].brick_type, BrickType::Type3);
+		assert_eq!((notes[1].beat_pos, notes[1].x_pos), (16, 6));
+	}
+
+	#[test]
+	fn merge_keeps_ours_for_a_conflicting_change() {
+		let base = song(&[tap(BrickType::Type1, 8, 2)]);
+		let merge = merge_charts(&base, &song(&[tap(BrickType::Type2, 8, 2)]), &song(&[tap(BrickType::Type3, 8, 2)])).unwrap();
+
+		assert_eq!(merge.conflicts.len(), 1);
+		let conflict = &merge.conflicts[0];
+		assert_eq!(conflict.base().unwrap().brick_type, BrickType::Type1);
+		assert_eq!(conflict.ours().unwrap().brick_type, BrickType::Type2);
+		assert_eq!(conflict.theirs().unwrap().brick_type, BrickType::Type3);
+		assert_eq!(merged_notes(&merge)[0].brick_type, BrickType::Type2);
+	}
+
+	#[test]
+	fn merge_rejects_a_change_of_theirs_that_overlaps() {
+		let hold = BrickData::new(BrickType::Type1, 0, 8, 5, false, false, false, true);
+		let merge = merge_charts(&song(&[]), &song(&[hold]), &song(&[tap(BrickType::Type2, 4, 5)])).unwrap();
+
+		assert_eq!(merge.conflicts.len(), 1);
+		assert!(merge.conflicts[0].ours().is_none());
+		assert_eq!(merge.conflicts[0].theirs().unwrap().beat_pos, 4);
+		let notes = merged_notes(&merge);
+		assert_eq!(notes.len(), 1);
+		assert!(notes[0].is_hold_note);
+	}
+}
@@ -0,0 +1,597 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Array;
+
+use crate::BrickData;
+use crate::game::Game;
+use crate::config::GameConfig;
+use crate::edit_result;
+use crate::objects::BrickType;
+use crate::objects::DEFAULT_BRICK_TYPES;
+use crate::objects::DEFAULT_LANES;
+
+const OSU_PLAYFIELD_WIDTH: f32 = 512.0;
+const OSU_MANIA_MODE: i32 = 3;
+const OSU_HOLD_TYPE: i32 = 128;
+const MAX_LANE_SPACING: i32 = 4; // how far apart (in x_pos) columns are spread by default
+const OFF_GRID_TOLERANCE: f32 = 0.05; // how far from a beat pos (in beat positions) a note can be before it's reported as snapped
+
+// which lane and brick type each column of an imported chart maps to. Columns that aren't set are spread across the song's lanes,
+	// cycling through the first num_brick_types brick types
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct ColumnMapping {
+	pub num_brick_types: u8,
+	pub lanes: u8,
+	columns: Vec<Option<(i32, BrickType)>>
+}
+
+// a chart converted from another game's format or generated from audio, with anything that couldn't be converted listed in the warnings
+#[wasm_bindgen]
+pub struct ImportedChart {
+	pub bpm: f32,
+	pub start_offset: f32, // time in seconds into the audio of beat pos 0
+	pub num_brick_types: u8, // the brick types the chart was made with, raised to fit every note
+	pub lanes: u8, // the lanes the chart was made with, raised to fit every note
+	pub(crate) notes: Vec<BrickData>,
+	warnings: Vec<String>
+}
+
+// a point in the chart where the tempo changes
+struct TimingPoint {
+	time: f32, // in seconds from the start of the audio
+	bpm: f32
+}
+
+// a note read from a chart, before it's converted to a brick
+struct ChartNote {
+	column: usize,
+	time: f32,
+	end_time: Option<f32> // some for holds
+}
+
+#[wasm_bindgen]
+impl ColumnMapping {
+	pub fn new() -> ColumnMapping {
+		return ColumnMapping { num_brick_types: DEFAULT_BRICK_TYPES, lanes: DEFAULT_LANES, columns: Vec::new() };
+	}
+
+	// maps a column to a lane and brick type. Fails if the lane is outside the mapping's lanes
+	pub fn set_column(&mut self, column: usize, x_pos: i32, brick_type: BrickType) -> Result<(), String> {
+		if x_pos < 0 || x_pos >= self.lanes as i32 {
+			return Err(format!("Lane {} is outside the {} lanes of the mapping", x_pos, self.lanes));
+		}
+		if column >= self.columns.len() {
+			self.columns.resize(column + 1, None);
+		}
+		self.columns[column] = Some((x_pos, brick_type));
+		return Ok(());
+	}
+}
+
+impl ColumnMapping {
+	// the lane and brick type of a column, out of the given number of columns
+	fn column(&self, column: usize, num_columns: usize) -> (i32, BrickType) {
+		if let Some(Some(mapped)) = self.columns.get(column) {
+			return *mapped;
+		}
+
+		// spread columns evenly around the middle lane, cycling through brick types
+		let lanes = self.lanes.max(1) as i32;
+		let num_columns = num_columns.max(1) as i32;
+		let spacing = (lanes / num_columns).max(1).min(MAX_LANE_SPACING);
+		let first_lane = (lanes - spacing * (num_columns - 1)) / 2;
+		let x_pos = (first_lane + spacing * column as i32).max(0).min(lanes - 1);
+		let brick_type = BrickType::from_index_wrapped(column, self.num_brick_types);
+
+		return (x_pos, brick_type);
+	}
+}
+
+#[wasm_bindgen]
+impl ImportedChart {
+	pub fn notes(&self) -> Array {
+		let array = Array::new_with_length(self.notes.len() as u32);
+		for (i, brick) in self.notes.iter().enumerate() {
+			array.set(i as u32, JsValue::from(brick.clone()));
+		}
+		return array;
+	}
+
+	pub fn warnings(&self) -> Array {
+		let array = Array::new_with_length(self.warnings.len() as u32);
+		for (i, warning) in self.warnings.iter().enumerate() {
+			array.set(i as u32, JsValue::from_str(warning));
+		}
+		return array;
+	}
+
+	// creates a game holding the chart's notes. Fails if the chart has more brick types or lanes than a song can have
+	pub fn to_game(&self, brick_speed: f32, duration: f32, config: &GameConfig) -> Result<Game, String> {
+		let mut game = Game::new(self.bpm, brick_speed, duration, config);
+		for brick_data in &self.notes {
+			game.initial_load_add_brick(brick_data.clone());
+		}
+		game.initial_load_song_settings(self.num_brick_types, self.lanes)?;
+		game.seek(0.0);
+
+		return Ok(game);
+	}
+}
+
+impl ImportedChart {
+	// notes the editor couldn't add (such as taps inside a hold note, or overlapping holds) are left out with a warning
+	pub(crate) fn new(bpm: f32, start_offset: f32, num_brick_types: u8, lanes: u8, notes: Vec<BrickData>, mut warnings: Vec<String>) -> ImportedChart {
+		let num_brick_types = notes.iter().map(|brick_data| brick_data.brick_type as u8 + 1).fold(num_brick_types.max(1), u8::max);
+		let lanes = notes.iter().map(|brick_data| (brick_data.x_pos + 1).clamp(1, u8::MAX as i32) as u8).fold(lanes.max(1), u8::max);
+
+		// notes are checked in order, so a hold note is kept over the notes that start within it
+		let mut kept = BTreeSet::new();
+		let mut num_rejected = 0;
+		for brick_data in notes {
+			match edit_result::check_add(&kept, &brick_data, num_brick_types, lanes) {
+				Ok(None) => { kept.insert(brick_data); },
+				_ => { num_rejected += 1; }
+			}
+		}
+		if num_rejected > 0 {
+			warnings.push(format!("{} notes overlapped a hold note or were outside the song and were dropped", num_rejected));
+		}
+
+		return ImportedChart { bpm, start_offset, num_brick_types, lanes, notes: kept.into_iter().collect(), warnings };
+	}
+}
+
+// converts an osu!mania (.osu) beatmap
+#[wasm_bindgen]
+pub fn import_osu_chart(text: &str, mapping: &ColumnMapping) -> Result<ImportedChart, String> {
+	let mut warnings = Vec::new();
+	let mut section = "";
+	let mut mode = 0;
+	let mut num_columns = 0;
+	let mut timing_points: Vec<TimingPoint> = Vec::new();
+	let mut num_sv_changes = 0;
+	let mut notes = Vec::new();
+
+	for line in text.lines() {
+		let line = line.trim();
+		if line.is_empty() || line.starts_with("//") {
+			continue;
+		}
+		if line.starts_with('[') && line.ends_with(']') {
+			section = &line[1 .. line.len() - 1];
+			continue;
+		}
+
+		match section {
+			"General" | "Difficulty" => {
+				let (key, value) = match line.split_once(':') {
+					Some((k, v)) => (k.trim(), v.trim()),
+					None => { continue; }
+				};
+				if key == "Mode" {
+					mode = parse_num(value, "Mode")?;
+				} else if key == "CircleSize" {
+					num_columns = parse_finite(value, "CircleSize")? as usize;
+				}
+			},
+			"TimingPoints" => {
+				let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+				if fields.len() < 2 {
+					return Err(format!("Timing point with too few fields: {}", line));
+				}
+				let time = parse_finite(fields[0], "timing point time")? / 1000.0;
+				let beat_length = parse_finite(fields[1], "timing point beat length")?;
+				let uninherited = if fields.len() > 6 { fields[6] != "0" } else { true };
+
+				if uninherited && beat_length > 0.0 {
+					let bpm = 60000.0 / beat_length;
+					if !bpm.is_finite() {
+						return Err(format!("Invalid timing point beat length: {}", fields[1]));
+					}
+					timing_points.push(TimingPoint { time, bpm });
+				} else {
+					num_sv_changes += 1;
+				}
+			},
+			"HitObjects" => {
+				let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+				if fields.len() < 4 {
+					return Err(format!("Hit object with too few fields: {}", line));
+				}
+				if num_columns == 0 {
+					return Err("Hit objects found before the key count (CircleSize)".to_string());
+				}
+				let x = parse_finite(fields[0], "hit object x")?;
+				let time = parse_finite(fields[2], "hit object time")? / 1000.0;
+				let object_type: i32 = parse_num(fields[3], "hit object type")?;
+				let column = ((x * num_columns as f32 / OSU_PLAYFIELD_WIDTH).floor().max(0.0) as usize).min(num_columns - 1);
+
+				let mut end_time = None;
+				if object_type & OSU_HOLD_TYPE != 0 {
+					let end_field = fields.get(5).and_then(|f| f.split(':').next()).unwrap_or("");
+					end_time = Some(parse_finite(end_field, "hold end time")? / 1000.0);
+				}
+
+				notes.push(ChartNote { column, time, end_time });
+			},
+			_ => {}
+		}
+	}
+
+	if mode != OSU_MANIA_MODE {
+		return Err(format!("Beatmap is for mode {}, only osu!mania (mode 3) beatmaps can be imported", mode));
+	}
+	if num_sv_changes > 0 {
+		warnings.push(format!("{} scroll velocity changes were ignored", num_sv_changes));
+	}
+
+	timing_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+	return convert_notes(notes, num_columns, &timing_points, mapping, warnings);
+}
+
+// converts one of the charts of a StepMania (.sm) simfile. chart_idx is the index of the #NOTES section to read
+#[wasm_bindgen]
+pub fn import_sm_chart(text: &str, chart_idx: usize, mapping: &ColumnMapping) -> Result<ImportedChart, String> {
+	let mut warnings = Vec::new();
+	let mut offset = 0.0;
+	let mut bpm_changes: Vec<(f32, f32)> = Vec::new(); // (beat, bpm)
+	let mut stops: Vec<(f32, f32)> = Vec::new(); // (beat, seconds)
+	let mut note_data = None;
+	let mut num_charts = 0;
+
+	// strip comments, then read the #TAG:value; pairs
+	let text: String = text.lines().map(|l| match l.find("//") { Some(i) => &l[..i], None => l }).collect::<Vec<&str>>().join("\n");
+	for tag in text.split(';') {
+		let tag = tag.trim();
+		let (name, value) = match tag.strip_prefix('#').and_then(|t| t.split_once(':')) {
+			Some((n, v)) => (n.trim().to_uppercase(), v.trim()),
+			None => { continue; }
+		};
+
+		match name.as_str() {
+			"OFFSET" => { offset = parse_finite(value, "#OFFSET")?; },
+			"BPMS" => { bpm_changes = parse_beat_pairs(value, "#BPMS")?; },
+			"STOPS" | "FREEZES" => { stops = parse_beat_pairs(value, "#STOPS")?; },
+			"NOTES" => {
+				if num_charts == chart_idx {
+					let parts: Vec<&str> = value.splitn(6, ':').collect();
+					if parts.len() < 6 {
+						return Err("#NOTES section with fewer than 6 fields".to_string());
+					}
+					note_data = Some(parts[5].to_string());
+				}
+				num_charts += 1;
+			},
+			_ => {}
+		}
+	}
+
+	let note_data = match note_data {
+		Some(nd) => nd,
+		None => { return Err(format!("Chart {} not found, the simfile has {} charts", chart_idx, num_charts)); }
+	};
+	if bpm_changes.is_empty() {
+		return Err("The simfile has no #BPMS".to_string());
+	}
+	if let Some((_, bpm)) = bpm_changes.iter().find(|(_, bpm)| *bpm <= 0.0) {
+		return Err(format!("Invalid #BPMS bpm: {}", bpm));
+	}
+
+	// beat 0 is at -#OFFSET seconds into the audio. build timing points in seconds from the bpm changes and stops
+	let mut timing_points: Vec<TimingPoint> = Vec::new();
+	bpm_changes.sort_by(|a, b| a.0.total_cmp(&b.0));
+	stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+	let beat_to_time = |beat: f32| -> f32 {
+		let mut time = -offset;
+		let mut prev_beat = 0.0;
+		let mut prev_bpm = bpm_changes[0].1;
+		for (change_beat, bpm) in &bpm_changes {
+			if *change_beat >= beat {
+				break;
+			}
+			time += (change_beat - prev_beat).max(0.0) * 60.0 / prev_bpm;
+			prev_beat = change_beat.max(prev_beat);
+			prev_bpm = *bpm;
+		}
+		time += (beat - prev_beat) * 60.0 / prev_bpm;
+		for (stop_beat, seconds) in &stops {
+			if *stop_beat < beat {
+				time += seconds;
+			}
+		}
+		return time;
+	};
+	for (beat, bpm) in &bpm_changes {
+		timing_points.push(TimingPoint { time: beat_to_time(*beat), bpm: *bpm });
+	}
+
+	// read the measures of the note data
+	let mut notes = Vec::new();
+	let mut num_columns = 0;
+	let mut open_holds: Vec<Option<usize>> = Vec::new(); // index into notes of a hold which hasn't ended yet, by column
+	let (mut num_mines, mut num_rolls, mut num_lifts, mut num_fakes) = (0, 0, 0, 0);
+
+	for (measure_idx, measure) in note_data.split(',').enumerate() {
+		let rows: Vec<&str> = measure.lines().map(|r| r.trim()).filter(|r| !r.is_empty()).collect();
+		for (row_idx, row) in rows.iter().enumerate() {
+			if num_columns == 0 {
+				num_columns = row.len();
+				open_holds = vec![None; num_columns];
+			}
+
+			let beat = 4.0 * measure_idx as f32 + 4.0 * row_idx as f32 / rows.len() as f32;
+			for (column, c) in row.chars().enumerate().take(num_columns) {
+				match c {
+					'1' => { notes.push(ChartNote { column, time: beat_to_time(beat), end_time: None }); },
+					'2' | '4' => {
+						if c == '4' {
+							num_rolls += 1;
+						}
+						open_holds[column] = Some(notes.len());
+						notes.push(ChartNote { column, time: beat_to_time(beat), end_time: None });
+					},
+					'3' => {
+						if let Some(idx) = open_holds[column].take() {
+							notes[idx].end_time = Some(beat_to_time(beat));
+						}
+					},
+					'L' => {
+						num_lifts += 1;
+						notes.push(ChartNote { column, time: beat_to_time(beat), end_time: None });
+					},
+					'M' => { num_mines += 1; },
+					'F' => { num_fakes += 1; },
+					_ => {}
+				}
+			}
+		}
+	}
+
+	if num_mines > 0 { warnings.push(format!("{} mines were dropped", num_mines)); }
+	if num_rolls > 0 { warnings.push(format!("{} rolls were converted into hold notes", num_rolls)); }
+	if num_lifts > 0 { warnings.push(format!("{} lifts were converted into regular notes", num_lifts)); }
+	if num_fakes > 0 { warnings.push(format!("{} fake notes were dropped", num_fakes)); }
+
+	return convert_notes(notes, num_columns, &timing_points, mapping, warnings);
+}
+
+// converts notes timed in seconds into bricks at the chart's main bpm, with beat pos 0 on the beat grid of the main timing point
+fn convert_notes(notes: Vec<ChartNote>, num_columns: usize, timing_points: &[TimingPoint], mapping: &ColumnMapping,
+mut warnings: Vec<String>) -> Result<ImportedChart, String> {
+	if timing_points.is_empty() {
+		return Err("The chart has no timing points".to_string());
+	}
+
+	// the main bpm is the one that lasts the longest. other bpms are snapped onto its grid
+	let mut main_tp = &timing_points[0];
+	let mut longest_duration = -1.0;
+	let last_note_time = notes.iter().map(|n| n.end_time.unwrap_or(n.time)).fold(0.0, f32::max);
+	for (i, tp) in timing_points.iter().enumerate() {
+		let end_time = if i + 1 < timing_points.len() { timing_points[i + 1].time } else { last_note_time.max(tp.time) };
+		let duration = end_time - tp.time;
+		if duration > longest_duration {
+			longest_duration = duration;
+			main_tp = tp;
+		}
+	}
+	let bpm = main_tp.bpm;
+	if timing_points.iter().any(|tp| (tp.bpm - bpm).abs() > 0.01) {
+		warnings.push(format!("The chart changes tempo. Notes were placed on the grid of its main bpm, {}", bpm));
+	}
+
+	// beat pos 0 is on the main timing point's beat grid, as early as possible without being before the start of the audio
+	let seconds_per_beat = 60.0 / bpm;
+	let mut start_offset = main_tp.time % seconds_per_beat;
+	if start_offset < 0.0 {
+		start_offset += seconds_per_beat;
+	}
+
+	let mut bricks = BTreeSet::new();
+	let mut num_off_grid = 0;
+	let mut num_before_start = 0;
+	let mut num_overlapping = 0;
+	for note in &notes {
+		let time = note.time - start_offset;
+		if time < 0.0 {
+			num_before_start += 1;
+			continue;
+		}
+
+		let beat_pos_passed = time / (60.0 / bpm) * 4.0;
+		let beat_pos = beat_pos_passed.round() as i32;
+		if (beat_pos_passed - beat_pos as f32).abs() > OFF_GRID_TOLERANCE {
+			num_off_grid += 1;
+		}
+
+		let (x_pos, brick_type) = mapping.column(note.column, num_columns);
+		let (is_hold_note, end_beat_pos) = match note.end_time {
+			Some(end_time) => {
+				let end_beat_pos = BrickData::quantized_beat_pos(end_time - start_offset, bpm, 1);
+				if end_beat_pos > beat_pos { (true, end_beat_pos) } else { (false, beat_pos) }
+			},
+			None => (false, beat_pos)
+		};
+
+		let brick_data = BrickData {
+			brick_type,
+			beat_pos,
+			end_beat_pos,
+			x_pos,
+			is_triplet: false,
+			is_trailing: false,
+			is_leading: false,
+			is_hold_note
+		};
+		if !bricks.insert(brick_data) {
+			num_overlapping += 1;
+		}
+	}
+
+	if num_off_grid > 0 {
+		warnings.push(format!("{} notes were not on the sixteenth note grid and were snapped to it", num_off_grid));
+	}
+	if num_before_start > 0 {
+		warnings.push(format!("{} notes before the start of the audio were dropped", num_before_start));
+	}
+	if num_overlapping > 0 {
+		warnings.push(format!("{} notes landed on the same lane and beat as another note and were dropped", num_overlapping));
+	}
+
+	return Ok(ImportedChart::new(bpm, start_offset, mapping.num_brick_types, mapping.lanes, bricks.into_iter().collect(), warnings));
+}
+
+// parses a list of beat=value pairs, like those of #BPMS and #STOPS
+fn parse_beat_pairs(value: &str, tag: &str) -> Result<Vec<(f32, f32)>, String> {
+	let mut pairs = Vec::new();
+	for pair in value.split(',') {
+		let pair = pair.trim();
+		if pair.is_empty() {
+			continue;
+		}
+		let (beat, val) = match pair.split_once('=') {
+			Some(p) => p,
+			None => { return Err(format!("Invalid {} entry: {}", tag, pair)); }
+		};
+		pairs.push((parse_finite(beat.trim(), tag)?, parse_finite(val.trim(), tag)?));
+	}
+	return Ok(pairs);
+}
+
+fn parse_num<T: std::str::FromStr>(value: &str, name: &str) -> Result<T, String> {
+	return match value.parse::<T>() {
+		Ok(v) => Ok(v),
+		Err(_) => Err(format!("Invalid {}: {}", name, value))
+	};
+}
+
+// parses a time, bpm, or other float, rejecting values like "nan" and "inf" which parse but can't be placed in a chart
+fn parse_finite(value: &str, name: &str) -> Result<f32, String> {
+	let num: f32 = parse_num(value, name)?;
+	if !num.is_finite() {
+		return Err(format!("Invalid {}: {}", name, value));
+	}
+	return Ok(num);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// (brick type, beat pos, end beat pos, x_pos, is hold note) of each note
+	fn bricks(chart: &ImportedChart) -> Vec<(BrickType, i32, i32, i32, bool)> {
+		return chart.notes.iter().map(|b| (b.brick_type, b.beat_pos, b.end_beat_pos, b.x_pos, b.is_hold_note)).collect();
+	}
+
+	fn has_warning(chart: &ImportedChart, start: &str) -> bool {
+		return chart.warnings.iter().any(|warning| warning.starts_with(start));
+	}
+
+	// 4 keys at 120 bpm, changing at 1.1 seconds to 160 bpm for the rest of the chart
+	const OSU_CHART: &str = "osu file format v14
+
+[General]
+Mode: 3
+
+[Difficulty]
+CircleSize:4
+
+[TimingPoints]
+0,500,4,1,0,100,1,0
+1100,375,4,1,0,100,1,0
+1100,-50,4,1,0,100,0,0
+
+[HitObjects]
+64,192,1100,1,0,0:0:0:0:
+192,192,1475,128,0,2225:0:0:0:0:
+448,192,10100,1,0,0:0:0:0:
+";
+
+	#[test]
+	fn osu_notes_are_on_the_grid_of_the_main_tempo() {
+		let mut mapping = ColumnMapping::new();
+		mapping.set_column(1, 0, BrickType::Type3).unwrap();
+		let chart = import_osu_chart(OSU_CHART, &mapping).unwrap();
+
+		assert_eq!(chart.bpm, 160.0);
+		assert!((chart.start_offset - 0.35).abs() < 0.001, "start offset {}", chart.start_offset);
+		let (lane_0, type_0) = mapping.column(0, 4);
+		let (lane_3, type_3) = mapping.column(3, 4);
+		assert_eq!(bricks(&chart), vec![
+			(type_0, 8, 8, lane_0, false),
+			(BrickType::Type3, 12, 20, 0, true),
+			(type_3, 104, 104, lane_3, false)
+		]);
+		assert!(has_warning(&chart, "The chart changes tempo"));
+		assert!(has_warning(&chart, "1 scroll velocity changes"));
+		assert!(!chart.warnings.iter().any(|warning| warning.contains("snapped")));
+	}
+
+	#[test]
+	fn osu_chart_for_another_mode_is_rejected() {
+		assert!(import_osu_chart(&OSU_CHART.replace("Mode: 3", "Mode: 0"), &ColumnMapping::new()).is_err());
+	}
+
+	// beat 0 is half a second into the audio. 120 bpm for the first measure, then 240 bpm
+	const SM_CHART: &str = "#TITLE:test;
+#OFFSET:-0.5;
+#BPMS:0=120,4=240;
+#NOTES:
+     dance-single:
+     :
+     Beginner:
+     1:
+     0,0,0,0,0:
+1000
+0000
+0000
+0000
+,
+2000
+0000
+3000
+0100
+;";
+
+	#[test]
+	fn sm_notes_and_holds_follow_the_tempo_change() {
+		let mut mapping = ColumnMapping::new();
+		mapping.set_column(0, 4, BrickType::Type2).unwrap();
+		let chart = import_sm_chart(SM_CHART, 0, &mapping).unwrap();
+
+		// 120 bpm lasts longer than 240 does before the last note, so it's the main bpm
+		assert_eq!(chart.bpm, 120.0);
+		assert_eq!(chart.start_offset, 0.0);
+		let (lane_1, type_1) = mapping.column(1, 4);
+		assert_eq!(bricks(&chart), vec![
+			(BrickType::Type2, 4, 4, 4, false),
+			(BrickType::Type2, 20, 24, 4, true),
+			(type_1, 26, 26, lane_1, false)
+		]);
+		assert!(has_warning(&chart, "The chart changes tempo"));
+	}
+
+	#[test]
+	fn notes_the_editor_would_reject_are_dropped() {
+		let notes = vec![
+			BrickData::new(BrickType::Type1, 0, 8, 2, false, false, false, true),
+			BrickData::new(BrickType::Type1, 4, 4, 2, false, false, false, false), // inside the hold
+			BrickData::new(BrickType::Type2, 6, 10, 2, false, false, false, true), // overlapping the hold
+			BrickData::new(BrickType::Type1, 4, 4, 3, false, false, false, false),
+			BrickData::new(BrickType::Type1, -4, -4, 3, false, false, false, false) // before the start
+		];
+		let chart = ImportedChart::new(120.0, 0.0, 3, 8, notes, Vec::new());
+
+		assert_eq!(bricks(&chart), vec![(BrickType::Type1, 0, 8, 2, true), (BrickType::Type1, 4, 4, 3, false)]);
+		assert_eq!(chart.warnings, vec!["3 notes overlapped a hold note or were outside the song and were dropped".to_string()]);
+	}
+
+	#[test]
+	fn mapping_a_column_outside_the_lanes_fails() {
+		let mut mapping = ColumnMapping::new();
+		assert!(mapping.set_column(0, DEFAULT_LANES as i32, BrickType::Type1).is_err());
+		assert!(mapping.set_column(0, -1, BrickType::Type1).is_err());
+	}
+}
@@ -0,0 +1,305 @@
+
+use crate::objects;
+use crate::objects::ObjectBounds;
+
+// how close colliders must come to count as touching when sweeping, how many steps of conservative advancement to take
+	// before searching for the contact instead, and how many steps the search takes
+const CONTACT_TOLERANCE: f32 = 0.01;
+const MAX_CONTACT_STEPS: u32 = 64;
+const CONTACT_SEARCH_STEPS: u32 = 48;
+
+// the shape of an object within its bounds
+#[derive(Clone, PartialEq, Debug)]
+pub enum Shape {
+	Rect,
+	RoundedRect { radius: f32 }, // corners rounded with the radius, limited to half the width or height
+	ConvexPolygon(Vec<(f32, f32)>) // vertices in order around the polygon, relative to the top left of the bounds
+}
+
+// an object's bounds and the shape within them that counts for collisions
+#[derive(Clone, PartialEq, Debug)]
+pub struct Collider {
+	pub bounds: ObjectBounds,
+	pub shape: Shape
+}
+
+impl Collider {
+	pub fn rect(bounds: ObjectBounds) -> Collider {
+		return Collider { bounds, shape: Shape::Rect };
+	}
+
+	pub fn rounded_rect(bounds: ObjectBounds, radius: f32) -> Collider {
+		return Collider { bounds, shape: Shape::RoundedRect { radius } };
+	}
+
+	pub fn convex_polygon(bounds: ObjectBounds, vertices: Vec<(f32, f32)>) -> Collider {
+		return Collider { bounds, shape: Shape::ConvexPolygon(vertices) };
+	}
+
+	// the time within the duration at which the other collider, moving at the velocity (in pixels per second), first touches this one.
+		// 0 if they already overlap, None if they don't touch within the duration
+	pub fn contact_time(&self, other: &Collider, velocity_x: f32, velocity_y: f32, duration: f32) -> Option<f32> {
+		// the other collider can't reach this one if the bounds it sweeps through don't
+		let end = other.translated(velocity_x * duration, velocity_y * duration);
+		let swept_bounds = ObjectBounds {
+			left_x: other.bounds.left_x.min(end.bounds.left_x),
+			right_x: other.bounds.right_x.max(end.bounds.right_x),
+			top_y: other.bounds.top_y.min(end.bounds.top_y),
+			bottom_y: other.bounds.bottom_y.max(end.bounds.bottom_y)
+		};
+		if !objects::intersect(&self.bounds, &swept_bounds) {
+			return None;
+		}
+
+		// conservative advancement: moving the other collider by the gap between them can never pass through this one
+		let speed = (velocity_x * velocity_x + velocity_y * velocity_y).sqrt();
+		let mut moved = other.clone();
+		let mut time = 0.0;
+		for _ in 0..MAX_CONTACT_STEPS {
+			let gap = self.gap(&moved);
+			if gap <= CONTACT_TOLERANCE {
+				return Some(time);
+			}
+			if speed <= 0.0 {
+				return None;
+			}
+
+			let step = gap / speed;
+			time += step;
+			if time > duration {
+				return None;
+			}
+			moved = moved.translated(velocity_x * step, velocity_y * step);
+		}
+
+		// advancement is slow when the other collider approaches at a shallow angle, creeping closer by a fraction of the gap each step.
+			// The gap between convex shapes moving in a straight line is convex over time, so search for where it's smallest
+			// and then for the first time it's within the tolerance
+		let gap_at = |t: f32| self.gap(&other.translated(velocity_x * t, velocity_y * t));
+		let (mut low, mut high) = (time, duration);
+		for _ in 0..CONTACT_SEARCH_STEPS {
+			let third = (high - low) / 3.0;
+			if gap_at(low + third) < gap_at(high - third) {
+				high -= third;
+			} else {
+				low += third;
+			}
+		}
+		let closest = (low + high) / 2.0;
+		if gap_at(closest) > CONTACT_TOLERANCE {
+			return None;
+		}
+
+		let (mut low, mut high) = (time, closest);
+		for _ in 0..CONTACT_SEARCH_STEPS {
+			let mid = (low + high) / 2.0;
+			if gap_at(mid) <= CONTACT_TOLERANCE {
+				high = mid;
+			} else {
+				low = mid;
+			}
+		}
+		return Some(high);
+	}
+
+	pub fn translated(&self, dx: f32, dy: f32) -> Collider {
+		let mut collider = self.clone();
+		collider.bounds.left_x += dx;
+		collider.bounds.right_x += dx;
+		collider.bounds.top_y += dy;
+		collider.bounds.bottom_y += dy;
+		return collider;
+	}
+
+	pub fn contains(&self, x: f32, y: f32) -> bool {
+		if x < self.bounds.left_x || x > self.bounds.right_x || y < self.bounds.top_y || y > self.bounds.bottom_y {
+			return false;
+		}
+		if self.shape == Shape::Rect {
+			return true;
+		}
+
+		let (vertices, radius) = self.core();
+		return distance(&vertices, &[(x, y)]) <= radius;
+	}
+
+	// the distance between the two shapes, 0 or less if they overlap
+	fn gap(&self, other: &Collider) -> f32 {
+		let (vertices, radius) = self.core();
+		let (other_vertices, other_radius) = other.core();
+		return distance(&vertices, &other_vertices) - radius - other_radius;
+	}
+
+	// every shape is a convex polygon expanded by a radius
+	fn core(&self) -> (Vec<(f32, f32)>, f32) {
+		let b = &self.bounds;
+		let width = b.right_x - b.left_x;
+		let height = b.bottom_y - b.top_y;
+		match &self.shape {
+			Shape::Rect => {
+				return (rect_vertices(b.left_x, b.top_y, b.right_x, b.bottom_y), 0.0);
+			},
+			Shape::RoundedRect { radius } => {
+				let r = radius.max(0.0).min(width / 2.0).min(height / 2.0);
+				return (rect_vertices(b.left_x + r, b.top_y + r, b.right_x - r, b.bottom_y - r), r);
+			},
+			Shape::ConvexPolygon(vertices) => {
+				return (vertices.iter().map(|(x, y)| (b.left_x + x, b.top_y + y)).collect(), 0.0);
+			}
+		}
+	}
+}
+
+fn rect_vertices(left_x: f32, top_y: f32, right_x: f32, bottom_y: f32) -> Vec<(f32, f32)> {
+	return vec![(left_x, top_y), (right_x, top_y), (right_x, bottom_y), (left_x, bottom_y)];
+}
+
+// the distance between two convex polygons, 0 if they overlap
+fn distance(a: &[(f32, f32)], b: &[(f32, f32)]) -> f32 {
+	// with no separating axis the polygons overlap. Points and lines have no area, so only an axis of a polygon can rule out an overlap
+	if (a.len() >= 3 || b.len() >= 3) && !separated(a, b) && !separated(b, a) {
+		return 0.0;
+	}
+
+	let mut min_distance = f32::MAX;
+	for (vertices, edges) in [(a, b), (b, a)] {
+		for p in vertices {
+			for i in 0..edges.len() {
+				let d = point_segment_distance(*p, edges[i], edges[(i + 1) % edges.len()]);
+				min_distance = min_distance.min(d);
+			}
+		}
+	}
+	return min_distance;
+}
+
+// checks if an edge normal of polygon a separates it from polygon b
+fn separated(a: &[(f32, f32)], b: &[(f32, f32)]) -> bool {
+	if a.len() < 2 {
+		return false;
+	}
+
+	for i in 0..a.len() {
+		let (x1, y1) = a[i];
+		let (x2, y2) = a[(i + 1) % a.len()];
+		let axis = (y1 - y2, x2 - x1);
+		let (a_min, a_max) = project(a, axis);
+		let (b_min, b_max) = project(b, axis);
+		if a_max < b_min || b_max < a_min {
+			return true;
+		}
+	}
+	return false;
+}
+
+fn project(vertices: &[(f32, f32)], axis: (f32, f32)) -> (f32, f32) {
+	let mut min = f32::MAX;
+	let mut max = f32::MIN;
+	for (x, y) in vertices {
+		let p = x * axis.0 + y * axis.1;
+		min = min.min(p);
+		max = max.max(p);
+	}
+	return (min, max);
+}
+
+fn point_segment_distance(p: (f32, f32), start: (f32, f32), end: (f32, f32)) -> f32 {
+	let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+	let length_squared = dx * dx + dy * dy;
+	let t;
+	if length_squared > 0.0 {
+		t = (((p.0 - start.0) * dx + (p.1 - start.1) * dy) / length_squared).clamp(0.0, 1.0);
+	} else {
+		t = 0.0;
+	}
+	let (closest_x, closest_y) = (start.0 + t * dx, start.1 + t * dy);
+	return ((p.0 - closest_x).powi(2) + (p.1 - closest_y).powi(2)).sqrt();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bounds(left_x: f32, top_y: f32, right_x: f32, bottom_y: f32) -> ObjectBounds {
+		return ObjectBounds { left_x, right_x, top_y, bottom_y };
+	}
+
+	fn assert_near(actual: f32, expected: f32, tolerance: f32) {
+		assert!((actual - expected).abs() <= tolerance, "{} is not within {} of {}", actual, tolerance, expected);
+	}
+
+	#[test]
+	fn overlapping_polygons_have_no_distance() {
+		let a = rect_vertices(0.0, 0.0, 10.0, 10.0);
+		let b = rect_vertices(5.0, 5.0, 15.0, 15.0);
+		assert!(!separated(&a, &b) && !separated(&b, &a));
+		assert_eq!(distance(&a, &b), 0.0);
+	}
+
+	// the bounds of a diamond and a square overlap, but the diamond's diagonal edge separates them
+	#[test]
+	fn an_edge_normal_separates_polygons_whose_bounds_overlap() {
+		let diamond = vec![(5.0, 0.0), (10.0, 5.0), (5.0, 10.0), (0.0, 5.0)];
+		let square = rect_vertices(8.0, 8.0, 12.0, 12.0);
+		assert!(separated(&diamond, &square));
+		assert!(!separated(&square, &diamond));
+		assert_near(distance(&diamond, &square), 1.0 / 2.0_f32.sqrt(), 0.0001);
+
+		let diamond = Collider::convex_polygon(bounds(0.0, 0.0, 10.0, 10.0), diamond);
+		assert!(diamond.gap(&Collider::rect(bounds(8.0, 8.0, 12.0, 12.0))) > 0.0);
+		assert!(!diamond.contains(9.0, 9.0));
+		assert!(diamond.contains(5.0, 5.0));
+	}
+
+	#[test]
+	fn distance_is_to_the_closest_edge_or_corner() {
+		let a = rect_vertices(0.0, 0.0, 10.0, 10.0);
+		assert_near(distance(&a, &rect_vertices(15.0, 2.0, 20.0, 8.0)), 5.0, 0.0001);
+		assert_near(distance(&a, &rect_vertices(13.0, 14.0, 20.0, 20.0)), 5.0, 0.0001);
+		assert_near(point_segment_distance((5.0, 3.0), (0.0, 0.0), (10.0, 0.0)), 3.0, 0.0001);
+		assert_near(point_segment_distance((13.0, 4.0), (0.0, 0.0), (10.0, 0.0)), 5.0, 0.0001);
+	}
+
+	#[test]
+	fn rounded_corners_are_empty() {
+		let a = Collider::rounded_rect(bounds(0.0, 0.0, 10.0, 10.0), 2.0);
+		let b = Collider::rounded_rect(bounds(12.0, 12.0, 20.0, 20.0), 2.0);
+		// the corners of the cores are at (8, 8) and (14, 14)
+		assert_near(a.gap(&b), 72.0_f32.sqrt() - 4.0, 0.0001);
+		assert!(!a.contains(0.2, 0.2));
+		assert!(a.contains(0.2, 5.0));
+		assert!(a.gap(&Collider::rect(bounds(9.5, 9.5, 12.0, 12.0))) > 0.0);
+	}
+
+	#[test]
+	fn contact_time_of_an_approaching_collider() {
+		let hitbox = Collider::rect(bounds(0.0, 0.0, 10.0, 10.0));
+		let brick = Collider::rounded_rect(bounds(2.0, 20.0, 8.0, 30.0), 2.0);
+		assert_near(hitbox.contact_time(&brick, 0.0, -100.0, 1.0).unwrap(), 0.1, CONTACT_TOLERANCE / 100.0);
+		assert_eq!(hitbox.contact_time(&brick, 0.0, -100.0, 0.05), None);
+		assert_eq!(hitbox.contact_time(&brick, 0.0, 100.0, 1.0), None);
+		assert_eq!(hitbox.contact_time(&hitbox.translated(5.0, 5.0), 0.0, -100.0, 1.0), Some(0.0));
+	}
+
+	// moving almost parallel to an edge, each step of conservative advancement covers only a small part of the gap,
+		// so the contact is found by searching after MAX_CONTACT_STEPS
+	#[test]
+	fn contact_time_of_a_near_tangent_approach() {
+		let hitbox = Collider::rect(bounds(0.0, 0.0, 1000.0, 10.0));
+		let other = Collider::rect(bounds(0.0, -5.0, 1.0, -4.0));
+		let contact_time = hitbox.contact_time(&other, 1000.0, 4.0, 2.0).unwrap();
+		assert_near(contact_time, 1.0, CONTACT_TOLERANCE);
+		assert!(hitbox.gap(&other.translated(1000.0 * contact_time, 4.0 * contact_time)) <= CONTACT_TOLERANCE);
+
+		// sliding past the edge just outside of the tolerance never touches
+		let other = Collider::rect(bounds(0.0, -1.02, 1.0, -0.02));
+		assert_eq!(hitbox.contact_time(&other, 1000.0, 0.0, 2.0), None);
+
+		// grazing a rounded corner
+		let corner = Collider::rounded_rect(bounds(0.0, 0.0, 10.0, 10.0), 5.0);
+		let other = Collider::rect(bounds(10.0 - 0.001, 100.0, 11.0, 101.0));
+		let contact_time = corner.contact_time(&other, 0.0, -100.0, 2.0).unwrap();
+		assert!(corner.gap(&other.translated(0.0, -100.0 * contact_time)) <= CONTACT_TOLERANCE);
+		assert!(corner.gap(&other.translated(0.0, -100.0 * (contact_time - 0.001))) > 0.0);
+	}
+}
@@ -0,0 +1,47 @@
+
+use wasm_bindgen::prelude::*;
+
+use crate::objects::PLAYER_WIDTH;
+use crate::objects::BRICK_HEIGHT;
+
+// the gameplay tuning of a game, passed to Game::new. GameConfig::new() has the values the game was designed with
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct GameConfig {
+	pub min_pre_slash_time: f32, // how long a slash or dash waits for the other input to make a slash dash, in seconds
+	pub max_pre_slash_time: f32, // how long a slash or dash waits on a group of notes
+	pub pre_hold_time: f32, // how long after a slash the player goes into a hold if the slash key is still down
+	pub post_slash_time: f32, // how long after a slash the player stays in place
+	pub stunned_time: f32, // how long the player is stunned after missing a target
+	pub sprint_speed: f32, // in pixels per second
+	pub run_speed: f32, // in pixels per second
+	pub walk_speed: f32, // in pixels per second
+	pub max_boost_distance: f32, // the furthest the player may boost to a target, in pixels. Targets further away need a dash
+	pub early_arrival_distance: f32, // how far (in pixels of brick scroll) before a target becomes hittable the player arrives at it
+	pub brick_score: i32
+}
+
+#[wasm_bindgen]
+impl GameConfig {
+	pub fn new() -> GameConfig {
+		return GameConfig {
+			min_pre_slash_time: 0.015,
+			max_pre_slash_time: 0.06,
+			pre_hold_time: 0.24,
+			post_slash_time: 0.08,
+			stunned_time: 0.14,
+			sprint_speed: 580.0,
+			run_speed: 400.0,
+			walk_speed: 220.0,
+			max_boost_distance: 4.0 * PLAYER_WIDTH as f32,
+			early_arrival_distance: BRICK_HEIGHT as f32 / 2.0,
+			brick_score: 100
+		};
+	}
+}
+
+impl Default for GameConfig {
+	fn default() -> GameConfig {
+		return GameConfig::new();
+	}
+}
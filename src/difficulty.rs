@@ -0,0 +1,139 @@
+
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::game::TargetInfo;
+use crate::objects::MAX_BRICK_TYPES;
+use crate::config::GameConfig;
+
+const SECTION_LENGTH: f32 = 4.0; // seconds of song per section strain value
+const STRAIN_DECAY: f32 = 0.3; // proportion of strain remaining after 1 second
+const MIN_TARGET_INTERVAL: f32 = 0.05; // shorter times between targets are treated as this long
+const DENSITY_WEIGHT: f32 = 0.5;
+const TRAVEL_WEIGHT: f32 = 1.0;
+const BOOST_WEIGHT: f32 = 0.5; // for targets too far to run to in time, which the player must boost to
+const DASH_WEIGHT: f32 = 1.0; // for targets the player must slash dash to
+const GROUP_WIDTH_WEIGHT: f32 = 0.15; // per lane of a group beyond the first
+const TYPE_SWITCH_WEIGHT: f32 = 0.4; // per brick type of a target not in the previous target
+const HOLD_WEIGHT: f32 = 0.5; // per second of holding
+const SECTION_WEIGHT_DECAY: f32 = 0.9; // weight of each section strain relative to the next hardest section's
+const RATING_SCALE: f32 = 0.62; // puts the included songs near their hand entered difficulties
+
+// how hard a song is to play. The rating is mostly decided by the song's hardest sections,
+	// so long songs of easy sections don't outrate short hard songs
+#[wasm_bindgen]
+pub struct DifficultyRating {
+	pub rating: f32,
+	section_strains: Vec<f32> // the peak strain in each SECTION_LENGTH seconds of the song
+}
+
+#[wasm_bindgen]
+impl DifficultyRating {
+	pub fn section_strains(&self) -> Vec<f32> {
+		return self.section_strains.clone();
+	}
+
+	pub fn section_length() -> f32 {
+		return SECTION_LENGTH;
+	}
+}
+
+// rates the targets of a song. Each target adds strain according to how hard it is to hit coming from the previous target,
+	// and strain decays over time
+pub fn rate_targets(targets: &VecDeque<TargetInfo>, duration: f32, config: &GameConfig) -> DifficultyRating {
+	let num_sections = (duration.max(0.0) / SECTION_LENGTH).ceil() as usize;
+	let mut section_strains = vec![0.0; num_sections];
+
+	let mut strain = 0.0;
+	let mut prev_time = 0.0;
+	let mut prev: Option<&TargetInfo> = None;
+	for target in targets {
+		let time = target.hittable_time.max(0.0);
+		strain *= STRAIN_DECAY.powf(time - prev_time);
+		strain += target_strain(target, prev, config);
+
+		let section = (time / SECTION_LENGTH) as usize;
+		if section >= section_strains.len() {
+			section_strains.resize(section + 1, 0.0);
+		}
+		if strain > section_strains[section] {
+			section_strains[section] = strain;
+		}
+
+		prev_time = time;
+		prev = Some(target);
+	}
+
+	let mut sorted_strains = section_strains.clone();
+	sorted_strains.sort_by(|a, b| b.total_cmp(a));
+	let mut weighted_sum = 0.0;
+	let mut weight = 1.0;
+	for section_strain in sorted_strains {
+		weighted_sum += section_strain * weight;
+		weight *= SECTION_WEIGHT_DECAY;
+	}
+
+	// the weights add up to at most 1 / (1 - SECTION_WEIGHT_DECAY)
+	let rating = RATING_SCALE * weighted_sum * (1.0 - SECTION_WEIGHT_DECAY);
+
+	return DifficultyRating {
+		rating: (rating * 100.0).round() / 100.0,
+		section_strains
+	};
+}
+
+// the strain added by a target, given the target before it
+fn target_strain(target: &TargetInfo, prev: Option<&TargetInfo>, config: &GameConfig) -> f32 {
+	let (prev_time, prev_x, prev_types) = match prev {
+		Some(prev) => (prev.passed_time, prev.post_hit_x, brick_types(prev)),
+		None => (0.0, target.dest_x, [false; MAX_BRICK_TYPES])
+	};
+
+	let interval = match prev {
+		Some(prev) => (target.hittable_time - prev.hittable_time).max(MIN_TARGET_INTERVAL),
+		None => 1.0
+	};
+	let mut target_strain = DENSITY_WEIGHT / interval;
+
+	// travel, relative to how far the player can sprint before the target is hittable
+	let time_to_travel = (target.hittable_time - prev_time).max(MIN_TARGET_INTERVAL);
+	let distance = (target.dest_x - prev_x).abs();
+	let max_run_distance = time_to_travel * config.sprint_speed;
+	target_strain += TRAVEL_WEIGHT * (distance / max_run_distance).min(3.0);
+	if target.dash_to_target {
+		target_strain += DASH_WEIGHT;
+	} else if distance > max_run_distance && distance <= max_run_distance + config.max_boost_distance {
+		target_strain += BOOST_WEIGHT;
+	}
+
+	// group width, in lanes
+	let mut left_x = f32::MAX;
+	let mut right_x = f32::MIN;
+	for brick in &target.brick_group {
+		left_x = left_x.min(brick.x());
+		right_x = right_x.max(brick.x() + brick.width());
+	}
+	let lanes = ((right_x - left_x) / target.brick_group[0].width()).round();
+	target_strain += GROUP_WIDTH_WEIGHT * (lanes - 1.0).max(0.0);
+
+	// brick types that need a different input than the previous target
+	let types = brick_types(target);
+	let num_switches = (0 .. MAX_BRICK_TYPES).filter(|&i| types[i] && !prev_types[i]).count();
+	target_strain += TYPE_SWITCH_WEIGHT * num_switches as f32;
+
+	if target.is_hold_note {
+		target_strain += HOLD_WEIGHT * (target.passed_time - target.hittable_time).max(0.0);
+	}
+
+	return target_strain;
+}
+
+// which of the brick types are in the target
+fn brick_types(target: &TargetInfo) -> [bool; MAX_BRICK_TYPES] {
+	let mut types = [false; MAX_BRICK_TYPES];
+	for brick in &target.brick_group {
+		types[brick.brick_type() as usize] = true;
+	}
+	return types;
+}
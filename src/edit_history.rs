@@ -0,0 +1,290 @@
+
+use std::collections::btree_set::BTreeSet;
+use std::collections::VecDeque;
+
+use crate::BrickData;
+use crate::GameData;
+
+const MAX_HISTORY_STEPS: usize = 256; // the oldest steps are forgotten past this many
+
+// a single change to the song's notes, or to the brick types and lanes that limit them
+#[derive(Clone)]
+pub enum EditCommand {
+	Add(BrickData),
+	Remove(BrickData),
+	SetBrickTypes(u8, u8), // (from, to)
+	SetLanes(u8, u8) // (from, to)
+}
+
+// the undoable history of edits to the song's notes
+	// each step is one or more commands which are undone and redone together
+pub struct EditHistory {
+	steps: VecDeque<Vec<EditCommand>>,
+	position: usize, // number of steps currently applied. Steps at and after the position can be redone
+	saved_position: Option<usize>, // the position when the song was last saved, none if that position is no longer in the history
+	group: Vec<EditCommand>, // commands of the current group, recorded as one step when the outermost group ends
+	group_depth: u32
+}
+
+impl EditHistory {
+	pub fn new() -> EditHistory {
+		return EditHistory {
+			steps: VecDeque::new(),
+			position: 0,
+			saved_position: Some(0),
+			group: Vec::new(),
+			group_depth: 0
+		};
+	}
+
+	// records a command that has already been applied to the notes
+	pub fn record(&mut self, command: EditCommand) {
+		if self.group_depth > 0 {
+			self.group.push(command);
+		} else {
+			self.push_step(vec![command]);
+		}
+	}
+
+	// following commands are recorded as one step until the matching end_group. Groups may be nested
+	pub fn begin_group(&mut self) {
+		self.group_depth += 1;
+	}
+
+	pub fn end_group(&mut self) {
+		if self.group_depth == 0 {
+			return;
+		}
+
+		self.group_depth -= 1;
+		if self.group_depth == 0 && self.group.len() > 0 {
+			let step = std::mem::take(&mut self.group);
+			self.push_step(step);
+		}
+	}
+
+	// reverts the most recent step on the notes and song settings, returning false if there is nothing to undo
+		// steps are undone in the reverse order they were made, so the notes always fit the brick types and lanes
+	pub fn undo(&mut self, notes: &mut BTreeSet<BrickData>, game_data: &mut GameData) -> bool {
+		self.end_open_groups();
+		if self.position == 0 {
+			return false;
+		}
+
+		self.position -= 1;
+		for command in self.steps[self.position].iter().rev() {
+			match command {
+				EditCommand::Add(brick_data) => { notes.remove(brick_data); },
+				EditCommand::Remove(brick_data) => { notes.insert(brick_data.clone()); },
+				EditCommand::SetBrickTypes(from, _) => { game_data.num_brick_types = *from; },
+				EditCommand::SetLanes(from, _) => { game_data.lanes = *from; }
+			}
+		}
+
+		return true;
+	}
+
+	// reapplies the most recently undone step on the notes and song settings, returning false if there is nothing to redo
+	pub fn redo(&mut self, notes: &mut BTreeSet<BrickData>, game_data: &mut GameData) -> bool {
+		self.end_open_groups();
+		if self.position == self.steps.len() {
+			return false;
+		}
+
+		for command in &self.steps[self.position] {
+			match command {
+				EditCommand::Add(brick_data) => { notes.insert(brick_data.clone()); },
+				EditCommand::Remove(brick_data) => { notes.remove(brick_data); },
+				EditCommand::SetBrickTypes(_, to) => { game_data.num_brick_types = *to; },
+				EditCommand::SetLanes(_, to) => { game_data.lanes = *to; }
+			}
+		}
+		self.position += 1;
+
+		return true;
+	}
+
+	pub fn can_undo(&self) -> bool {
+		return self.position > 0 || self.group.len() > 0;
+	}
+
+	pub fn can_redo(&self) -> bool {
+		return self.position < self.steps.len();
+	}
+
+	pub fn mark_saved(&mut self) {
+		self.end_open_groups();
+		self.saved_position = Some(self.position);
+	}
+
+	// whether the notes differ from the saved notes (according to the history)
+	pub fn is_modified(&self) -> bool {
+		return self.saved_position != Some(self.position) || self.group.len() > 0;
+	}
+
+	fn push_step(&mut self, step: Vec<EditCommand>) {
+		// a new step replaces any undone steps
+		self.steps.truncate(self.position);
+		if let Some(saved_position) = self.saved_position {
+			if saved_position > self.position {
+				self.saved_position = None;
+			}
+		}
+
+		self.steps.push_back(step);
+		self.position += 1;
+
+		if self.steps.len() > MAX_HISTORY_STEPS {
+			self.steps.pop_front();
+			self.position -= 1;
+			self.saved_position = match self.saved_position {
+				Some(saved_position) => saved_position.checked_sub(1),
+				None => None
+			};
+		}
+	}
+
+	// undo and redo act on whole steps, so any group left open is closed first
+	fn end_open_groups(&mut self) {
+		while self.group_depth > 0 {
+			self.end_group();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::objects::BrickType;
+
+	fn tap(beat_pos: i32) -> BrickData {
+		return BrickData::new(BrickType::Type1, beat_pos, beat_pos, 0, false, false, false, false);
+	}
+
+	fn game_data() -> GameData {
+		return GameData { bpm: 120.0, beat_interval: 0.5, brick_speed: 600.0, time_running: 0.0, score: 0, max_score: 0,
+			duration: 60.0, num_brick_types: 3, lanes: 8, is_modified: false };
+	}
+
+	// adds a brick to the notes and records it, as the game does
+	fn add(history: &mut EditHistory, notes: &mut BTreeSet<BrickData>, beat_pos: i32) {
+		notes.insert(tap(beat_pos));
+		history.record(EditCommand::Add(tap(beat_pos)));
+	}
+
+	fn beat_positions(notes: &BTreeSet<BrickData>) -> Vec<i32> {
+		return notes.iter().map(|brick_data| brick_data.beat_pos).collect();
+	}
+
+	#[test]
+	fn nested_groups_are_one_step() {
+		let (mut history, mut notes, mut game_data) = (EditHistory::new(), BTreeSet::new(), game_data());
+		history.begin_group();
+		add(&mut history, &mut notes, 0);
+		history.begin_group();
+		add(&mut history, &mut notes, 4);
+		history.end_group();
+		assert!(!history.can_redo());
+		add(&mut history, &mut notes, 8);
+		history.end_group();
+		add(&mut history, &mut notes, 12);
+
+		assert!(history.undo(&mut notes, &mut game_data));
+		assert_eq!(beat_positions(&notes), vec![0, 4, 8]);
+		assert!(history.undo(&mut notes, &mut game_data));
+		assert!(notes.is_empty());
+		assert!(!history.undo(&mut notes, &mut game_data));
+
+		assert!(history.redo(&mut notes, &mut game_data));
+		assert_eq!(beat_positions(&notes), vec![0, 4, 8]);
+	}
+
+	#[test]
+	fn a_new_step_replaces_the_undone_steps() {
+		let (mut history, mut notes, mut game_data) = (EditHistory::new(), BTreeSet::new(), game_data());
+		add(&mut history, &mut notes, 0);
+		add(&mut history, &mut notes, 4);
+		history.undo(&mut notes, &mut game_data);
+		assert!(history.can_redo());
+
+		add(&mut history, &mut notes, 8);
+		assert!(!history.can_redo());
+		assert!(!history.redo(&mut notes, &mut game_data));
+		history.undo(&mut notes, &mut game_data);
+		history.undo(&mut notes, &mut game_data);
+		assert!(notes.is_empty());
+		history.redo(&mut notes, &mut game_data);
+		history.redo(&mut notes, &mut game_data);
+		assert_eq!(beat_positions(&notes), vec![0, 8]);
+	}
+
+	#[test]
+	fn undo_closes_an_open_group() {
+		let (mut history, mut notes, mut game_data) = (EditHistory::new(), BTreeSet::new(), game_data());
+		add(&mut history, &mut notes, 0);
+		history.begin_group();
+		history.begin_group();
+		add(&mut history, &mut notes, 4);
+		add(&mut history, &mut notes, 8);
+		assert!(history.is_modified());
+
+		assert!(history.undo(&mut notes, &mut game_data));
+		assert_eq!(beat_positions(&notes), vec![0]);
+		// the group was closed, so later commands are their own steps
+		add(&mut history, &mut notes, 12);
+		history.end_group();
+		history.undo(&mut notes, &mut game_data);
+		assert_eq!(beat_positions(&notes), vec![0]);
+	}
+
+	#[test]
+	fn saved_position_is_forgotten_once_evicted_or_replaced() {
+		let (mut history, mut notes, mut game_data) = (EditHistory::new(), BTreeSet::new(), game_data());
+		assert!(!history.is_modified());
+		add(&mut history, &mut notes, 0);
+		history.mark_saved();
+		add(&mut history, &mut notes, 4);
+		history.undo(&mut notes, &mut game_data);
+		assert!(!history.is_modified());
+
+		// the step before the saved position, then the saved step, are pushed out of the history
+		for beat_pos in 1 ..= MAX_HISTORY_STEPS as i32 + 1 {
+			add(&mut history, &mut notes, beat_pos * 4);
+		}
+		assert_eq!(history.saved_position, None);
+		while history.undo(&mut notes, &mut game_data) {
+			assert!(history.is_modified());
+		}
+		assert_eq!(beat_positions(&notes), vec![0, 4]);
+
+		// undone steps past the saved position are replaced by a new step
+		history.mark_saved();
+		history.redo(&mut notes, &mut game_data);
+		history.undo(&mut notes, &mut game_data);
+		add(&mut history, &mut notes, -4);
+		assert_eq!(history.saved_position, Some(0));
+		history.mark_saved();
+		history.undo(&mut notes, &mut game_data);
+		add(&mut history, &mut notes, -8);
+		assert_eq!(history.saved_position, None);
+	}
+
+	#[test]
+	fn settings_changes_are_undone_with_the_notes() {
+		let (mut history, mut notes, mut game_data) = (EditHistory::new(), BTreeSet::new(), game_data());
+		add(&mut history, &mut notes, 0);
+		history.record(EditCommand::SetLanes(8, 4));
+		history.record(EditCommand::SetBrickTypes(3, 2));
+		game_data.lanes = 4;
+		game_data.num_brick_types = 2;
+
+		history.undo(&mut notes, &mut game_data);
+		assert_eq!((game_data.num_brick_types, game_data.lanes), (3, 4));
+		history.undo(&mut notes, &mut game_data);
+		assert_eq!((game_data.num_brick_types, game_data.lanes), (3, 8));
+		history.redo(&mut notes, &mut game_data);
+		history.redo(&mut notes, &mut game_data);
+		assert_eq!((game_data.num_brick_types, game_data.lanes), (2, 4));
+		assert_eq!(notes.len(), 1);
+	}
+}
@@ -0,0 +1,96 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::BrickData;
+use crate::selection;
+
+// what a successful editor call did
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditOutcome {
+	Inserted,
+	Replaced, // a brick at the same position (with a different type or hold) was replaced
+	Removed
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditErrorCode {
+	OverlappingHold, // the brick would overlap a hold note in its lane, or is a hold note overlapping another brick
+	LaneOutOfRange,
+	BeatOutOfRange, // the brick would be before the start of the song
+	InvalidEndBeat, // a hold note that doesn't end after it starts, or a tap with an end beat pos different from its beat pos
+	TypeOutOfRange, // the brick's type is beyond the song's number of brick types
+	NotFound // no brick to remove at the position
+}
+
+// why an editor call failed. Thrown to javascript as an object with a code and a message
+#[wasm_bindgen]
+#[derive(Clone, Debug)]
+pub struct EditError {
+	pub code: EditErrorCode,
+	message: String
+}
+
+#[wasm_bindgen]
+impl EditError {
+	#[wasm_bindgen(getter)]
+	pub fn message(&self) -> String {
+		return self.message.clone();
+	}
+}
+
+impl EditError {
+	pub fn new(code: EditErrorCode, message: String) -> EditError {
+		return EditError { code, message };
+	}
+}
+
+// how many bricks of an import were added to the song, and how many were left out because check_add rejected them
+	// or a brick of the song was already in their place
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct ImportCount {
+	pub added: usize,
+	pub rejected: usize
+}
+
+// checks that a brick can be added to the notes. Returns the brick it would replace, if any
+pub fn check_add(notes: &BTreeSet<BrickData>, brick_data: &BrickData, num_brick_types: u8, lanes: u8) -> Result<Option<BrickData>, EditError> {
+	let lanes = lanes as i32;
+	if brick_data.x_pos < 0 || brick_data.x_pos >= lanes {
+		return Err(EditError::new(EditErrorCode::LaneOutOfRange,
+			format!("Lane {} is outside of the lanes 0 to {}", brick_data.x_pos, lanes - 1)));
+	}
+	if brick_data.beat_pos < 0 {
+		return Err(EditError::new(EditErrorCode::BeatOutOfRange,
+			format!("Beat pos {} is before the start of the song", brick_data.beat_pos)));
+	}
+	if brick_data.brick_type as u8 >= num_brick_types {
+		return Err(EditError::new(EditErrorCode::TypeOutOfRange,
+			format!("Brick type {} is outside of the song's brick types 1 to {}", brick_data.brick_type as u8 + 1, num_brick_types)));
+	}
+	if brick_data.is_hold_note && brick_data.end_beat_pos <= brick_data.beat_pos {
+		return Err(EditError::new(EditErrorCode::InvalidEndBeat,
+			format!("A hold note starting at beat pos {} must end after it, not at {}", brick_data.beat_pos, brick_data.end_beat_pos)));
+	}
+	if !brick_data.is_hold_note && brick_data.end_beat_pos != brick_data.beat_pos {
+		return Err(EditError::new(EditErrorCode::InvalidEndBeat,
+			format!("A brick that isn't a hold note must end where it starts, at beat pos {}", brick_data.beat_pos)));
+	}
+
+	let replaced = notes.get(brick_data).cloned();
+	for other in notes {
+		if replaced.as_ref() == Some(other) {
+			continue;
+		}
+		if (other.is_hold_note || brick_data.is_hold_note) && selection::overlaps(brick_data, other) {
+			return Err(EditError::new(EditErrorCode::OverlappingHold,
+				format!("The brick would overlap the brick at beat pos {} in lane {}", other.beat_pos, other.x_pos)));
+		}
+	}
+
+	return Ok(replaced);
+}
@@ -0,0 +1,81 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use crate::BrickData;
+use crate::Graphic;
+use crate::PositionedGraphic;
+use crate::resources::GraphicGroup;
+use crate::playfield::Playfield;
+use crate::objects::BRICK_HEIGHT;
+
+use crate::GAME_HEIGHT;
+use crate::TIME_ZERO_BRICK_POS;
+
+pub const GRID_LINE_THICKNESS: i32 = 1;
+pub const BEAT_LINE_THICKNESS: i32 = 2;
+pub const MEASURE_LINE_THICKNESS: i32 = 4;
+pub const LANE_SEPARATOR_THICKNESS: i32 = 2;
+pub const PLAYHEAD_THICKNESS: i32 = 4;
+
+const BEAT_POSITIONS_PER_BEAT: i32 = 4;
+const BEAT_POSITIONS_PER_MEASURE: i32 = 16;
+
+// lines through the middle of where bricks at each beat pos appear, and lane separators between where bricks at each x pos appear
+	// measure and beat lines are always drawn, and grid lines every grid number of beat positions in between
+pub fn grid_graphics(playfield: &Playfield, scrolled_y: f32, beat_interval: f32, brick_speed: f32, grid: i32) -> Vec<PositionedGraphic> {
+	let mut graphics = Vec::new();
+	let pixels_per_beat_pos = brick_speed * beat_interval / BEAT_POSITIONS_PER_BEAT as f32;
+	let grid = grid.max(1);
+
+	// the on screen y of the line at a beat pos, matching BrickData::appearance_y plus half a brick
+	let first_y = TIME_ZERO_BRICK_POS + BRICK_HEIGHT as f32 / 2.0 - scrolled_y;
+	let first_beat_pos = (-first_y / pixels_per_beat_pos).ceil() as i32;
+	let last_beat_pos = ((GAME_HEIGHT - first_y) / pixels_per_beat_pos).floor() as i32;
+
+	for beat_pos in first_beat_pos..=last_beat_pos {
+		let (g, thickness) = if beat_pos.rem_euclid(BEAT_POSITIONS_PER_MEASURE) == 0 {
+			(GraphicGroup::MeasureLine, MEASURE_LINE_THICKNESS)
+		} else if beat_pos.rem_euclid(BEAT_POSITIONS_PER_BEAT) == 0 {
+			(GraphicGroup::BeatLine, BEAT_LINE_THICKNESS)
+		} else if beat_pos.rem_euclid(grid) == 0 {
+			(GraphicGroup::GridLine, GRID_LINE_THICKNESS)
+		} else {
+			continue;
+		};
+
+		let y = first_y + beat_pos as f32 * pixels_per_beat_pos - thickness as f32 / 2.0;
+		graphics.push(PositionedGraphic::new(Graphic { g, frame: 0, flags: 0, arg: 0 }, 0.0, y));
+	}
+
+	for lane in 1..playfield.lanes() as i32 {
+		let x = playfield.lane_x(lane) - LANE_SEPARATOR_THICKNESS as f32 / 2.0;
+		graphics.push(PositionedGraphic::new(Graphic { g: GraphicGroup::LaneSeparator, frame: 0, flags: 0, arg: 0 }, x, 0.0));
+	}
+
+	return graphics;
+}
+
+// highlights over the selected bricks that are on screen
+pub fn selection_graphics(selected: &BTreeSet<BrickData>, playfield: &Playfield, scrolled_y: f32, bpm: f32, brick_speed: f32) -> Vec<PositionedGraphic> {
+	let mut graphics = Vec::new();
+	for brick_data in selected {
+		let y = brick_data.appearance_y(bpm, brick_speed) - scrolled_y;
+		if y > GAME_HEIGHT {
+			break;
+		}
+		if y + (BRICK_HEIGHT as f32) < 0.0 {
+			continue;
+		}
+
+		graphics.push(PositionedGraphic::sized(Graphic { g: GraphicGroup::SelectionHighlight, frame: 0, flags: 0, arg: 0 }, 
+			brick_data.x(playfield), y, playfield.lane_width(), BRICK_HEIGHT as f32));
+	}
+
+	return graphics;
+}
+
+// a line through the middle of where bricks at the current time appear
+pub fn playhead_graphic() -> PositionedGraphic {
+	let y = TIME_ZERO_BRICK_POS + BRICK_HEIGHT as f32 / 2.0 - PLAYHEAD_THICKNESS as f32 / 2.0;
+	return PositionedGraphic::new(Graphic { g: GraphicGroup::Playhead, frame: 0, flags: 0, arg: 0 }, 0.0, y);
+}
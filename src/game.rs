@@ -6,21 +6,48 @@ use std::collections::VecDeque;
 use std::cmp::Ordering;
 
 use crate::objects;
-use crate::player;
+use crate::midi;
+use crate::difficulty;
+use crate::edit_history::EditHistory;
+use crate::edit_history::EditCommand;
+use crate::selection::Selection;
+use crate::selection::SelectionEdit;
+use crate::transform;
+use crate::edit_result;
+use crate::edit_result::EditOutcome;
+use crate::edit_result::EditError;
+use crate::edit_result::EditErrorCode;
+use crate::edit_result::ImportCount;
+use crate::hit_test;
+use crate::editor_render;
+use crate::path_preview;
+use crate::hit_test::HitTest;
+use crate::playfield::Playfield;
 
 use crate::player::Player;
+use crate::brick;
 use crate::brick::HittableBrick;
 use crate::BrickData;
 use crate::GameData;
 use crate::Input;
+use crate::config::GameConfig;
+use crate::QueuedInput;
 use crate::GraphicGroup;
 use crate::Graphic;
 use crate::GraphicFlags;
 use crate::PositionedGraphic;
+use crate::Position;
 use crate::LingeringGraphic;
 use crate::RenderingInstructions;
 use crate::AudioInstructions;
 use crate::SoundEffect;
+use crate::midi::MidiImportOptions;
+use crate::difficulty::DifficultyRating;
+use crate::scheduler::Scheduler;
+use crate::scheduler::Event;
+use crate::scheduler::EventKind;
+use crate::viewport::Viewport;
+use crate::render_queue::RenderQueue;
 use objects::Object;
 use objects::HitBox;
 use objects::BrickType;
@@ -36,20 +63,22 @@ use crate::GAME_HEIGHT;
 use crate::GAME_WIDTH;
 use crate::GROUND_POS;
 use objects::BRICK_HEIGHT;
+use objects::MAX_BRICK_TYPES;
+use objects::DEFAULT_BRICK_TYPES;
+use objects::DEFAULT_LANES;
+use objects::MIN_LANES;
+use objects::MAX_LANES;
 use objects::BRICK_SEGMENT_HEIGHT;
 use objects::BRICK_SEGMENT_GAP;
-use objects::BRICK_WIDTH;
 use objects::HOLD_HITBOX_WIDTH;
-use player::SPRINT_SPEED;
 
 const MAX_TIME_BETWEEN_TICKS: f32 = 0.025;
-const MAX_BOOST_DISTANCE: f32 = 4.0 * PLAYER_WIDTH as f32;
-const EARLY_ARRIVAL_DISTANCE: f32 = BRICK_HEIGHT as f32 / 2.0;
-const BRICK_SCORE: i32 = 100;
+pub const MAX_INPUT_LATENESS: f32 = 0.1; // how long before the last tick an input may be and still be handled
 const HOLD_SEGMENT_SCORE: i32 = 10;
 pub const DASH_INDICATOR_WIDTH: i32 = 85;
 pub const DASH_INDICATOR_HEIGHT: i32 = 60;
 const MISS_EFFECT_TIME: f32 = 0.2;
+const SLASH_FORGIVENESS_TIME: f32 = 0.025; // bricks that scroll into a slash hitbox within this time after the slash are still destroyed
 
 #[derive(Clone, Copy)]
 struct UpcomingBrick {
@@ -60,7 +89,7 @@ struct UpcomingBrick {
 		// notes off the bottom of the screen have appearance_y's corresponding to how much has to be scrolled before they show up
 	appearance_y: f32, // y value (including amount needed to scroll) of the brick
 	end_y: f32, // y value (including amount needed to scroll) of the bottom of the brick and its hold segments
-	hold_segments: u8
+	hold_segments: u32
 }
 
 #[derive(Clone)]
@@ -91,28 +120,36 @@ pub struct Game {
 	last_target_missed: bool, // true when the last target was missed, used to turn dash targets into regular targets post stun
 	scrolled_y: f32, // how much y has been scrolled (time 0 has a scrolled y of 0)
 	end_y: f32, // y value of the bottom of the screen plus a 2 second window of bricks scrolling (so bricks offscreen may be loaded early)
+	config: GameConfig, // gameplay tuning
 	game_data: GameData, 
 	// TODO only store bricks, not the notes
 	notes: BTreeSet<BrickData>, // all notes of the song before conversion into bricks
-	rendering_instructions_buf: Vec<PositionedGraphic>,
-	audio_instructions_flags: [bool; 128], // !!! size must be greater than the number of SoundEffects
+	history: EditHistory, // edits made to the notes, for undo and redo
+	selection: Selection, // bricks selected in the editor and bricks copied
+	scheduler: Scheduler, // inputs and releases waiting to be handled during the tick
+	hit_times: Vec<f32>, // the contact time of every brick and hold segment hit since the last seek
+	render_queue: RenderQueue, // the graphics of the frame, bucketed by layer before they're passed to javascript
+	audio_instructions_times: [Option<f32>; 128], // the time of the first contact of each sound effect in the tick. !!! size must be greater than the number of SoundEffects
 	audio_instructions_buf: Vec<u8>,
+	audio_delays_buf: Vec<f32>,
 	game_graphics: Vec<LingeringGraphic>, // graphic effects on the game (passed to graphics when javascript requests rendering data)
 }
 
 #[wasm_bindgen]
 impl Game {
-	pub fn new(bpm: f32, brick_speed: f32, duration: f32) -> Game {
+	pub fn new(bpm: f32, brick_speed: f32, duration: f32, config: &GameConfig) -> Game {
 		console_error_panic_hook::set_once();
 		
+		let config = *config;
 		return Game {
-			player: Player::new((BRICK_WIDTH * 2) as f32 - objects::PLAYER_WIDTH as f32 / 2.0, EARLY_ARRIVAL_DISTANCE / brick_speed),
+			player: Player::new(Game::player_start_x(&Playfield::default()), config.early_arrival_distance / brick_speed, config, Playfield::default()),
 			bricks: VecDeque::new(), // all bricks of the song, ordered by time they are meant to be played
 			targets: VecDeque::new(),
 			target_idx: 0,
 			last_target_missed: false,
 			scrolled_y: 0.0,
 			end_y: Game::end_y(0.0, brick_speed),
+			config,
 			game_data: GameData {
 				bpm,
 				beat_interval: 60.0 / bpm as f32,
@@ -121,153 +158,71 @@ impl Game {
 				score: 0,
 				max_score: 0,
 				duration,
+				num_brick_types: DEFAULT_BRICK_TYPES,
+				lanes: DEFAULT_LANES,
 				is_modified: false
 			},
 			notes: BTreeSet::new(),
-			rendering_instructions_buf: Vec::with_capacity(512), // TODO what should the upper limit be? Make it a hard limit
-			audio_instructions_flags: [false; 128],
+			history: EditHistory::new(),
+			selection: Selection::new(),
+			scheduler: Scheduler::new(),
+			hit_times: Vec::new(),
+			render_queue: RenderQueue::with_capacity(512), // TODO what should the upper limit be? Make it a hard limit
+			audio_instructions_times: [None; 128],
 			audio_instructions_buf: Vec::with_capacity(32),
+			audio_delays_buf: Vec::with_capacity(32),
 			game_graphics: Vec::with_capacity(12)
 		};
 	}
 			
 	// tick the game state by the given amount of time
-	pub fn tick(&mut self, mut seconds_passed: f32) {
-		self.audio_instructions_flags = [false; 128];
+		// inputs, releases, actions, holds, and bricks crossing the top of the screen are handled in order of time, advancing the game between them
+	pub fn tick(&mut self, seconds_passed: f32) {
+		self.audio_instructions_times = [None; 128];
 		
-		// prevent disproportionally long ticks
-		if seconds_passed > MAX_TIME_BETWEEN_TICKS { 
-			self.tick(seconds_passed - MAX_TIME_BETWEEN_TICKS);
-			seconds_passed = MAX_TIME_BETWEEN_TICKS;
-		}
-		
-		// check for any actions that happened mid tick. Either action_tick (if the player is slashing) or regular tick
 		let end_tick_time = self.game_data.time_running + seconds_passed;
-		if let Some(action_time) = self.player.check_action(self.game_data.time_running, end_tick_time) {
-			let pre_action_time = action_time - self.game_data.time_running;
-			let post_action_time = seconds_passed - pre_action_time;
-			
-			// destroy any holds that may have been hit in the short span from the beginning of the tick until the action
-			self.destroy_holds(seconds_passed);
-			
-			// scroll screen to the time of action
-			let delta_y = pre_action_time * self.game_data.brick_speed;
-			self.scrolled_y += delta_y;
-			self.end_y += delta_y;
-			self.game_data.time_running += pre_action_time;
-			
-			// action tick the player, check for brick destruction
-			let hitbox = self.player.action_tick(&self.game_data);
-			self.destroy_bricks(&hitbox);
-			
-			// scroll screen the rest of the tick time
-			let delta_y = post_action_time * self.game_data.brick_speed;
-			self.scrolled_y += delta_y;
-			self.end_y += delta_y;
-			self.game_data.time_running += post_action_time;
-			
-			// second, forgiveness check for brick destruction (if action occurs in the same tick that notes become hittable, always destroy the bricks)
-			self.destroy_bricks(&hitbox);
-		} 
-		else {
-			// destroy any holds that may have been hit during the tick
-			self.destroy_holds(seconds_passed);
-			
-			// tick the player
-			self.player.tick(seconds_passed, &self.game_data);
-			
-			// scroll screen
-			let delta_y = seconds_passed * self.game_data.brick_speed;
-			self.scrolled_y += delta_y;
-			self.end_y += delta_y;
-			self.game_data.time_running += seconds_passed;
-		}
-			
-		// update target if all its bricks are destroyed
-		let mut all_destroyed = true;
-		if let Some(ti) = self.targets.get(self.target_idx) {
-			for brick in &ti.brick_group {
-				if !brick.is_broken() {
-					all_destroyed = false;
-				}
-			}
-		}
-		if all_destroyed && self.target_idx < self.targets.len() {
-			self.target_idx += 1;
-			self.last_target_missed = false;
-		}
-		
-		// stun player if a brick has hit the top of the screen
-		if let Some(ti) = self.targets.get(self.target_idx) {
-			if ti.appearance_y < self.scrolled_y {
-				let top_y = ti.appearance_y - self.scrolled_y;
-				let mut illegal_bricks = false;
-				
-				for brick in &ti.brick_group {
-					if let Some(brick_bounds) = brick.bounds(top_y) {
-						if brick_bounds.top_y < 0.0 {
-							illegal_bricks = true;
-							break;
-						}
-					}
-				}
-				
-				if illegal_bricks {
-					// set new target beyond all targets that are in the game section (which are forgotten)
-					loop {
-						if self.target_idx >= self.targets.len() {
-							break;
-						} else if self.targets[self.target_idx].appearance_y > self.end_y {
-							break;
-						} else {
-							self.target_idx += 1;
-						}
-					}
-					
-					// visual effect
-					let graphic = Graphic{ g: GraphicGroup::MissEffect, frame: 0, flags: 0, arg: 0 };
-					let positioned_graphic = PositionedGraphic::new(graphic, 0.0, 0.0);
-					self.game_graphics.push(LingeringGraphic {
-						positioned_graphic,
-						start_t: self.game_data.time_running,
-						end_t: self.game_data.time_running + MISS_EFFECT_TIME
-					});
-					
-					self.player.stun(self.game_data.time_running);
-					self.last_target_missed = true;
-				}
+		loop {
+			let mut game_events = Vec::with_capacity(3);
+			if let Some(event) = self.player.next_event(self.game_data.time_running) {
+				game_events.push(event);
 			}
-		}
-		
-		// update player's target
-		let mut target = None; 
-		if let Some(ti) = self.targets.get(self.target_idx) {
-			if ti.appearance_y > self.end_y {
-				target = None;
+			if let Some(time) = self.crossing_time() {
+				game_events.push(Event { time, kind: EventKind::BrickCrossing });
 			}
-			else {
-				if self.last_target_missed {
-					let mut ti = ti.clone();
-					ti.dash_to_target = false;
-					target = Some(ti.clone());
+			if let Some(ti) = self.targets.get(self.target_idx) {
+				let time = self.appearance_time(ti);
+				if time > self.game_data.time_running {
+					game_events.push(Event { time, kind: EventKind::TargetAppears });
 				}
-				else {
-					target = Some(ti.clone());
+			}
+			
+			match self.scheduler.next_event(&game_events, end_tick_time) {
+				None => {
+					self.advance(end_tick_time);
+					break;
+				},
+				Some(event) => {
+					self.advance(event.time);
+					self.handle_event(event);
+					self.update_targets();
 				}
 			}
 		}
-		self.player.update_target(target);
 	}
 	
 	// updates the audio instructions and returns a pointer to access them
 	pub fn audio_instructions(&mut self) -> AudioInstructions {
 		let audio_instructions_buf = &mut self.audio_instructions_buf;
+		let audio_delays_buf = &mut self.audio_delays_buf;
 		audio_instructions_buf.clear();
+		audio_delays_buf.clear();
 		
 		let mut audioKey = 0;
-		for val in self.audio_instructions_flags {
-			if val {
+		for val in self.audio_instructions_times {
+			if let Some(time) = val {
 				audio_instructions_buf.push(audioKey);
+				// contacts earlier in the tick have already passed, contacts that are already known to happen later are delayed
+				audio_delays_buf.push((time - self.game_data.time_running).max(0.0));
 			}
 			
 			audioKey += 1;
@@ -275,19 +230,44 @@ impl Game {
 		
 		return AudioInstructions {
 			num_instructions: audio_instructions_buf.len(),
-			instructions_ptr: audio_instructions_buf.as_ptr()
+			instructions_ptr: audio_instructions_buf.as_ptr(),
+			delays_ptr: audio_delays_buf.as_ptr()
 		};
 	}
 	
-	// updates the rendering instructions and returns a pointer to access them
-	pub fn rendering_instructions(&mut self) -> RenderingInstructions {
-		let rendering_instructions_buf = &mut self.rendering_instructions_buf;
+	// updates the rendering instructions for the screen of the viewport and returns a pointer to access them
+	pub fn rendering_instructions(&mut self, viewport: &Viewport) -> RenderingInstructions {
+		self.push_game_graphics();
+		return self.screen_instructions(viewport);
+	}
+	
+	// the rendering instructions of the game, along with the editor's grid, the player's planned path to each target if show_path, 
+		// highlights over the selected bricks, and a playhead at the current time, each in its own layer.
+		// grid is the number of beat positions between grid lines
+	pub fn editor_rendering_instructions(&mut self, viewport: &Viewport, grid: i32, show_path: bool) -> RenderingInstructions {
+		self.push_game_graphics();
+		
+		self.render_queue.append(&mut editor_render::grid_graphics(
+			&self.playfield(), self.scrolled_y, self.game_data.beat_interval, self.game_data.brick_speed, grid));
+		if show_path {
+			self.render_queue.append(&mut path_preview::path_graphics(&self.targets, self.target_idx, self.scrolled_y, &self.config));
+		}
+		self.render_queue.append(&mut editor_render::selection_graphics(
+			self.selection.selected(), &self.playfield(), self.scrolled_y, self.game_data.bpm, self.game_data.brick_speed));
+		self.render_queue.push(editor_render::playhead_graphic());
+		
+		return self.screen_instructions(viewport);
+	}
+	
+	// fills the render queue with the game's graphics, positioned in the world
+	fn push_game_graphics(&mut self) {
+		let render_queue = &mut self.render_queue;
 		let game_graphics = &mut self.game_graphics;
 		
-		rendering_instructions_buf.clear();
+		render_queue.clear();
 		
 		// push background
-		rendering_instructions_buf.push(
+		render_queue.push(
 			PositionedGraphic::new(Graphic{ g: GraphicGroup::Background, frame: 0, flags: 0, arg: 0}, 0.0, 0.0)
 		);
 		
@@ -300,7 +280,7 @@ impl Game {
 				
 				pg.g.flags |= GraphicFlags::Opacity as u8;
 				pg.g.arg = 255 - (proportion_time_passed * 255.0) as u8;
-				rendering_instructions_buf.push(pg);
+				render_queue.push(pg);
 				return true;
 			} else {
 				return false;
@@ -324,7 +304,7 @@ impl Game {
 					}
 				}
 				
-				rendering_instructions_buf.push( pg );
+				render_queue.push( pg );
 			}
 		}
 		// push dash indicators for targets after the current target
@@ -346,7 +326,7 @@ impl Game {
 						}
 					}
 					
-					rendering_instructions_buf.push( pg );
+					render_queue.push( pg );
 				}
 				
 				if ti.appearance_y > self.end_y {
@@ -360,8 +340,8 @@ impl Game {
 		}
 		
 		// push player graphics
-		rendering_instructions_buf.append(&mut self.player.rendering_instructions(self.game_data.time_running));
-		rendering_instructions_buf.append(&mut self.player.lg_rendering_instructions(self.game_data.time_running));
+		render_queue.append(&mut self.player.rendering_instructions(self.game_data.time_running));
+		render_queue.append(&mut self.player.lg_rendering_instructions(self.game_data.time_running));
 		
 		// push bricks' graphics
 		let mut idx = self.target_idx;
@@ -379,16 +359,21 @@ impl Game {
 			let target_y = ti.appearance_y - self.scrolled_y;
 			for brick in &ti.brick_group {
 				let mut brick_graphics = brick.rendering_instructions(target_y);
-				rendering_instructions_buf.append(&mut brick_graphics);
+				render_queue.append(&mut brick_graphics);
 			}
 			
 			idx += 1;
 		}
-		
-		return RenderingInstructions {
-			num_graphics: rendering_instructions_buf.len(),
-			graphics_ptr: rendering_instructions_buf.as_ptr()
+	}
+	
+	// orders the render queue by layer and moves its graphics from the world onto the viewport's screen
+	fn screen_instructions(&mut self, viewport: &Viewport) -> RenderingInstructions {
+		self.render_queue.finish();
+		for graphic in self.render_queue.graphics_mut() {
+			viewport.apply(graphic);
 		}
+		
+		return self.render_queue.instructions();
 	}
 	
 	// returns the songs game data
@@ -396,6 +381,20 @@ impl Game {
 		return self.game_data;
 	}
 	
+	// returns the gameplay tuning the game was created with
+	pub fn config(&self) -> GameConfig {
+		return self.config;
+	}
+	
+	// the contact times of the bricks and hold segments hit since the last seek, in the order they were hit
+	pub fn hit_times(&self) -> Vec<f32> {
+		return self.hit_times.clone();
+	}
+	
+	pub fn player_x(&self) -> f32 {
+		return self.player.bounds().left_x;
+	}
+	
 	// returns all bricks of the song
 	pub fn bricks(&self) -> Array {
 		let array = Array::new_with_length(self.notes.len() as u32);
@@ -408,18 +407,117 @@ impl Game {
 		return array;
 	}
 	
-	// takes an input command and passes it forward to be handled
+	// takes an input command at a time relative to the last tick and queues it to be handled at its time
 	pub fn input_command(&mut self, input: Input, time_since_tick: f32) {
-		let input_time = self.game_data.time_running + time_since_tick;
-		
-		self.player.input(input, input_time);
+		self.queue_input(input, self.game_data.time_running + time_since_tick);
 	}
 	
-	// takes key release command and passes it forward to be handled
+	// takes key release command at a time relative to the last tick and queues it to be handled at its time
 	pub fn stop_command(&mut self, input: Input, time_since_tick: f32) {
-		let stop_input_time = self.game_data.time_running + time_since_tick;
+		self.queue_release(input, self.game_data.time_running + time_since_tick);
+	}
+	
+	// queues an input at its time in the song (e.g. from the audio clock) to be handled when a tick reaches that time.
+		// inputs from before the last tick are handled at the start of the next tick, unless they are more than MAX_INPUT_LATENESS old.
+		// returns false if the input was rejected
+	pub fn queue_input(&mut self, input: Input, time: f32) -> bool {
+		let time_running = self.game_data.time_running;
+		if time < time_running - MAX_INPUT_LATENESS {
+			return false;
+		}
 		
-		self.player.end_input(input, stop_input_time);
+		self.scheduler.push_input(Event { time: time.max(time_running), kind: EventKind::Input(input) });
+		return true;
+	}
+	
+	// queues a release at its time in the song. Releases are never rejected so keys can't get stuck, 
+		// releases from before the last tick are handled at the start of the next tick
+	pub fn queue_release(&mut self, input: Input, time: f32) {
+		let time_running = self.game_data.time_running;
+		self.scheduler.push_input(Event { time: time.max(time_running), kind: EventKind::Release(input) });
+	}
+	
+	// returns an array of the inputs and releases waiting to be handled, in order of time, for debugging
+	pub fn queued_inputs(&self) -> Array {
+		let queued = self.queued();
+		let array = Array::new_with_length(queued.len() as u32);
+		for (i, queued_input) in queued.into_iter().enumerate() {
+			array.set(i as u32, JsValue::from(queued_input));
+		}
+		return array;
+	}
+	
+	// sets how many brick types (colors) the song uses, as an undoable edit. 
+		// Fails if it's outside 1 to MAX_BRICK_TYPES or a brick of the song has a type beyond it
+	pub fn set_num_brick_types(&mut self, num_brick_types: u8) -> Result<(), String> {
+		self.check_num_brick_types(num_brick_types)?;
+		
+		if num_brick_types != self.game_data.num_brick_types {
+			self.history.record(EditCommand::SetBrickTypes(self.game_data.num_brick_types, num_brick_types));
+			self.game_data.num_brick_types = num_brick_types;
+			self.game_data.is_modified = self.history.is_modified();
+		}
+		return Ok(());
+	}
+	
+	// sets how many lanes the song's bricks are spread over, which sets the width of bricks and slashes, as an undoable edit. 
+		// Fails if it's outside MIN_LANES to MAX_LANES or a brick of the song is beyond the last lane
+	pub fn set_lanes(&mut self, lanes: u8) -> Result<(), String> {
+		self.check_lanes(lanes)?;
+		
+		if lanes != self.game_data.lanes {
+			self.history.record(EditCommand::SetLanes(self.game_data.lanes, lanes));
+			self.game_data.lanes = lanes;
+			self.game_data.is_modified = self.history.is_modified();
+			self.seek(self.game_data.time_running);
+		}
+		return Ok(());
+	}
+	
+	// sets the brick types and lanes of a song being loaded without recording an edit or setting the is_modified flag to true
+		// fails like set_num_brick_types and set_lanes
+	pub fn initial_load_song_settings(&mut self, num_brick_types: u8, lanes: u8) -> Result<(), String> {
+		self.check_num_brick_types(num_brick_types)?;
+		self.check_lanes(lanes)?;
+		
+		self.game_data.num_brick_types = num_brick_types;
+		self.game_data.lanes = lanes;
+		self.seek(self.game_data.time_running);
+		return Ok(());
+	}
+	
+	fn check_num_brick_types(&self, num_brick_types: u8) -> Result<(), String> {
+		if num_brick_types < 1 || num_brick_types as usize > MAX_BRICK_TYPES {
+			return Err(format!("A song must use 1 to {} brick types, not {}", MAX_BRICK_TYPES, num_brick_types));
+		}
+		for brick_data in &self.notes {
+			if brick_data.brick_type as u8 >= num_brick_types {
+				return Err(format!("The brick at beat pos {} in lane {} has brick type {}, beyond the {} brick types", 
+					brick_data.beat_pos, brick_data.x_pos, brick_data.brick_type as u8 + 1, num_brick_types));
+			}
+		}
+		return Ok(());
+	}
+	
+	fn check_lanes(&self, lanes: u8) -> Result<(), String> {
+		if !(MIN_LANES..=MAX_LANES).contains(&lanes) {
+			return Err(format!("A song must have {} to {} lanes, not {}", MIN_LANES, MAX_LANES, lanes));
+		}
+		for brick_data in &self.notes {
+			if brick_data.x_pos >= lanes as i32 {
+				return Err(format!("The brick at beat pos {} is in lane {}, beyond the {} lanes", 
+					brick_data.beat_pos, brick_data.x_pos, lanes));
+			}
+		}
+		return Ok(());
+	}
+	
+	// the size of the song's bricks, as wide as its lanes
+	pub fn brick_dimensions(&self) -> Position {
+		return Position {
+			x: self.playfield().lane_width(),
+			y: BRICK_HEIGHT as f32
+		};
 	}
 	
 	// adds a brick to the song without setting the is_modified flag to true or calling seek()
@@ -427,13 +525,65 @@ impl Game {
 		self.notes.insert( brick_data );
 	}
 	
-	// TODO return true/false on success/failure add_brick and remove_brick
-	// adds a brick according to the brick's brick data
-	pub fn add_brick(&mut self, brick_data: BrickData) {
-		self.notes.insert( brick_data );
-		self.game_data.is_modified = true;
+	// adds the notes of a standard MIDI file to the song according to the import options. returns the number of bricks added and left out.
+		// bricks get the song's brick types and lanes, except for exported charts, which raise the song's number of brick types 
+		// and lanes to fit their bricks. Bricks the editor couldn't add (such as ones overlapping a hold note, or exported chart bricks
+		// beyond MAX_LANES) are left out. The import is undone as one edit
+	pub fn load_midi(&mut self, bytes: &[u8], options: &MidiImportOptions) -> Result<ImportCount, String> {
+		let midi = midi::read_midi(bytes)?;
+		let bricks = midi::midi_to_bricks(&midi, options, self.game_data.bpm, self.game_data.num_brick_types, self.game_data.lanes);
+		let mut num_brick_types = self.game_data.num_brick_types;
+		let mut lanes = self.game_data.lanes;
+		for brick_data in &bricks {
+			num_brick_types = num_brick_types.max(brick_data.brick_type as u8 + 1);
+			if (0..MAX_LANES as i32).contains(&brick_data.x_pos) {
+				lanes = lanes.max(brick_data.x_pos as u8 + 1);
+			}
+		}
+
+		let mut count = ImportCount { added: 0, rejected: 0 };
+		self.history.begin_group();
+		// only raised, so they fit the song's bricks
+		self.set_num_brick_types(num_brick_types)?;
+		self.set_lanes(lanes)?;
+		for brick_data in bricks {
+			match edit_result::check_add(&self.notes, &brick_data, self.game_data.num_brick_types, self.game_data.lanes) {
+				Ok(None) => {
+					self.notes.insert( brick_data.clone() );
+					self.history.record(EditCommand::Add(brick_data));
+					count.added += 1;
+				},
+				_ => { count.rejected += 1; }
+			}
+		}
+		self.history.end_group();
+		self.game_data.is_modified = self.history.is_modified();
+
+		self.seek(self.game_data.time_running);
+		return Ok(count);
+	}
+
+	// returns the song's bricks written as a standard MIDI file. Import with MidiImportOptions::chart() to get the same bricks back
+	pub fn export_midi(&self) -> Result<Vec<u8>, String> {
+		return midi::bricks_to_midi(self.notes.iter(), self.game_data.bpm);
+	}
+
+	// rates how hard the song is to play from its targets
+	pub fn difficulty_rating(&self) -> DifficultyRating {
+		return difficulty::rate_targets(&self.targets, self.game_data.duration, &self.config);
+	}
+
+	// adds a brick according to the brick's brick data, replacing any brick at the same position
+	pub fn add_brick(&mut self, brick_data: BrickData) -> Result<EditOutcome, EditError> {
+		let replaced = edit_result::check_add(&self.notes, &brick_data, self.game_data.num_brick_types, self.game_data.lanes)?;
+		
+		self.history.begin_group();
+		let outcome = self.place_brick(brick_data, replaced);
+		self.history.end_group();
+		self.game_data.is_modified = self.history.is_modified();
 		
 		self.seek(self.game_data.time_running);
+		return Ok(outcome);
 	}
 	// select the brick which overlaps with the given brick pos and x pos
 	pub fn select_brick(&self, beat_pos: i32, x_pos: i32) -> Option<BrickData> {
@@ -452,10 +602,274 @@ impl Game {
 		return None;
 	}
 	
+	// what is under the point x, y on the screen (in game coordinates): a brick, one of a hold note's segments, or nothing,
+		// along with the beat pos and lane the point snaps to
+	pub fn hit_test(&self, x: f32, y: f32) -> HitTest {
+		return hit_test::hit_test(&self.notes, &self.playfield(), self.game_data.bpm, self.game_data.brick_speed, x, y + self.scrolled_y);
+	}
+	
 	// removes the brick equal to brick_data
-	pub fn remove_brick(&mut self, brick_data: BrickData) {
-		self.notes.remove( &brick_data ); // TODO alert/log when a value was already there and the brick wasn't updated
-		self.game_data.is_modified = true;
+	pub fn remove_brick(&mut self, brick_data: BrickData) -> Result<EditOutcome, EditError> {
+		let removed = match self.notes.take( &brick_data ) {
+			Some(removed) => removed,
+			None => {
+				return Err(EditError::new(EditErrorCode::NotFound, 
+					format!("There is no brick at beat pos {} in lane {}", brick_data.beat_pos, brick_data.x_pos)));
+			}
+		};
+		self.selection.forget(&removed);
+		self.history.record(EditCommand::Remove(removed));
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return Ok(EditOutcome::Removed);
+	}
+	
+	// replaces the brick at the from position with the to brick (e.g. moving it or changing its type) as one edit
+		// if the to brick can't be added, the from brick is left in place
+	pub fn move_brick(&mut self, from: BrickData, to: BrickData) -> Result<EditOutcome, EditError> {
+		let removed = match self.notes.take( &from ) {
+			Some(removed) => removed,
+			None => {
+				return Err(EditError::new(EditErrorCode::NotFound, 
+					format!("There is no brick at beat pos {} in lane {}", from.beat_pos, from.x_pos)));
+			}
+		};
+		
+		let replaced = match edit_result::check_add(&self.notes, &to, self.game_data.num_brick_types, self.game_data.lanes) {
+			Ok(replaced) => replaced,
+			Err(err) => {
+				self.notes.insert(removed);
+				return Err(err);
+			}
+		};
+		
+		self.history.begin_group();
+		self.selection.forget(&removed);
+		self.history.record(EditCommand::Remove(removed));
+		let outcome = self.place_brick(to, replaced);
+		self.history.end_group();
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return Ok(outcome);
+	}
+	
+	// moves the end of the brick to the end beat pos, as with dragging a hold note's end. The brick becomes a tap if the end is at or before its start
+	pub fn resize_hold(&mut self, brick_data: BrickData, end_beat_pos: i32) -> Result<EditOutcome, EditError> {
+		let resized = transform::with_end(&brick_data, end_beat_pos);
+		return self.move_brick(brick_data, resized);
+	}
+	
+	// the number of hold segments the brick would have if its end were moved to the end beat pos
+	pub fn hold_segment_preview(&self, brick_data: &BrickData, end_beat_pos: i32) -> u32 {
+		let resized = transform::with_end(brick_data, end_beat_pos);
+		return brick::hold_segment_count(&resized, self.game_data.bpm, self.game_data.brick_speed);
+	}
+	
+	// inserts a brick that passed check_add, removing the brick it replaces
+	fn place_brick(&mut self, brick_data: BrickData, replaced: Option<BrickData>) -> EditOutcome {
+		let outcome;
+		if let Some(replaced) = replaced {
+			self.notes.remove(&replaced);
+			self.selection.forget(&replaced);
+			self.history.record(EditCommand::Remove(replaced));
+			outcome = EditOutcome::Replaced;
+		} else {
+			outcome = EditOutcome::Inserted;
+		}
+		self.notes.insert( brick_data.clone() );
+		self.history.record(EditCommand::Add(brick_data));
+		return outcome;
+	}
+	
+	// edits until the matching end_edit_group are undone and redone as one step (e.g. the removal and re-adding of a moved brick)
+	pub fn begin_edit_group(&mut self) {
+		self.history.begin_group();
+	}
+	
+	pub fn end_edit_group(&mut self) {
+		self.history.end_group();
+		self.game_data.is_modified = self.history.is_modified();
+	}
+	
+	// reverts the last edit. returns false if there was nothing to undo
+	pub fn undo(&mut self) -> bool {
+		let undone = self.history.undo(&mut self.notes, &mut self.game_data);
+		self.selection.clear();
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return undone;
+	}
+	
+	// reapplies the last undone edit. returns false if there was nothing to redo
+	pub fn redo(&mut self) -> bool {
+		let redone = self.history.redo(&mut self.notes, &mut self.game_data);
+		self.selection.clear();
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return redone;
+	}
+	
+	pub fn can_undo(&self) -> bool {
+		return self.history.can_undo();
+	}
+	
+	pub fn can_redo(&self) -> bool {
+		return self.history.can_redo();
+	}
+	
+	// selects the bricks starting within the beat range in the lane range (both inclusive), replacing the selection
+		// returns the number of bricks selected
+	pub fn select_bricks(&mut self, start_beat_pos: i32, end_beat_pos: i32, left_x_pos: i32, right_x_pos: i32) -> usize {
+		return self.selection.select_range(&self.notes, start_beat_pos, end_beat_pos, left_x_pos, right_x_pos);
+	}
+	
+	pub fn clear_selection(&mut self) {
+		self.selection.clear();
+	}
+	
+	// returns an array of the selected bricks' brick data
+	pub fn selected_bricks(&self) -> Array {
+		let selected = self.selection.selected();
+		let array = Array::new_with_length(selected.len() as u32);
+		for (i, brick_data) in selected.iter().enumerate() {
+			array.set(i as u32, JsValue::from(brick_data.clone()));
+		}
+		return array;
+	}
+	
+	// copies the selected bricks to be pasted. returns the number of bricks copied
+	pub fn copy_selection(&mut self) -> usize {
+		return self.selection.copy();
+	}
+	
+	// copies then deletes the selected bricks. returns the number of bricks cut
+	pub fn cut_selection(&mut self) -> usize {
+		self.selection.copy();
+		return self.delete_selection();
+	}
+	
+	// removes the selected bricks. returns the number of bricks removed
+	pub fn delete_selection(&mut self) -> usize {
+		let num_removed = self.selection.delete(&mut self.notes, &mut self.history);
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return num_removed;
+	}
+	
+	// adds the copied bricks, with the first copied brick at the given beat pos. The pasted bricks become the selection
+		// nothing is pasted if any brick would collide with an existing brick, and the collisions are returned
+	pub fn paste(&mut self, beat_pos: i32) -> SelectionEdit {
+		let edit = self.selection.paste(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, beat_pos);
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return edit;
+	}
+	
+	// moves the selected bricks by the beat pos and lane offsets
+		// nothing is moved if any brick would collide with an unselected brick, and the collisions are returned
+	pub fn move_selection(&mut self, beat_offset: i32, lane_offset: i32) -> SelectionEdit {
+		let edit = self.selection.move_by(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, beat_offset, lane_offset);
+		self.game_data.is_modified = self.history.is_modified();
+		
+		self.seek(self.game_data.time_running);
+		return edit;
+	}
+	
+	// sets the type of all bricks starting within the beat range (inclusive). The transforms below all act on the same bricks, 
+		// and change nothing if a transformed brick would collide with another brick or leave the lanes
+	pub fn set_brick_types(&mut self, start_beat_pos: i32, end_beat_pos: i32, brick_type: BrickType) -> SelectionEdit {
+		if brick_type as u8 >= self.game_data.num_brick_types {
+			return SelectionEdit::out_of_range();
+		}
+		
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::with_type(brick_data, brick_type));
+		self.finish_transform();
+		return edit;
+	}
+	
+	pub fn cycle_brick_types(&mut self, start_beat_pos: i32, end_beat_pos: i32) -> SelectionEdit {
+		let num_brick_types = self.game_data.num_brick_types;
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::cycled_type(brick_data, num_brick_types));
+		self.finish_transform();
+		return edit;
+	}
+	
+	pub fn mirror_lanes(&mut self, start_beat_pos: i32, end_beat_pos: i32) -> SelectionEdit {
+		let lanes = self.game_data.lanes;
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::mirrored(brick_data, lanes));
+		self.finish_transform();
+		return edit;
+	}
+	
+	pub fn shift_lanes(&mut self, start_beat_pos: i32, end_beat_pos: i32, lane_offset: i32) -> SelectionEdit {
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::lane_shifted(brick_data, lane_offset));
+		self.finish_transform();
+		return edit;
+	}
+	
+	// scales the timing of the bricks from the start of the range by the ratio (2 doubles the time between bricks, 0.5 halves it)
+	pub fn stretch_timing(&mut self, start_beat_pos: i32, end_beat_pos: i32, ratio: f32) -> Result<SelectionEdit, String> {
+		if ratio.is_nan() || ratio <= 0.0 {
+			return Err(format!("The stretch ratio must be positive, got {}", ratio));
+		}
+		
+		let anchor_beat_pos = start_beat_pos.min(end_beat_pos);
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::stretched(brick_data, anchor_beat_pos, ratio));
+		self.finish_transform();
+		return Ok(edit);
+	}
+	
+	// makes taps into holds lasting the given number of beat positions
+	pub fn taps_to_holds(&mut self, start_beat_pos: i32, end_beat_pos: i32, length: i32) -> Result<SelectionEdit, String> {
+		if length < 1 {
+			return Err(format!("The hold length must be at least 1 beat position, got {}", length));
+		}
+		
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::to_hold(brick_data, length));
+		self.finish_transform();
+		return Ok(edit);
+	}
+	
+	pub fn holds_to_taps(&mut self, start_beat_pos: i32, end_beat_pos: i32) -> SelectionEdit {
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, transform::to_tap);
+		self.finish_transform();
+		return edit;
+	}
+	
+	// snaps bricks to a grid of the given number of beat positions (1 for 16th notes, 2 for 8th notes, 4 for beats...)
+	pub fn requantize(&mut self, start_beat_pos: i32, end_beat_pos: i32, grid: i32) -> Result<SelectionEdit, String> {
+		if grid < 1 {
+			return Err(format!("The grid must be at least 1 beat position, got {}", grid));
+		}
+		
+		let edit = transform::transform_range(&mut self.notes, &mut self.history, self.game_data.num_brick_types, self.game_data.lanes, start_beat_pos, end_beat_pos, 
+			|brick_data| transform::requantized(brick_data, grid));
+		self.finish_transform();
+		return Ok(edit);
+	}
+	
+	// marks the current notes as saved, so is_modified is false until they're edited (or until edits are undone or redone)
+	pub fn mark_saved(&mut self) {
+		self.history.mark_saved();
+		self.game_data.is_modified = self.history.is_modified();
+	}
+	
+	// updates the game after a transform, rebuilding bricks and targets once for all transformed bricks
+	fn finish_transform(&mut self) {
+		self.selection.clear();
+		self.game_data.is_modified = self.history.is_modified();
 		
 		self.seek(self.game_data.time_running);
 	}
@@ -463,28 +877,17 @@ impl Game {
 	fn prepare_song(&mut self) {
 		self.bricks = VecDeque::new();
 		self.targets = VecDeque::new();
+		let playfield = self.playfield();
 
 		// populate self.bricks
 		for brick_data in &self.notes {
 			let appearance_y = brick_data.appearance_y(self.game_data.bpm, self.game_data.brick_speed);
-			let end_appearance_y = brick_data.end_appearance_y(self.game_data.bpm, self.game_data.brick_speed);
-			let hold_segments;
-			
-			if brick_data.is_hold_note {
-				// hold length from the bottom of the starting note 
-					// to the bottom of the ending note, if there was a full note (rather than just a hold segment) at the end beat pos
-				let hold_length_pixels = end_appearance_y - appearance_y;
-				// how many brick segments fit into the hold length, floored to a u8
-				hold_segments = (hold_length_pixels / (objects::BRICK_SEGMENT_HEIGHT + objects::BRICK_SEGMENT_GAP) as f32) as u8;
-			} 
-			else {
-				hold_segments = 0;
-			}
+			let hold_segments = brick::hold_segment_count(brick_data, self.game_data.bpm, self.game_data.brick_speed);
 			
 			let end_y = appearance_y + BRICK_HEIGHT as f32 + (BRICK_SEGMENT_HEIGHT + BRICK_SEGMENT_GAP) as f32 * hold_segments as f32;
 			self.bricks.push_back( UpcomingBrick {
 				brick_type: brick_data.brick_type, 
-				x: brick_data.x(),
+				x: brick_data.x(&playfield),
 				appearance_y,
 				end_y,
 				hold_segments
@@ -499,7 +902,7 @@ impl Game {
 		
 		for upcoming_brick in &self.bricks {
 			if upcoming_brick.appearance_y == group_appearance_y {
-				brick_group.push_back( HittableBrick::new(upcoming_brick.brick_type, upcoming_brick.x, upcoming_brick.hold_segments));
+				brick_group.push_back( HittableBrick::new(upcoming_brick.brick_type, upcoming_brick.x, playfield.lane_width(), upcoming_brick.hold_segments));
 			}
 			else {
 				if brick_group.len() > 0 {
@@ -512,7 +915,7 @@ impl Game {
 				}
 				
 				group_appearance_y = upcoming_brick.appearance_y;
-				brick_group.push_back( HittableBrick::new(upcoming_brick.brick_type, upcoming_brick.x, upcoming_brick.hold_segments));
+				brick_group.push_back( HittableBrick::new(upcoming_brick.brick_type, upcoming_brick.x, playfield.lane_width(), upcoming_brick.hold_segments));
 			}
 		}
 		if brick_group.len() > 0 {
@@ -531,7 +934,7 @@ impl Game {
 
 		let mut max_score = 0;
 		for brick in &self.bricks {
-			max_score += BRICK_SCORE;
+			max_score += self.config.brick_score;
 			max_score += brick.hold_segments as i32 * HOLD_SEGMENT_SCORE;
 		}
 		self.game_data.max_score = max_score;
@@ -544,14 +947,14 @@ impl Game {
 		
 		// get data from brick_group
 		let mut group_left_x = brick_group[0].x();
-		let mut group_right_x = brick_group[0].x() + BRICK_WIDTH as f32;
+		let mut group_right_x = brick_group[0].x() + brick_group[0].width();
 		let mut max_hold_segments = brick_group[0].hold_segments();
 		for brick in &brick_group {
 			if brick.x() < group_left_x {
 				group_left_x = brick.x();
 			}
-			if brick.x() + BRICK_WIDTH as f32 > group_right_x {
-				group_right_x = brick.x() + BRICK_WIDTH as f32;
+			if brick.x() + brick.width() > group_right_x {
+				group_right_x = brick.x() + brick.width();
 			}
 			if brick.hold_segments() > max_hold_segments {
 				max_hold_segments = brick.hold_segments();
@@ -595,9 +998,9 @@ impl Game {
 		hittable_time = group_appearance_y / self.game_data.brick_speed - ground_pos_time; // brick rises above ground
 		passed_time = group_end_y / self.game_data.brick_speed - ground_pos_time + player_height_time; // brick rises above player head
 		let time_until_target = hittable_time - player_start_time;
-		let max_run_distance = time_until_target * SPRINT_SPEED;
+		let max_run_distance = time_until_target * self.config.sprint_speed;
 
-		if distance_to_target > max_run_distance + MAX_BOOST_DISTANCE {
+		if distance_to_target > max_run_distance + self.config.max_boost_distance {
 			dash_to_target = true;
 		}
 		else if distance_to_target > max_run_distance {
@@ -631,10 +1034,153 @@ impl Game {
 		return target_info;
 	}
 
-	// destroy any bricks that overlap with passed hitboxes
-	fn destroy_bricks(&mut self, hitbox: &HitBox) {
+	// advances the game to the given time with no events in between, in steps of at most MAX_TIME_BETWEEN_TICKS
+	fn advance(&mut self, until: f32) {
+		while self.game_data.time_running < until {
+			let step_end = until.min(self.game_data.time_running + MAX_TIME_BETWEEN_TICKS);
+			let seconds_passed = step_end - self.game_data.time_running;
+			
+			// destroy any holds that may have been hit during the step
+			self.destroy_holds(seconds_passed);
+			
+			// tick the player
+			self.player.tick(seconds_passed, &self.game_data);
+			
+			// scroll screen, from the time rather than by adding each step's scroll so rounding doesn't depend on the steps taken
+			self.game_data.time_running = step_end;
+			self.scrolled_y = self.game_data.brick_speed * step_end;
+			self.end_y = Game::end_y(self.scrolled_y, self.game_data.brick_speed);
+			
+			// hold segments destroyed during the step may have finished the target
+			self.update_targets();
+		}
+	}
+	
+	fn handle_event(&mut self, event: Event) {
+		// inputs can't change what has already happened
+		let time = event.time.max(self.game_data.time_running);
+		match event.kind {
+			EventKind::Input(input) => {
+				self.scheduler.pop_input();
+				self.player.input(input, time);
+			},
+			EventKind::Release(input) => {
+				self.scheduler.pop_input();
+				self.player.end_input(input, time);
+			},
+			EventKind::Action => {
+				// slashes check for brick destruction, sweeping the bricks over the forgiveness time 
+					// (if action occurs just before notes become hittable, always destroy the bricks)
+				if let Some(hitbox) = self.player.action_tick(&self.game_data) {
+					self.destroy_bricks(&hitbox, SLASH_FORGIVENESS_TIME);
+					// the slash ends right away, going into a hold if hold notes were hit
+					self.player.tick(0.0, &self.game_data);
+				}
+			},
+			EventKind::HoldStart => {
+				self.player.start_hold();
+			},
+			EventKind::HoldEnd => {
+				// destroy any hold segments hit right at the release, then the player moves on
+				self.destroy_holds(0.0);
+				self.player.tick(0.0, &self.game_data);
+			},
+			EventKind::TargetAppears => {
+				// the target is given to the player after the event is handled
+			},
+			EventKind::BrickCrossing => {
+				self.miss_target();
+			}
+		}
+	}
+	
+	// the time the topmost unbroken part of the current target reaches the top of the screen
+	fn crossing_time(&self) -> Option<f32> {
+		let ti = self.targets.get(self.target_idx)?;
+		let target_y = ti.appearance_y - self.scrolled_y;
+		
+		let mut top_y: Option<f32> = None;
+		for brick in &ti.brick_group {
+			if let Some(brick_bounds) = brick.bounds(target_y) {
+				top_y = Some(top_y.map_or(brick_bounds.top_y, |y| y.min(brick_bounds.top_y)));
+			}
+		}
+		
+		return top_y.map(|y| self.game_data.time_running + y.max(0.0) / self.game_data.brick_speed);
+	}
+	
+	// the time at which the target comes within end_y
+	fn appearance_time(&self, ti: &TargetInfo) -> f32 {
+		return (ti.appearance_y - GAME_HEIGHT) / self.game_data.brick_speed - 2.0;
+	}
+	
+	// a brick has hit the top of the screen, stun the player
+	fn miss_target(&mut self) {
+		// set new target beyond all targets that are in the game section (which are forgotten)
+		loop {
+			if self.target_idx >= self.targets.len() {
+				break;
+			} else if self.targets[self.target_idx].appearance_y > self.end_y {
+				break;
+			} else {
+				self.target_idx += 1;
+			}
+		}
+		
+		// visual effect
+		let graphic = Graphic{ g: GraphicGroup::MissEffect, frame: 0, flags: 0, arg: 0 };
+		let positioned_graphic = PositionedGraphic::new(graphic, 0.0, 0.0);
+		self.game_graphics.push(LingeringGraphic {
+			positioned_graphic,
+			start_t: self.game_data.time_running,
+			end_t: self.game_data.time_running + MISS_EFFECT_TIME
+		});
+		
+		self.player.stun(self.game_data.time_running);
+		self.last_target_missed = true;
+	}
+	
+	// moves on from the current target once all its bricks are destroyed, and updates the player's target
+	fn update_targets(&mut self) {
+		let mut all_destroyed = true;
+		if let Some(ti) = self.targets.get(self.target_idx) {
+			for brick in &ti.brick_group {
+				if !brick.is_broken() {
+					all_destroyed = false;
+				}
+			}
+		}
+		if all_destroyed && self.target_idx < self.targets.len() {
+			self.target_idx += 1;
+			self.last_target_missed = false;
+		}
+		
+		let mut target = None; 
+		if let Some(ti) = self.targets.get(self.target_idx) {
+			// from the time rather than end_y, so the target appears at exactly the time of its event
+			if self.appearance_time(ti) > self.game_data.time_running {
+				target = None;
+			}
+			else {
+				if self.last_target_missed {
+					let mut ti = ti.clone();
+					ti.dash_to_target = false;
+					target = Some(ti.clone());
+				}
+				else {
+					target = Some(ti.clone());
+				}
+			}
+		}
+		self.player.update_target(target);
+	}
+	
+	// destroy any bricks that overlap with the passed hitbox, or touch it as they scroll up over the duration
+	fn destroy_bricks(&mut self, hitbox: &HitBox, duration: f32) {
 		let score = &mut self.game_data.score;
 		let mut new_hold_positions = Vec::new();
+		let time_running = self.game_data.time_running;
+		let brick_velocity = -self.game_data.brick_speed;
 		
 		if let Some(ti) = self.targets.get_mut(self.target_idx) {
 			let target_y = ti.appearance_y - self.scrolled_y;
@@ -643,37 +1189,39 @@ impl Game {
 					continue;
 				}
 				let brick_type = brick.brick_type();
-				let brick_bounds = match brick.bounds(target_y) {
+				let brick_collider = match brick.collider(target_y) {
 					None => { continue; },
-					Some(b) => { b }
+					Some(c) => { c }
 				};
 				
-				if hitbox.brick_type == brick_type && objects::intersect(&hitbox.bounds, &brick_bounds) {
-					*score += BRICK_SCORE;
-					self.audio_instructions_flags[SoundEffect::NoteHit as usize] = true;
-					
-					if brick.attempt_break() {
-						continue;
-					}
-					
-					// any broken hold notes are added to the Game's hold positions
-					let hold_position = brick_bounds.left_x + (BRICK_WIDTH as f32 / 2.0) - (HOLD_HITBOX_WIDTH as f32 / 2.0);
-					new_hold_positions.push(hold_position);
-				}
-				else {
+				let contact_time = match hitbox.collider.contact_time(&brick_collider, 0.0, brick_velocity, duration) {
+					Some(t) if hitbox.brick_type == brick_type => { t },
+					_ => { continue; }
+				};
+				
+				*score += self.config.brick_score;
+				self.hit_times.push(time_running + contact_time);
+				schedule_sound(&mut self.audio_instructions_times, SoundEffect::NoteHit, time_running + contact_time);
+				
+				if brick.attempt_break() {
 					continue;
 				}
+				
+				// any broken hold notes are added to the Game's hold positions
+				let hold_position = brick_collider.bounds.left_x + (brick.width() / 2.0) - (HOLD_HITBOX_WIDTH as f32 / 2.0);
+				new_hold_positions.push(hold_position);
 
-				// it's a hold note, break any hold segments which also intersect with the hitbox
+				// it's a hold note, break any hold segments which also touch the hitbox over the duration
 					// (when the top of a hold is broken, also break any hold segments which are hit 
 					// so that the hold portion begins strictly after the initial hit)
 				loop {
-					let segment_brick_bounds = match brick.bounds(target_y) {
+					let segment_collider = match brick.collider(target_y) {
 						None => { break; },
-						Some(b) => { b }
-					};;
-					if objects::intersect(&hitbox.bounds, &segment_brick_bounds) {
+						Some(c) => { c }
+					};
+					if let Some(contact_time) = hitbox.collider.contact_time(&segment_collider, 0.0, brick_velocity, duration) {
 						*score += HOLD_SEGMENT_SCORE;
+						self.hit_times.push(time_running + contact_time);
 						if brick.attempt_break() {
 							break;
 						}
@@ -702,6 +1250,8 @@ impl Game {
 		let hold_time = if let Some(t) = self.player.hold_end_time() { t - self.game_data.time_running } else { tick_duration };
 		let hold_hitboxes = self.player.hold_hitboxes();
 		let score = &mut self.game_data.score;
+		let time_running = self.game_data.time_running;
+		let brick_velocity = -self.game_data.brick_speed;
 		
 		// if there is a current target and it has hold segments which intersect the hold hitbox (over the duration), destroy those segments
 		if let Some(ti) = self.targets.get_mut(self.target_idx) {
@@ -716,15 +1266,18 @@ impl Game {
 						break;
 					}
 					
-					if let Some(segment_brick_bounds) = brick.bounds(target_y) {
-						let mut modified_brick_bounds = segment_brick_bounds;
-						modified_brick_bounds.top_y -= hold_time * self.game_data.brick_speed;
+					if let Some(segment_collider) = brick.collider(target_y) {
 						brick_survived = true;
 						
+						// sweep the segment as it scrolls up over the hold
 						for hitbox in &hold_hitboxes {
-							if hitbox.brick_type == brick_type && objects::intersect(&hitbox.bounds, &modified_brick_bounds) {
-								self.audio_instructions_flags[SoundEffect::NoteSegmentHit as usize] = true;
+							if hitbox.brick_type != brick_type {
+								continue;
+							}
+							if let Some(contact_time) = hitbox.collider.contact_time(&segment_collider, 0.0, brick_velocity, hold_time) {
+								schedule_sound(&mut self.audio_instructions_times, SoundEffect::NoteSegmentHit, time_running + contact_time);
 								*score += HOLD_SEGMENT_SCORE;
+								self.hit_times.push(time_running + contact_time);
 								brick.attempt_break();
 								brick_survived = false;
 								break;
@@ -749,7 +1302,11 @@ impl Game {
 		
 		let time = if time < 0.0 { 0.0 } else { time };
 		
-		self.player = Player::new((BRICK_WIDTH * 2) as f32 - objects::PLAYER_WIDTH as f32 / 2.0, EARLY_ARRIVAL_DISTANCE / self.game_data.brick_speed);
+		let playfield = self.playfield();
+		self.player = Player::new(Game::player_start_x(&playfield), 
+			self.config.early_arrival_distance / self.game_data.brick_speed, self.config, playfield);
+		self.scheduler.clear();
+		self.hit_times.clear();
 		self.last_target_missed = false;
 		self.scrolled_y = self.game_data.brick_speed * time;
 		self.end_y = Game::end_y(self.scrolled_y, self.game_data.brick_speed);
@@ -771,7 +1328,8 @@ impl Game {
 		// update player's target
 		let mut target = None; 
 		if let Some(ti) = self.targets.get(self.target_idx) {
-			if ti.appearance_y > self.end_y {
+			// from the time rather than end_y, so the target appears at exactly the time of its event
+			if self.appearance_time(ti) > self.game_data.time_running {
 				target = None;
 			}
 			else {
@@ -784,10 +1342,43 @@ impl Game {
 	}
 	
 	fn end_y(scrolled_y: f32, brick_speed: f32) -> f32 {
-		return scrolled_y + GAME_HEIGHT as f32 + brick_speed * 2.0; // 2 second window after bricks are off the screen
+		return scrolled_y + GAME_HEIGHT + brick_speed * 2.0; // 2 second window after bricks are off the screen
+	}
+}
+
+// not exported to wasm
+impl Game {
+	// all targets of the song, in order
+	pub fn targets(&self) -> &VecDeque<TargetInfo> {
+		return &self.targets;
+	}
+	
+	pub fn playfield(&self) -> Playfield {
+		return Playfield::new(self.game_data.lanes);
+	}
+	
+	// the inputs and releases waiting to be handled, in order of time
+	pub fn queued(&self) -> Vec<QueuedInput> {
+		return self.scheduler.inputs().iter().filter_map(|event| {
+			return match event.kind {
+				EventKind::Input(input) => Some(QueuedInput { time: event.time, input, press: true }),
+				EventKind::Release(input) => Some(QueuedInput { time: event.time, input, press: false }),
+				_ => None
+			};
+		}).collect();
+	}
+	
+	// the player starts centered on the line between the second and third lanes
+	fn player_start_x(playfield: &Playfield) -> f32 {
+		return playfield.lane_x(2) - objects::PLAYER_WIDTH as f32 / 2.0;
 	}
 }
 
+// plays a sound effect this tick, at the earliest time it was scheduled for
+fn schedule_sound(audio_instructions_times: &mut [Option<f32>; 128], sound: SoundEffect, time: f32) {
+	let scheduled = &mut audio_instructions_times[sound as usize];
+	*scheduled = Some(scheduled.map_or(time, |t| t.min(time)));
+}
 
 // Equality and Order are determined only on the appearance y of bricks
 impl PartialEq for UpcomingBrick {
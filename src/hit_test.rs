@@ -0,0 +1,84 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::BrickData;
+use crate::brick;
+use crate::playfield::Playfield;
+use crate::objects::ObjectBounds;
+use crate::objects::BRICK_SEGMENT_GAP;
+
+use crate::TIME_ZERO_BRICK_POS;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitKind {
+	Brick,
+	HoldSegment,
+	Empty
+}
+
+// what is under a point on the screen, and the beat pos and lane the point snaps to
+#[wasm_bindgen]
+pub struct HitTest {
+	pub kind: HitKind,
+	pub beat_pos: i32,
+	pub x_pos: i32,
+	pub segment: u32, // which hold segment was hit (0 being the segment right below the brick), if the kind is HoldSegment
+	brick: Option<BrickData>
+}
+
+#[wasm_bindgen]
+impl HitTest {
+	// the brick that was hit (the hold note for a hold segment), if any
+	pub fn brick(&self) -> Option<BrickData> {
+		return self.brick.clone();
+	}
+}
+
+// finds what is at the point x, y where y includes the amount scrolled (y = scrolled_y is the top of the screen)
+	// bricks are hit within their collider (so not on their empty corners), and hold segments within their bounds or the gap above them.
+	// a brick is preferred over the hold segments of another brick
+pub fn hit_test(notes: &BTreeSet<BrickData>, playfield: &Playfield, bpm: f32, brick_speed: f32, x: f32, y: f32) -> HitTest {
+	let seconds_per_beat = 60.0 / bpm;
+	let time = (y - TIME_ZERO_BRICK_POS) / brick_speed;
+	let beat_pos = (time / seconds_per_beat * 4.0).floor() as i32;
+	let x_pos = playfield.lane_at(x);
+	let brick_width = playfield.lane_width();
+
+	let mut hit = HitTest { kind: HitKind::Empty, beat_pos, x_pos, segment: 0, brick: None };
+	for brick_data in notes {
+		let top_y = brick_data.appearance_y(bpm, brick_speed);
+		if top_y > y {
+			break;
+		}
+
+		let brick_x = brick_data.x(playfield);
+		if brick::brick_collider(brick_x, brick_width, top_y).contains(x, y) {
+			hit.kind = HitKind::Brick;
+			hit.segment = 0;
+			hit.brick = Some(brick_data.clone());
+			return hit;
+		}
+
+		if hit.kind == HitKind::Empty {
+			for segment in 0..brick::hold_segment_count(brick_data, bpm, brick_speed) {
+				let mut bounds = brick::hold_segment_bounds(brick_x, brick_width, top_y, segment);
+				bounds.top_y -= BRICK_SEGMENT_GAP as f32;
+				if contains(&bounds, x, y) {
+					hit.kind = HitKind::HoldSegment;
+					hit.segment = segment;
+					hit.brick = Some(brick_data.clone());
+					break;
+				}
+			}
+		}
+	}
+
+	return hit;
+}
+
+fn contains(bounds: &ObjectBounds, x: f32, y: f32) -> bool {
+	return x >= bounds.left_x && x < bounds.right_x && y >= bounds.top_y && y < bounds.bottom_y;
+}
@@ -2,18 +2,12 @@
 // TODO
 // handle losing focus on window / possible browser events that disrupt the game
 
-// object collision??
-	// object collision detecting more precise than using a minimum bounding rectangle
 // check-sum on loaded songs 
-// Precise ticking even for longer delta times
-// create the data structure to hold objects in order of layer
 
 // rhythm-warrior.js uses workaround because instantiateStreaming doesn't function correctly (MIME type not working??)
 	// https://stackoverflow.com/questions/52239924/webassembly-instantiatestreaming-wrong-mime-type 
 	// -- defaulting to using "instantiate" instead of "instantiateStreaming"
 // Make sure things work in all browsers, especially ESModules
-// expand on read-MIDI functionality, and add options to control generated output such as only use certain program numbers (instruments)
-	// or channels to generate notes, criteria for excluding notes if there are too many, etc.
 // stick with sqlite/sqljs?
 // Log objects going beyond boundaries
 // Valid to create/delete menu if it means better performance
@@ -28,13 +22,32 @@
 // !!! fix and extend midi-reader / song converter
 // !!! are as casts what I want / are they idiomatic Rust? Also, types seem to be arbitrary...
 	// (define floats and integer forms of constants so casting isn't needed?)
-	// why are objects (in objects.rs) and GAME_WIDTH and GAME_HEIGHT integers while most everythings else are floats?
+	// why are objects (in objects.rs) integers while most everythings else are floats?
 
 mod objects;
+mod playfield;
+mod collider;
 mod resources;
 mod player;
 mod brick;
 mod game;
+mod config;
+mod scheduler;
+mod midi;
+mod chart_import;
+mod analysis;
+mod difficulty;
+mod edit_history;
+mod selection;
+mod transform;
+mod edit_result;
+mod hit_test;
+mod editor_render;
+mod path_preview;
+mod viewport;
+mod render_queue;
+mod song_json;
+mod chart_diff;
 
 use std::cmp::Ordering;
 use macros;
@@ -42,13 +55,33 @@ use macros;
 use wasm_bindgen::prelude::*;
 use macros::EnumVariantCount;
 
-use resources::GraphicGroup;
 use objects::BrickType;
+use resources::GraphicGroup;
+use playfield::Playfield;
+use render_queue::Layer;
+use render_queue::NUM_LAYERS;
+
+// internals for the tests in tests/, not part of the crate's interface
+#[doc(hidden)]
+pub mod test_support {
+	pub use crate::objects::BrickType;
+	pub use crate::game::Game;
+	pub use crate::game::MAX_INPUT_LATENESS;
+	pub use crate::config::GameConfig;
+	pub use crate::game::TargetInfo;
+	pub use crate::brick::HittableBrick;
+	pub use crate::song_json::JsonValue;
+	pub use crate::song_json::parse as parse_json;
+	pub use crate::song_json::song_notes;
+	pub use crate::midi::MidiImportOptions;
+}
 
-const GAME_WIDTH: i32 = 1920;
-const GAME_HEIGHT: i32 = 1080;
+// the size of the world in world units. Positions, sizes, and speeds of the game are in world units, 
+	// and the viewport maps them to pixels when the game is rendered
+const GAME_WIDTH: f32 = 1920.0;
+const GAME_HEIGHT: f32 = 1080.0;
 const LEFT_BOUNDARY: f32 = 0.0;
-const RIGHT_BOUNDARY: f32 = LEFT_BOUNDARY + GAME_WIDTH as f32;
+const RIGHT_BOUNDARY: f32 = LEFT_BOUNDARY + GAME_WIDTH;
 const TOP_BOUNDARY: f32 = 0.0;
 const GROUND_POS: f32 = TOP_BOUNDARY + 240.0; // !!! associate with the graphic for the ground
 const TIME_ZERO_BRICK_POS: f32 = GROUND_POS - (objects::PLAYER_HEIGHT as f32 / 2.0) - (objects::BRICK_HEIGHT as f32 / 2.0);
@@ -67,7 +100,9 @@ pub struct GameData {
 	pub score: i32,
 	pub max_score: i32,
 	pub duration: f32,
-	pub is_modified: bool // boolean indicating whether the game's data (bricks) have been modified since opening
+	pub num_brick_types: u8, // how many brick types (colors) the song uses, from Type1 on
+	pub lanes: u8, // how many lanes (x positions) the song's bricks are spread over
+	pub is_modified: bool // boolean indicating whether the game's data (bricks) have been modified since opening or last saving
 }
 
 #[wasm_bindgen]
@@ -100,10 +135,12 @@ pub enum GraphicFlags {
 	Opacity = 4
 }
 
+// the graphics of a frame in order of layer. Javascript draws the graphics of each render pass (pass_start to pass_end) in turn
 #[wasm_bindgen]
 pub struct RenderingInstructions {
 	pub num_graphics: usize,
-	pub graphics_ptr: *const PositionedGraphic
+	pub graphics_ptr: *const PositionedGraphic,
+	layer_starts: [usize; NUM_LAYERS + 1]
 }
 
 #[wasm_bindgen]
@@ -117,15 +154,21 @@ pub enum SoundEffect {
 #[wasm_bindgen]
 pub struct AudioInstructions {
 	pub num_instructions: usize,
-	pub instructions_ptr: *const u8
+	pub instructions_ptr: *const u8,
+	pub delays_ptr: *const f32 // seconds from now to play each sound
 }
 
+// read by graphics.js from wasm memory in field order: the graphic's 4 bytes, then 4 floats, then the layer padded to 4 bytes
 #[wasm_bindgen]
+#[repr(C)]
 #[derive(Clone)]
 pub struct PositionedGraphic {
 	pub g: Graphic,
 	pub x: f32,
 	pub y: f32,
+	pub width: f32, // the size the graphic is drawn at
+	pub height: f32,
+	pub layer: Layer // from the graphic group's entry in resources::GRAPHIC_LAYERS
 }
 
 #[wasm_bindgen]
@@ -150,6 +193,21 @@ pub enum Input {
 	Slash1,
 	Slash2,
 	Slash3,
+	Slash4,
+	Slash5,
+	Slash6,
+}
+
+const NUM_INPUTS: usize = objects::MAX_BRICK_TYPES + 1; // the dash and a slash for each brick type
+const SLASH_INPUTS: [Input; objects::MAX_BRICK_TYPES] = [Input::Slash1, Input::Slash2, Input::Slash3, Input::Slash4, Input::Slash5, Input::Slash6];
+
+// an input or release waiting in the game's input queue, at its time in the song
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct QueuedInput {
+	pub time: f32,
+	pub input: Input,
+	pub press: bool // false for a release
 }
 
 #[wasm_bindgen]
@@ -158,8 +216,18 @@ pub fn ground_pos() -> i32 {
 }
 
 #[wasm_bindgen]
-pub fn max_notes_per_screen_width() -> u8 {
-	return objects::MAX_NOTES_PER_SCREEN_WIDTH;
+pub fn min_lanes() -> u8 {
+	return objects::MIN_LANES;
+}
+
+#[wasm_bindgen]
+pub fn max_lanes() -> u8 {
+	return objects::MAX_LANES;
+}
+
+#[wasm_bindgen]
+pub fn max_brick_types() -> usize {
+	return objects::MAX_BRICK_TYPES;
 }
 
 #[wasm_bindgen]
@@ -168,10 +236,11 @@ pub fn time_zero_brick_pos() -> f32 {
 }
 
 #[wasm_bindgen]
+// the size of the world, in world units
 pub fn game_dimensions() -> Position {
 	Position {
-		x: GAME_WIDTH as f32,
-		y: GAME_HEIGHT as f32,
+		x: GAME_WIDTH,
+		y: GAME_HEIGHT,
 	}
 }
 
@@ -183,14 +252,6 @@ pub fn player_dimensions() -> Position {
 	}
 }
 
-#[wasm_bindgen]
-pub fn brick_dimensions() -> Position {
-	return Position {
-		x: objects::BRICK_WIDTH as f32,
-		y: objects::BRICK_HEIGHT as f32,
-	};
-}
-
 fn frame_number(time_since_start: f32) -> u8 {
 	return ((time_since_start / FRAME_TIME) % 256.0) as u8;
 }
@@ -202,13 +263,46 @@ pub fn num_possible_inputs() -> usize {
 
 // --- methods and trait implementation ---
 
+impl Input {
+	// the slash input of a brick type
+	pub fn slash(brick_type: BrickType) -> Input {
+		return SLASH_INPUTS[brick_type as usize];
+	}
+	
+	// the brick type that a slash input hits, None for the dash
+	pub fn brick_type(self) -> Option<BrickType> {
+		return match self {
+			Input::Dash => None,
+			_ => BrickType::from_index(self as usize - 1)
+		};
+	}
+}
+
 impl PositionedGraphic {
 	pub fn new(g: Graphic, x: f32, y: f32) -> PositionedGraphic {
 		let offset = resources::GRAPHIC_OFFSETS[g.g as usize];
+		let size = resources::GRAPHIC_SIZES[g.g as usize];
 		return PositionedGraphic {
 			g,
 			x: x - offset.x,
-			y: y - offset.y
+			y: y - offset.y,
+			width: size.x,
+			height: size.y,
+			layer: resources::GRAPHIC_LAYERS[g.g as usize]
+		}
+	}
+	
+	// a graphic of an object sized differently than the graphic's object size (such as a brick in wider or narrower lanes)
+		// the offset around the object stays the same
+	pub fn sized(g: Graphic, x: f32, y: f32, width: f32, height: f32) -> PositionedGraphic {
+		let offset = resources::GRAPHIC_OFFSETS[g.g as usize];
+		return PositionedGraphic {
+			g,
+			x: x - offset.x,
+			y: y - offset.y,
+			width: width + 2.0 * offset.x,
+			height: height + 2.0 * offset.y,
+			layer: resources::GRAPHIC_LAYERS[g.g as usize]
 		}
 	}
 }
@@ -241,6 +335,13 @@ impl Ord for BrickData {
 	}
 }
 
+impl BrickData {
+	// the left of the brick's lane
+	pub fn x(&self, playfield: &Playfield) -> f32 {
+		return playfield.lane_x(self.x_pos);
+	}
+}
+
 #[wasm_bindgen]
 impl BrickData {
 	// here for construction of BrickData structures from javascript
@@ -292,10 +393,6 @@ impl BrickData {
 		return pixels_passed + TIME_ZERO_BRICK_POS;
 	}
 	
-	pub fn x(&self) -> f32 {
-		return (self.x_pos * objects::BRICK_WIDTH) as f32;
-	}
-	
 	pub fn approx_time(beat_pos: i32, bpm: f32) -> f32 {
 		let minutes_per_beat = 1.0 / bpm;
 		let seconds_per_beat = 60.0 * minutes_per_beat;
@@ -311,6 +408,13 @@ impl BrickData {
 		let beat_pos_passed = num_beats_passed * 4.0;
 		return beat_pos_passed.floor() as i32;
 	}
+	
+	// the beat pos closest to the given time, snapped to a grid of the given number of beat positions
+	pub fn quantized_beat_pos(time: f32, bpm: f32, grid: i32) -> i32 {
+		let seconds_per_beat = 60.0 / bpm;
+		let beat_pos_passed = time / seconds_per_beat * 4.0;
+		return (beat_pos_passed / grid as f32).round() as i32 * grid;
+	}
 }
 
 // !!! logging
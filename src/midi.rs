@@ -0,0 +1,743 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+
+use crate::BrickData;
+use crate::objects::BrickType;
+use crate::objects::MAX_BRICK_TYPES;
+
+const HEADER_CHUNK: &[u8] = b"MThd";
+const TRACK_CHUNK: &[u8] = b"MTrk";
+const DEFAULT_TEMPO: u32 = 500000; // microseconds per quarter note (120 bpm), used until a set tempo meta event is read
+
+// exported charts use 480 ticks per quarter note, so that a beat pos (sixteenth note) is 120 ticks
+	// and the leading/trailing offset of an eighth of a beat is 60 ticks
+const EXPORT_TICKS_PER_QUARTER: u16 = 480;
+const EXPORT_TICKS_PER_BEAT_POS: u32 = EXPORT_TICKS_PER_QUARTER as u32 / 4;
+const EXPORT_LEADING_OFFSET: u32 = EXPORT_TICKS_PER_QUARTER as u32 / 8;
+const EXPORT_TAP_LENGTH: u32 = EXPORT_TICKS_PER_BEAT_POS / 2;
+const CHART_BASE_PITCH: i32 = 36; // pitch of x_pos 0 in exported charts
+// velocity of exported notes. The low bits carry the brick's flags so charts import back without loss
+const CHART_VELOCITY: u8 = 64;
+const CHART_LEADING_FLAG: u8 = 1;
+const CHART_TRAILING_FLAG: u8 = 2;
+const CHART_TRIPLET_FLAG: u8 = 4;
+const CHART_HOLD_FLAG: u8 = 8;
+// each brick type's notes go on the channel of its index or a second channel 6 higher (skipping the percussion channel 9)
+	// a note goes on the next channel when one of the same type and pitch is still sounding, so every note on pairs with its own note off
+const CHART_CHANNELS: [[u8; 2]; MAX_BRICK_TYPES] = [[0, 6], [1, 7], [2, 8], [3, 10], [4, 11], [5, 12]];
+
+// how a note's brick type is chosen
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BrickTypeSource {
+	Velocity, // notes are split by velocity into the song's brick types, softest to Type1 and loudest to the last type
+	Channel // the note's channel mod the number of brick types
+}
+
+// options controlling which notes of a MIDI file become bricks and how they are placed
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub struct MidiImportOptions {
+	pub channels: u16, // bitmask of the channels to read notes from. bit n set means channel n is read
+	pub program: Option<u8>, // if set, only notes played by this program number (instrument) are read
+	pub type_source: BrickTypeSource,
+	pub max_notes_per_beat: u8, // the maximum amount of notes that start within one beat, quietest notes excluded first. 0 for no cap
+	pub quantization: i32, // grid, in beat positions (sixteenth notes), that note starts and ends are snapped to
+	pub min_hold_length: i32, // notes at least this many beat positions long become hold notes. 0 for no hold notes
+	pub chart_notes: bool // notes were written by export_midi. pitch is read directly as the lane, and flags are read from the velocity
+}
+
+struct MidiNote {
+	channel: u8,
+	program: u8,
+	pitch: u8,
+	velocity: u8,
+	start_tick: u32,
+	end_tick: u32
+}
+
+struct TempoChange {
+	tick: u32,
+	tempo: u32 // microseconds per quarter note
+}
+
+// the notes and tempo map read from a standard MIDI file
+pub struct MidiFile {
+	ticks_per_quarter: u16,
+	tempo_map: Vec<TempoChange>, // ordered by tick, always starts at tick 0
+	notes: Vec<MidiNote>
+}
+
+#[wasm_bindgen]
+impl MidiImportOptions {
+	pub fn new() -> MidiImportOptions {
+		return MidiImportOptions {
+			channels: 0xFFFF,
+			program: None,
+			type_source: BrickTypeSource::Velocity,
+			max_notes_per_beat: 0,
+			quantization: 1,
+			min_hold_length: 4,
+			chart_notes: false
+		};
+	}
+	
+	// options to import a chart exported with export_midi back into the same bricks
+	pub fn chart() -> MidiImportOptions {
+		return MidiImportOptions {
+			channels: 0xFFFF,
+			program: None,
+			type_source: BrickTypeSource::Channel,
+			max_notes_per_beat: 0,
+			quantization: 1,
+			min_hold_length: 0,
+			chart_notes: true
+		};
+	}
+}
+
+impl Default for MidiImportOptions {
+	fn default() -> MidiImportOptions {
+		return MidiImportOptions::new();
+	}
+}
+
+// returns the bpm of the first tempo of the MIDI file, to be used as the bpm of a song created from the file
+#[wasm_bindgen]
+pub fn midi_bpm(bytes: &[u8]) -> Result<f32, String> {
+	let midi = read_midi(bytes)?;
+	return Ok(60000000.0 / midi.tempo_map[0].tempo as f32);
+}
+
+// reads the header, note on/off events, program changes, and tempo changes of a standard MIDI file
+pub fn read_midi(bytes: &[u8]) -> Result<MidiFile, String> {
+	let mut reader = ByteReader { bytes, pos: 0 };
+	let mut ticks_per_quarter = None;
+	let mut tempo_map = Vec::new();
+	let mut notes = Vec::new();
+
+	while reader.remaining() >= 8 {
+		let chunk_type = reader.read_slice(4)?;
+		let chunk_len = reader.read_u32()? as usize;
+		if chunk_len > reader.remaining() {
+			return Err(format!("Chunk of length {} runs past the end of the file", chunk_len));
+		}
+		let chunk = reader.read_slice(chunk_len)?;
+
+		if chunk_type == HEADER_CHUNK {
+			if ticks_per_quarter.is_some() {
+				return Err("Found more than one header chunk".to_string());
+			}
+			if chunk.len() < 6 {
+				return Err("Header chunk is shorter than 6 bytes".to_string());
+			}
+
+			let format = u16::from_be_bytes([chunk[0], chunk[1]]);
+			let division = u16::from_be_bytes([chunk[4], chunk[5]]);
+			if format > 2 {
+				return Err(format!("Unknown MIDI format {}", format));
+			}
+			if division & 0x8000 != 0 {
+				return Err("SMPTE time division is not supported".to_string());
+			}
+			if division == 0 {
+				return Err("Ticks per quarter note is 0".to_string());
+			}
+
+			ticks_per_quarter = Some(division);
+		}
+		else if chunk_type == TRACK_CHUNK {
+			if ticks_per_quarter.is_none() {
+				return Err("Found a track chunk before the header chunk".to_string());
+			}
+			read_track(chunk, &mut tempo_map, &mut notes)?;
+		}
+		// unknown chunks are skipped
+	}
+
+	let ticks_per_quarter = match ticks_per_quarter {
+		Some(t) => t,
+		None => { return Err("No header chunk found".to_string()); }
+	};
+
+	// tempo changes can come from any track. Order them and make sure there is a tempo at tick 0
+	tempo_map.sort_by_key(|tc: &TempoChange| tc.tick);
+	if tempo_map.len() == 0 || tempo_map[0].tick != 0 {
+		tempo_map.insert(0, TempoChange { tick: 0, tempo: DEFAULT_TEMPO });
+	}
+
+	notes.sort_by_key(|n: &MidiNote| (n.start_tick, n.pitch));
+
+	return Ok(MidiFile { ticks_per_quarter, tempo_map, notes });
+}
+
+// reads the events of a single track chunk, adding to the tempo map and the list of notes
+fn read_track(chunk: &[u8], tempo_map: &mut Vec<TempoChange>, notes: &mut Vec<MidiNote>) -> Result<(), String> {
+	let mut reader = ByteReader { bytes: chunk, pos: 0 };
+	let mut tick: u32 = 0;
+	let mut running_status: Option<u8> = None;
+	let mut programs = [0u8; 16];
+	// notes which have been turned on but not off, by channel and pitch. Overlapping notes of the same pitch are closed first-in first-out
+	let mut notes_on: Vec<Vec<Vec<(u32, u8, u8)>>> = vec![vec![Vec::new(); 128]; 16];
+
+	while reader.remaining() > 0 {
+		let delta = reader.read_variable_length()?;
+		tick = match tick.checked_add(delta) {
+			Some(t) => t,
+			None => { return Err(format!("Delta time {} after tick {} is past the last tick a file can have", delta, tick)); }
+		};
+
+		let mut status = reader.peek()?;
+		if status & 0x80 != 0 {
+			reader.pos += 1;
+		}
+		else {
+			status = match running_status {
+				Some(s) => s,
+				None => { return Err(format!("Data byte with no running status at tick {}", tick)); }
+			};
+		}
+
+		match status {
+			0xFF => {
+				let meta_type = reader.read_u8()?;
+				let len = reader.read_variable_length()? as usize;
+				let data = reader.read_slice(len)?;
+
+				match meta_type {
+					0x51 => {
+						if data.len() != 3 {
+							return Err("Set tempo meta event does not have a length of 3".to_string());
+						}
+						let tempo = (data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32;
+						if tempo == 0 {
+							return Err("Set tempo meta event with a tempo of 0".to_string());
+						}
+						tempo_map.push(TempoChange { tick, tempo });
+					},
+					0x2F => { break; }, // end of track
+					_ => {} // other meta events don't affect the notes
+				}
+				running_status = None;
+			},
+			0xF0 | 0xF7 => {
+				// skip system exclusive events
+				let len = reader.read_variable_length()? as usize;
+				reader.read_slice(len)?;
+				running_status = None;
+			},
+			_ => {
+				let channel = (status & 0x0F) as usize;
+				running_status = Some(status);
+
+				match status & 0xF0 {
+					0x80 | 0x90 => {
+						let pitch = reader.read_u8()? & 0x7F;
+						let velocity = reader.read_u8()? & 0x7F;
+						let open_notes = &mut notes_on[channel][pitch as usize];
+
+						// a note on with 0 velocity is a note off
+						if status & 0xF0 == 0x90 && velocity > 0 {
+							open_notes.push((tick, velocity, programs[channel]));
+						}
+						else if open_notes.len() > 0 {
+							let (start_tick, velocity, program) = open_notes.remove(0);
+							notes.push(MidiNote { channel: channel as u8, program, pitch, velocity, start_tick, end_tick: tick });
+						}
+					},
+					0xA0 | 0xB0 | 0xE0 => { reader.read_slice(2)?; },
+					0xC0 => { programs[channel] = reader.read_u8()? & 0x7F; },
+					0xD0 => { reader.read_slice(1)?; },
+					_ => { return Err(format!("Unknown status byte {:#X} at tick {}", status, tick)); }
+				}
+			}
+		}
+	}
+
+	// close any notes that were never turned off at the last tick of the track
+	for (channel, channel_notes_on) in notes_on.iter().enumerate() {
+		for (pitch, open_notes) in channel_notes_on.iter().enumerate() {
+			for (start_tick, velocity, program) in open_notes {
+				notes.push(MidiNote { channel: channel as u8, program: *program, pitch: pitch as u8, velocity: *velocity,
+					start_tick: *start_tick, end_tick: tick });
+			}
+		}
+	}
+
+	return Ok(());
+}
+
+// converts the notes of a MIDI file into bricks for a song with the given bpm
+pub fn midi_to_bricks(midi: &MidiFile, options: &MidiImportOptions, bpm: f32, num_brick_types: u8, lanes: u8) -> Vec<BrickData> {
+	let grid = if options.quantization < 1 { 1 } else { options.quantization };
+
+	let notes: Vec<&MidiNote> = midi.notes.iter().filter(|n| {
+		let channel_read = options.channels & (1 << n.channel) != 0;
+		let program_read = match options.program {
+			None => true,
+			Some(p) => p == n.program
+		};
+		return channel_read && program_read;
+	}).collect();
+
+	if notes.len() == 0 {
+		return Vec::new();
+	}
+
+	// pitch range of the notes being read, to spread them across the screen
+	let mut min_pitch = notes[0].pitch;
+	let mut max_pitch = notes[0].pitch;
+	for note in &notes {
+		if note.pitch < min_pitch { min_pitch = note.pitch; }
+		if note.pitch > max_pitch { max_pitch = note.pitch; }
+	}
+
+	// candidate bricks paired with the velocity of the note they came from
+	let mut candidates: Vec<(BrickData, u8)> = Vec::with_capacity(notes.len());
+	for note in &notes {
+		if options.chart_notes {
+			candidates.push((chart_note_to_brick(midi, note, bpm), note.velocity));
+			continue;
+		}
+		
+		let beat_pos = BrickData::quantized_beat_pos(midi.tick_to_seconds(note.start_tick), bpm, grid);
+		let end_beat_pos = BrickData::quantized_beat_pos(midi.tick_to_seconds(note.end_tick), bpm, grid);
+		let is_hold_note = options.min_hold_length > 0 && end_beat_pos - beat_pos >= options.min_hold_length;
+
+		let brick_type = match options.type_source {
+			BrickTypeSource::Velocity => BrickType::from_index_wrapped(note.velocity as usize * num_brick_types as usize / 128, num_brick_types),
+			BrickTypeSource::Channel => BrickType::from_index_wrapped(note.channel as usize, num_brick_types)
+		};
+
+		let brick_data = BrickData {
+			brick_type,
+			beat_pos,
+			end_beat_pos: if is_hold_note { end_beat_pos } else { beat_pos },
+			x_pos: pitch_to_x_pos(note.pitch, min_pitch, max_pitch, lanes),
+			is_triplet: false,
+			is_trailing: false,
+			is_leading: false,
+			is_hold_note
+		};
+		candidates.push((brick_data, note.velocity));
+	}
+
+	// louder notes take priority, both for the density cap and when two notes land on the same position
+	candidates.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+	let mut bricks = BTreeSet::new();
+	let mut notes_per_beat: Vec<u8> = Vec::new();
+	for (brick_data, _) in candidates {
+		if options.max_notes_per_beat > 0 {
+			let beat = (brick_data.beat_pos / 4) as usize;
+			if beat >= notes_per_beat.len() {
+				notes_per_beat.resize(beat + 1, 0);
+			}
+			if notes_per_beat[beat] >= options.max_notes_per_beat {
+				continue;
+			}
+			if bricks.insert(brick_data) {
+				notes_per_beat[beat] += 1;
+			}
+		}
+		else {
+			bricks.insert(brick_data);
+		}
+	}
+
+	return bricks.into_iter().collect();
+}
+
+// reads a note written by export_midi, whose pitch is its lane and whose velocity carries its flags
+fn chart_note_to_brick(midi: &MidiFile, note: &MidiNote, bpm: f32) -> BrickData {
+	let flags = note.velocity.wrapping_sub(CHART_VELOCITY);
+	let is_leading = flags & CHART_LEADING_FLAG != 0;
+	let is_trailing = flags & CHART_TRAILING_FLAG != 0;
+	let is_hold_note = flags & CHART_HOLD_FLAG != 0;
+
+	// undo the leading/trailing offset before snapping to the beat pos
+	let offset = if is_leading { 0.5 } else if is_trailing { -0.5 } else { 0.0 };
+	let seconds_per_beat = 60.0 / bpm;
+	let beat_pos_passed = midi.tick_to_seconds(note.start_tick) / seconds_per_beat * 4.0 + offset;
+	let beat_pos = beat_pos_passed.round() as i32;
+	let end_beat_pos = BrickData::quantized_beat_pos(midi.tick_to_seconds(note.end_tick), bpm, 1);
+
+	return BrickData {
+		brick_type: chart_channel_type(note.channel),
+		beat_pos,
+		end_beat_pos: if is_hold_note { end_beat_pos } else { beat_pos },
+		x_pos: note.pitch as i32 - CHART_BASE_PITCH,
+		is_triplet: flags & CHART_TRIPLET_FLAG != 0,
+		is_trailing,
+		is_leading,
+		is_hold_note
+	};
+}
+
+// writes the bricks of a song as a format 0 standard MIDI file, with the song's bpm as its tempo
+	// x_pos is written as pitch, each brick type has its own channels, and hold notes sound until their end beat pos
+	// errors, listing the bricks, if any brick is before the start of the song or has an x_pos that no pitch can carry
+pub fn bricks_to_midi<'a, I>(bricks: I, bpm: f32) -> Result<Vec<u8>, String> where I: Iterator<Item = &'a BrickData> {
+	// (tick, is note on, channel, pitch, velocity)
+	let mut events: Vec<(u32, bool, u8, u8, u8)> = Vec::new();
+	// tick that the last note on each channel and pitch ends
+	let mut sounding_until = [[0u32; 128]; 16];
+	let mut unexportable = Vec::new();
+
+	for brick_data in bricks {
+		let pitch = brick_data.x_pos + CHART_BASE_PITCH;
+		// a leading brick at beat pos 0 would start before the start of the file
+		let before_start = brick_data.beat_pos < 0 || (brick_data.beat_pos == 0 && brick_data.is_leading);
+		if !(0..=127).contains(&pitch) || before_start {
+			unexportable.push(format!("beat {} lane {}", brick_data.beat_pos as f32 / 4.0, brick_data.x_pos));
+			continue;
+		}
+		let pitch = pitch as u8;
+
+		let mut velocity = CHART_VELOCITY;
+		let mut start_tick = brick_data.beat_pos as u32 * EXPORT_TICKS_PER_BEAT_POS;
+		if brick_data.is_leading {
+			velocity |= CHART_LEADING_FLAG;
+			start_tick -= EXPORT_LEADING_OFFSET;
+		} else if brick_data.is_trailing {
+			velocity |= CHART_TRAILING_FLAG;
+			start_tick += EXPORT_LEADING_OFFSET;
+		}
+		if brick_data.is_triplet {
+			velocity |= CHART_TRIPLET_FLAG;
+		}
+
+		let mut end_tick = start_tick + EXPORT_TAP_LENGTH;
+		if brick_data.is_hold_note {
+			velocity |= CHART_HOLD_FLAG;
+			let hold_end_tick = brick_data.end_beat_pos.max(0) as u32 * EXPORT_TICKS_PER_BEAT_POS;
+			if hold_end_tick > start_tick {
+				end_tick = hold_end_tick;
+			}
+		}
+
+		let type_channels = &CHART_CHANNELS[brick_data.brick_type as usize];
+		let mut channel = type_channels[type_channels.len() - 1];
+		for c in type_channels {
+			if sounding_until[*c as usize][pitch as usize] <= start_tick {
+				channel = *c;
+				break;
+			}
+		}
+		sounding_until[channel as usize][pitch as usize] = end_tick;
+
+		events.push((start_tick, true, channel, pitch, velocity));
+		events.push((end_tick, false, channel, pitch, 0));
+	}
+
+	if !unexportable.is_empty() {
+		return Err(format!("{} bricks are before the start of the song or outside the lanes a MIDI file can hold: {}", unexportable.len(), unexportable.join(", ")));
+	}
+
+	// order by time, with note offs before note ons at the same tick
+	events.sort_by_key(|e| (e.0, e.1));
+
+	let mut track = Vec::new();
+	let tempo = (60000000.0 / bpm).round() as u32;
+	write_variable_length(&mut track, 0);
+	track.extend_from_slice(&[0xFF, 0x51, 0x03, (tempo >> 16) as u8, (tempo >> 8) as u8, tempo as u8]);
+
+	let mut tick = 0;
+	for (event_tick, is_note_on, channel, pitch, velocity) in events {
+		write_variable_length(&mut track, event_tick - tick);
+		tick = event_tick;
+
+		let status = if is_note_on { 0x90 } else { 0x80 };
+		track.extend_from_slice(&[status | channel, pitch, velocity]);
+	}
+	write_variable_length(&mut track, 0);
+	track.extend_from_slice(&[0xFF, 0x2F, 0x00]);
+
+	let mut bytes = Vec::with_capacity(track.len() + 22);
+	bytes.extend_from_slice(HEADER_CHUNK);
+	bytes.extend_from_slice(&6u32.to_be_bytes());
+	bytes.extend_from_slice(&0u16.to_be_bytes()); // format 0
+	bytes.extend_from_slice(&1u16.to_be_bytes()); // 1 track
+	bytes.extend_from_slice(&EXPORT_TICKS_PER_QUARTER.to_be_bytes());
+	bytes.extend_from_slice(TRACK_CHUNK);
+	bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+	bytes.extend_from_slice(&track);
+
+	return Ok(bytes);
+}
+
+fn write_variable_length(buf: &mut Vec<u8>, val: u32) {
+	let mut groups = [0u8; 4];
+	let mut num_groups = 0;
+	let mut remaining = val;
+	loop {
+		groups[num_groups] = (remaining & 0x7F) as u8;
+		num_groups += 1;
+		remaining >>= 7;
+		if remaining == 0 || num_groups == groups.len() {
+			break;
+		}
+	}
+
+	// most significant group first, with the continuation bit set on all but the last byte
+	for i in (0 .. num_groups).rev() {
+		let continuation = if i > 0 { 0x80 } else { 0 };
+		buf.push(groups[i] | continuation);
+	}
+}
+
+// spreads pitches across the song's lanes, centered if the range fits and scaled down if it doesn't
+fn pitch_to_x_pos(pitch: u8, min_pitch: u8, max_pitch: u8, lanes: u8) -> i32 {
+	let lanes = lanes as i32;
+	let range = (max_pitch - min_pitch) as i32;
+
+	if range < lanes {
+		let mid_pitch = (min_pitch as i32 + max_pitch as i32) / 2;
+		let x_pos = pitch as i32 - mid_pitch + lanes / 2;
+		return if x_pos > lanes - 1 { lanes - 1 } else { x_pos };
+	}
+
+	return (pitch - min_pitch) as i32 * (lanes - 1) / range;
+}
+
+// the brick type written to the channel by export_midi, Type1 for channels export_midi doesn't use
+fn chart_channel_type(channel: u8) -> BrickType {
+	for (idx, channels) in CHART_CHANNELS.iter().enumerate() {
+		if channels.contains(&channel) {
+			return BrickType::from_index(idx).unwrap();
+		}
+	}
+	return BrickType::Type1;
+}
+
+impl MidiFile {
+	// the time in seconds of a tick, following the tempo map
+	pub fn tick_to_seconds(&self, tick: u32) -> f32 {
+		let mut seconds: f64 = 0.0;
+		let mut prev = &self.tempo_map[0];
+
+		for tc in &self.tempo_map[1..] {
+			if tc.tick >= tick {
+				break;
+			}
+			seconds += (tc.tick - prev.tick) as f64 * prev.tempo as f64;
+			prev = tc;
+		}
+		seconds += (tick - prev.tick) as f64 * prev.tempo as f64;
+
+		return (seconds / 1000000.0 / self.ticks_per_quarter as f64) as f32;
+	}
+}
+
+struct ByteReader<'a> {
+	bytes: &'a [u8],
+	pos: usize
+}
+
+impl<'a> ByteReader<'a> {
+	fn remaining(&self) -> usize {
+		return self.bytes.len() - self.pos;
+	}
+
+	fn peek(&self) -> Result<u8, String> {
+		return match self.bytes.get(self.pos) {
+			Some(b) => Ok(*b),
+			None => Err("Unexpected end of data".to_string())
+		};
+	}
+
+	fn read_u8(&mut self) -> Result<u8, String> {
+		let b = self.peek()?;
+		self.pos += 1;
+		return Ok(b);
+	}
+
+	fn read_u32(&mut self) -> Result<u32, String> {
+		let s = self.read_slice(4)?;
+		return Ok(u32::from_be_bytes([s[0], s[1], s[2], s[3]]));
+	}
+
+	fn read_slice(&mut self, len: usize) -> Result<&'a [u8], String> {
+		if len > self.remaining() {
+			return Err("Unexpected end of data".to_string());
+		}
+		let s = &self.bytes[self.pos .. self.pos + len];
+		self.pos += len;
+		return Ok(s);
+	}
+
+	// reads a variable length quantity (at most 4 bytes, 7 bits per byte, most significant first)
+	fn read_variable_length(&mut self) -> Result<u32, String> {
+		let mut val: u32 = 0;
+		for _ in 0 .. 4 {
+			let b = self.read_u8()?;
+			val = (val << 7) | (b & 0x7F) as u32;
+			if b & 0x80 == 0 {
+				return Ok(val);
+			}
+		}
+		return Err("Variable length quantity longer than 4 bytes".to_string());
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// every field of a brick, since BrickData's equality ignores the brick type and end beat pos
+	fn fields(brick_data: &BrickData) -> (u8, i32, i32, i32, bool, bool, bool, bool) {
+		return (brick_data.brick_type as u8, brick_data.beat_pos, brick_data.end_beat_pos, brick_data.x_pos,
+			brick_data.is_triplet, brick_data.is_trailing, brick_data.is_leading, brick_data.is_hold_note);
+	}
+
+	fn reimport(bricks: &[BrickData], bpm: f32) -> Vec<BrickData> {
+		let bytes = bricks_to_midi(bricks.iter(), bpm).unwrap();
+		let midi = read_midi(&bytes).unwrap();
+		return midi_to_bricks(&midi, &MidiImportOptions::chart(), midi_bpm(&bytes).unwrap(), MAX_BRICK_TYPES as u8, 8);
+	}
+
+	const TICKS_PER_QUARTER: u16 = 96;
+
+	// appends an event to a track, preceded by its delta time
+	fn event(track: &mut Vec<u8>, delta: u32, data: &[u8]) {
+		write_variable_length(track, delta);
+		track.extend_from_slice(data);
+	}
+
+	fn tempo(track: &mut Vec<u8>, delta: u32, tempo: u32) {
+		event(track, delta, &[0xFF, 0x51, 0x03, (tempo >> 16) as u8, (tempo >> 8) as u8, tempo as u8]);
+	}
+
+	fn midi_file(format: u16, tracks: &[Vec<u8>]) -> Vec<u8> {
+		let mut bytes = Vec::new();
+		bytes.extend_from_slice(HEADER_CHUNK);
+		bytes.extend_from_slice(&6u32.to_be_bytes());
+		bytes.extend_from_slice(&format.to_be_bytes());
+		bytes.extend_from_slice(&(tracks.len() as u16).to_be_bytes());
+		bytes.extend_from_slice(&TICKS_PER_QUARTER.to_be_bytes());
+		for track in tracks {
+			let mut track = track.clone();
+			event(&mut track, 0, &[0xFF, 0x2F, 0x00]);
+			bytes.extend_from_slice(TRACK_CHUNK);
+			bytes.extend_from_slice(&(track.len() as u32).to_be_bytes());
+			bytes.extend_from_slice(&track);
+		}
+		return bytes;
+	}
+
+	// (channel, program, pitch, velocity, start tick, end tick) of each note
+	fn notes(midi: &MidiFile) -> Vec<(u8, u8, u8, u8, u32, u32)> {
+		return midi.notes.iter().map(|n| (n.channel, n.program, n.pitch, n.velocity, n.start_tick, n.end_tick)).collect();
+	}
+
+	#[test]
+	fn reads_format_0() {
+		let mut track = Vec::new();
+		tempo(&mut track, 0, 400000);
+		event(&mut track, 0, &[0xC1, 5]);
+		event(&mut track, 0, &[0x91, 60, 100]);
+		event(&mut track, 0, &[0x90, 64, 50]);
+		event(&mut track, 48, &[0x80, 64, 0]);
+		event(&mut track, 48, &[0x81, 60, 0]);
+		let bytes = midi_file(0, &[track]);
+		let midi = read_midi(&bytes).unwrap();
+
+		assert_eq!(notes(&midi), vec![(1, 5, 60, 100, 0, 96), (0, 0, 64, 50, 0, 48)]);
+		assert_eq!(midi_bpm(&bytes).unwrap(), 150.0);
+	}
+
+	#[test]
+	fn reads_format_1_tracks_and_their_tempo_changes() {
+		let mut tempo_track = Vec::new();
+		tempo(&mut tempo_track, 0, 500000);
+		tempo(&mut tempo_track, 192, 250000);
+		let mut first = Vec::new();
+		event(&mut first, 96, &[0x90, 60, 80]);
+		event(&mut first, 96, &[0x80, 60, 0]);
+		let mut second = Vec::new();
+		event(&mut second, 288, &[0x92, 48, 90]);
+		event(&mut second, 24, &[0x82, 48, 0]);
+		let midi = read_midi(&midi_file(1, &[tempo_track, first, second])).unwrap();
+
+		assert_eq!(notes(&midi), vec![(0, 0, 60, 80, 96, 192), (2, 0, 48, 90, 288, 312)]);
+		// two quarters at 120 bpm, then the tempo doubles
+		assert_eq!(midi.tick_to_seconds(192), 1.0);
+		assert_eq!(midi.tick_to_seconds(288), 1.25);
+
+		// notes after the tempo change are placed by time on the song's grid
+		let mut options = MidiImportOptions::new();
+		options.min_hold_length = 0;
+		let bricks = midi_to_bricks(&midi, &options, 120.0, 1, 4);
+		let beat_positions: Vec<i32> = bricks.iter().map(|b| b.beat_pos).collect();
+		assert_eq!(beat_positions, vec![4, 10]);
+	}
+
+	#[test]
+	fn reads_running_status_and_note_ons_with_0_velocity_as_note_offs() {
+		let mut track = Vec::new();
+		event(&mut track, 0, &[0x93, 60, 70]);
+		event(&mut track, 0, &[62, 71]);
+		event(&mut track, 24, &[60, 0]);
+		event(&mut track, 24, &[62, 0]);
+		// a meta event clears the running status
+		tempo(&mut track, 0, 500000);
+		event(&mut track, 0, &[0x93, 64, 72]);
+		event(&mut track, 12, &[64, 0]);
+		let midi = read_midi(&midi_file(0, &[track])).unwrap();
+
+		assert_eq!(notes(&midi), vec![(3, 0, 60, 70, 0, 24), (3, 0, 62, 71, 0, 48), (3, 0, 64, 72, 48, 60)]);
+
+		let mut no_status = Vec::new();
+		event(&mut no_status, 0, &[60, 70]);
+		assert!(read_midi(&midi_file(0, &[no_status])).is_err());
+	}
+
+	#[test]
+	fn ticks_past_u32_are_an_error() {
+		// the largest delta time a variable length quantity holds, enough times to pass u32::MAX
+		let mut track = Vec::new();
+		for _ in 0 .. 17 {
+			event(&mut track, 0x0FFFFFFF, &[0xC0, 0]);
+		}
+		assert!(read_midi(&midi_file(0, &[track])).is_err());
+	}
+
+	#[test]
+	fn exported_chart_imports_as_the_same_bricks() {
+		let mut bricks = vec![
+			BrickData::new(BrickType::Type1, 0, 0, 0, false, false, false, false),
+			BrickData::new(BrickType::Type2, 2, 2, 3, false, false, true, false),
+			BrickData::new(BrickType::Type3, 4, 4, 7, false, true, false, false),
+			BrickData::new(BrickType::Type6, 6, 6, 1, true, false, false, false),
+			// a hold note and a tap of the same type and lane that starts while the hold is sounding
+			BrickData::new(BrickType::Type4, 8, 24, 2, false, false, false, true),
+			BrickData::new(BrickType::Type4, 12, 12, 2, false, false, false, false),
+			BrickData::new(BrickType::Type5, 31, 31, 5, false, false, false, false)
+		];
+		bricks.sort();
+
+		for bpm in [120.0, 137.0, 174.5].iter() {
+			let imported = reimport(&bricks, *bpm);
+			let expected: Vec<_> = bricks.iter().map(fields).collect();
+			let actual: Vec<_> = imported.iter().map(fields).collect();
+			assert_eq!(actual, expected, "bpm {}", bpm);
+		}
+	}
+
+	#[test]
+	fn bricks_before_the_start_or_outside_the_pitch_range_are_not_exported() {
+		let unexportable = [
+			BrickData::new(BrickType::Type1, -4, -4, 0, false, false, false, false),
+			BrickData::new(BrickType::Type1, 0, 0, 1, false, false, true, false),
+			BrickData::new(BrickType::Type1, 4, 4, 128 - CHART_BASE_PITCH, false, false, false, false)
+		];
+		for brick_data in unexportable.iter() {
+			let bricks = [BrickData::new(BrickType::Type1, 8, 8, 0, false, false, false, false), brick_data.clone()];
+			let err = bricks_to_midi(bricks.iter(), 120.0).unwrap_err();
+			assert!(err.starts_with("1 bricks"), "{}", err);
+		}
+	}
+}
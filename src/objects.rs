@@ -1,24 +1,33 @@
 
 use wasm_bindgen::prelude::*;
 
+use crate::collider::Collider;
 use crate::LEFT_BOUNDARY;
 use crate::RIGHT_BOUNDARY;
 
-pub const MAX_NOTES_PER_SCREEN_WIDTH: u8 = 32;
+pub const DEFAULT_LANES: u8 = 32;
+pub const MIN_LANES: u8 = 4;
+pub const MAX_LANES: u8 = 64;
+pub const MAX_BRICK_TYPES: usize = 6; // the most brick types (colors) a song can use, each with its own slash input
+pub const DEFAULT_BRICK_TYPES: u8 = 3;
 
 pub const PLAYER_WIDTH: i32 = 50;
 pub const PLAYER_HEIGHT: i32 = 100; 
-pub const BRICK_WIDTH: i32 = (RIGHT_BOUNDARY - LEFT_BOUNDARY) as i32 / MAX_NOTES_PER_SCREEN_WIDTH as i32;
+// the width of a brick with the default number of lanes, and the size of its graphics. A song's bricks fill its lanes (see playfield.rs)
+pub const DEFAULT_BRICK_WIDTH: i32 = (RIGHT_BOUNDARY - LEFT_BOUNDARY) as i32 / DEFAULT_LANES as i32;
 pub const BRICK_HEIGHT: i32 = 100;
 pub const BRICK_SEGMENT_HEIGHT: i32 = 50;
-pub const BRICK_SEGMENT_WIDTH: i32 = 40;
+pub const BRICK_SEGMENT_WIDTH: i32 = 40; // with the default number of lanes, like SLASH_WIDTH
 pub const BRICK_SEGMENT_GAP: i32 = 20;
-pub const SLASH_WIDTH: i32 = 60;
+pub const BRICK_CORNER_RADIUS: f32 = 10.0; // also the corner radius of hold segments
+pub const SLASH_WIDTH: i32 = 60; // with the default number of lanes. Slashes are as much wider or narrower as the song's lanes
 pub const SLASH_HEIGHT: i32 = PLAYER_HEIGHT;
+pub const SLASH_CORNER_CUT: f32 = 20.0; // how much of the far corners of a slash is cut off, following the arc of the slash
 pub const MIN_DASH_WIDTH: i32 = 50;
 pub const DASH_HEIGHT: i32 = PLAYER_HEIGHT;
 pub const HOLD_HITBOX_WIDTH: i32 = 20;
 pub const HOLD_HITBOX_HEIGHT: i32 = 60;
+pub const HOLD_HITBOX_RADIUS: f32 = HOLD_HITBOX_WIDTH as f32 / 2.0; // hold hitboxes are capsules
 
 pub const DASH_CD: f32 = 0.12;
 pub const BRICK_DATA_BUFFER_SIZE: usize = 4;
@@ -29,7 +38,7 @@ pub trait Object {
 }
 
 // storing all bounds rather than pos+size because more comparisons between objects are possible than updates of positions
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub struct ObjectBounds { 
 	pub left_x: f32,
 	pub right_x: f32,
@@ -38,7 +47,7 @@ pub struct ObjectBounds {
 }
 
 pub struct HitBox {
-	pub bounds: ObjectBounds,
+	pub collider: Collider,
 	pub brick_type: BrickType
 }
 
@@ -50,13 +59,19 @@ pub enum Direction {
 
 #[wasm_bindgen]
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BrickType {
 	Type1,
 	Type2,
-	Type3
+	Type3,
+	Type4,
+	Type5,
+	Type6
 }
 
+const BRICK_TYPES: [BrickType; MAX_BRICK_TYPES] = [BrickType::Type1, BrickType::Type2, BrickType::Type3, 
+	BrickType::Type4, BrickType::Type5, BrickType::Type6];
+
 // checks if two object bounds intersect
 pub fn intersect(obj1: &ObjectBounds, obj2: &ObjectBounds) -> bool {
 	if obj1.top_y > obj2.bottom_y
@@ -68,12 +83,24 @@ pub fn intersect(obj1: &ObjectBounds, obj2: &ObjectBounds) -> bool {
 	return true;
 }
 
+impl Object for HitBox {
+	fn bounds(&self) -> ObjectBounds {
+		return self.collider.bounds;
+	}
+}
+
 impl BrickType {
 	pub fn to_input(bt: BrickType) -> crate::Input {
-		return match bt {
-			BrickType::Type1 => crate::Input::Slash1,
-			BrickType::Type2 => crate::Input::Slash2,
-			BrickType::Type3 => crate::Input::Slash3
-		};
+		return crate::Input::slash(bt);
+	}
+	
+	// the brick type at the index (0 for Type1), None past the last brick type
+	pub fn from_index(idx: usize) -> Option<BrickType> {
+		return BRICK_TYPES.get(idx).copied();
+	}
+	
+	// the brick type at the index, wrapping around the number of brick types used
+	pub fn from_index_wrapped(idx: usize, num_brick_types: u8) -> BrickType {
+		return BRICK_TYPES[idx % (num_brick_types as usize).clamp(1, MAX_BRICK_TYPES)];
 	}
 }
@@ -0,0 +1,119 @@
+
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use crate::Graphic;
+use crate::GraphicFlags;
+use crate::PositionedGraphic;
+use crate::resources::GraphicGroup;
+use crate::game::TargetInfo;
+use crate::objects::Direction;
+use crate::objects::PLAYER_WIDTH;
+use crate::objects::BRICK_HEIGHT;
+use crate::config::GameConfig;
+
+use crate::GAME_HEIGHT;
+use crate::F32_ZERO;
+
+pub const PATH_TILE_WIDTH: i32 = 16;
+pub const PATH_TILE_HEIGHT: i32 = 6;
+pub const PATH_TILE_GAP: i32 = 4;
+pub const PATH_MARKER_HEIGHT: i32 = 12;
+pub const HIT_DIRECTION_SIZE: i32 = 30;
+
+// how the player is expected to move to a target, from standing still to dashing
+#[wasm_bindgen]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Movement {
+	Stand,
+	Walk,
+	Run,
+	Sprint,
+	Boost, // too far to sprint, the player boosts the remaining distance when the target becomes hittable
+	Dash
+}
+
+// the movement to a target, given where the player is after the previous target and when the previous target has passed.
+	// uses the same speed thresholds as Player::move_player, assuming the player moves as soon as the previous target passes
+pub fn movement(start_x: f32, start_time: f32, target: &TargetInfo, config: &GameConfig) -> Movement {
+	if target.dash_to_target {
+		return Movement::Dash;
+	}
+
+	let distance = (target.dest_x - start_x).abs();
+	let time = target.hittable_time - start_time;
+	if distance < F32_ZERO {
+		return Movement::Stand;
+	}
+	else if distance > time * config.sprint_speed {
+		return Movement::Boost;
+	}
+	else if distance > time * config.run_speed {
+		return Movement::Sprint;
+	}
+	else if distance > time * config.walk_speed {
+		return Movement::Run;
+	}
+	return Movement::Walk;
+}
+
+// for each target from first_idx that appears on screen: a dotted line from where the player starts to the target's dest_x, colored by the movement,
+	// a marker at dest_x with an arrow in the hit direction, and a marker at post_hit_x below the target
+pub fn path_graphics(targets: &VecDeque<TargetInfo>, first_idx: usize, scrolled_y: f32, config: &GameConfig) -> Vec<PositionedGraphic> {
+	let mut graphics = Vec::new();
+
+	for idx in first_idx..targets.len() {
+		let target = &targets[idx];
+		let top_y = target.appearance_y - scrolled_y;
+		if top_y > GAME_HEIGHT {
+			break;
+		}
+		if target.end_y - scrolled_y < 0.0 {
+			continue;
+		}
+
+		let (start_x, start_time) = match idx {
+			0 => (0.0, 0.0),
+			_ => (targets[idx - 1].post_hit_x, targets[idx - 1].passed_time)
+		};
+		let middle_y = top_y + BRICK_HEIGHT as f32 / 2.0;
+
+		// dotted line from the start to the destination
+		let tile_group = match movement(start_x, start_time, target, config) {
+			Movement::Stand => None,
+			Movement::Walk => Some(GraphicGroup::PathWalk),
+			Movement::Run => Some(GraphicGroup::PathRun),
+			Movement::Sprint => Some(GraphicGroup::PathSprint),
+			Movement::Boost => Some(GraphicGroup::PathBoost),
+			Movement::Dash => Some(GraphicGroup::PathDash)
+		};
+		if let Some(g) = tile_group {
+			let left_x = start_x.min(target.dest_x) + PLAYER_WIDTH as f32 / 2.0;
+			let right_x = start_x.max(target.dest_x) + PLAYER_WIDTH as f32 / 2.0;
+			let tile_y = middle_y - PATH_TILE_HEIGHT as f32 / 2.0;
+			let mut tile_x = left_x;
+			while tile_x + (PATH_TILE_WIDTH as f32) <= right_x {
+				graphics.push(PositionedGraphic::new(Graphic { g, frame: 0, flags: 0, arg: 0 }, tile_x, tile_y));
+				tile_x += (PATH_TILE_WIDTH + PATH_TILE_GAP) as f32;
+			}
+		}
+
+		// destination and hit direction
+		let marker_y = middle_y - PATH_MARKER_HEIGHT as f32 / 2.0;
+		graphics.push(PositionedGraphic::new(Graphic { g: GraphicGroup::PathDestination, frame: 0, flags: 0, arg: 0 }, target.dest_x, marker_y));
+		let flags = match target.hit_dir {
+			Direction::Left => GraphicFlags::HorizontalFlip as u8,
+			Direction::Right => 0
+		};
+		let arrow_x = target.dest_x + (PLAYER_WIDTH - HIT_DIRECTION_SIZE) as f32 / 2.0;
+		let arrow_y = marker_y - HIT_DIRECTION_SIZE as f32;
+		graphics.push(PositionedGraphic::new(Graphic { g: GraphicGroup::PathHitDirection, frame: 0, flags, arg: 0 }, arrow_x, arrow_y));
+
+		// where the player ends up after hitting the target
+		let post_hit_y = target.end_y - scrolled_y;
+		graphics.push(PositionedGraphic::new(Graphic { g: GraphicGroup::PathPostHit, frame: 0, flags: 0, arg: 0 }, target.post_hit_x, post_hit_y));
+	}
+
+	return graphics;
+}
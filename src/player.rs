@@ -6,9 +6,14 @@ use std::collections::btree_set::BTreeSet;
 use crate::log;
 use crate::frame_number;
 use crate::Input;
+use crate::NUM_INPUTS;
 
 use crate::PositionedGraphic;
 use crate::resources::GraphicGroup;
+use crate::resources::SLASHING_GRAPHICS;
+use crate::resources::DASH_GRAPHICS;
+use crate::resources::HOLDING_GRAPHICS;
+use crate::resources::HOLD_GRAPHICS;
 use crate::Graphic;
 use crate::GraphicFlags;
 use crate::LingeringGraphic;
@@ -18,8 +23,11 @@ use crate::objects::Direction;
 use crate::objects::ObjectBounds;
 use crate::objects::BrickType;
 use crate::objects::HitBox;
+use crate::collider::Collider;
 
 use crate::game::TargetInfo;
+use crate::scheduler::Event;
+use crate::scheduler::EventKind;
 
 use crate::GROUND_POS;
 use crate::LEFT_BOUNDARY;
@@ -27,28 +35,22 @@ use crate::RIGHT_BOUNDARY;
 use crate::TIME_ZERO_BRICK_POS;
 use crate::F32_ZERO;
 use crate::GameData;
+use crate::config::GameConfig;
 use crate::objects::PLAYER_WIDTH;
 use crate::objects::PLAYER_HEIGHT;
-use crate::objects::BRICK_WIDTH;
 use crate::objects::MIN_DASH_WIDTH;
 use crate::objects::SLASH_WIDTH;
 use crate::objects::HOLD_HITBOX_WIDTH;
 use crate::objects::HOLD_HITBOX_HEIGHT;
+use crate::objects::HOLD_HITBOX_RADIUS;
+use crate::objects::SLASH_CORNER_CUT;
+use crate::playfield::Playfield;
 
-// delays dash/slash by a tiny amount so they can be pressed at the same time. starts animation during delay
-const MIN_PRE_SLASH_TIME: f32 = 0.015;
-const MAX_PRE_SLASH_TIME: f32 = 0.06;
-pub const PRE_HOLD_TIME: f32 = 0.24;
-// so slash animation can finish
-pub const POST_SLASH_TIME: f32 = 0.08;
-const STUNNED_TIME: f32 = 0.14;
 const DASH_LINGER_TIME: f32 = 0.3; // how long the dash graphic lingers
 const BOOST_LINGER_TIME: f32 = 0.3;
 const BOOST_PRELINGER_TIME: f32 = 1.2;
 
-pub const SPRINT_SPEED: f32 = 580.0; // in pixels per second
-pub const RUN_SPEED: f32 = 400.0; // in pixels per second
-pub const WALK_SPEED: f32 = 220.0; // in pixels per second
+const MIN_MOVE_PART_TIME: f32 = 0.0001; // so moving in parts always progresses
 
 pub const BOOST_GRAPHIC_OFFSET: f32 = PLAYER_WIDTH as f32 / 10.0; // how close the boost graphics are to one another
 
@@ -56,9 +58,10 @@ pub struct Player {
 	state: TaggedState,
 	bounds: ObjectBounds,
 	early_arrival_margin: f32, // time in seconds to arrive early to a target
+	config: GameConfig,
+	playfield: Playfield, // the song's lanes, which set the width of slashes and where holds are
 	
-	// !!! this way of getting the size of the enum is flimsy. Would prefer enum_variant_count!(Input)
-	inputs_down: [bool; Input::Slash3 as usize + 1], 
+	inputs_down: [bool; NUM_INPUTS], 
 	face_dir: Direction,
 	hit_dir: Direction,
 	
@@ -110,7 +113,7 @@ impl Object for Player {
 
 impl Player {
 	
-	pub fn new(x: f32, early_arrival_margin: f32) -> Player {
+	pub fn new(x: f32, early_arrival_margin: f32, config: GameConfig, playfield: Playfield) -> Player {
 		Player {
 			state: TaggedState { time: 0.0, state: PlayerState::Standing },
 			bounds: ObjectBounds {
@@ -120,8 +123,10 @@ impl Player {
 				bottom_y: GROUND_POS as f32
 			},
 			early_arrival_margin,
+			config,
+			playfield,
 			
-			inputs_down: [false; Input::Slash3 as usize + 1],
+			inputs_down: [false; NUM_INPUTS],
 			face_dir: Direction::Right,
 			hit_dir: Direction::Right,
 			
@@ -160,49 +165,29 @@ impl Player {
 		return false;
 	}
 	
-	// checks if an action (Slash or SlashDash) is performed during the tick. If so, returns the time of the action
-	pub fn check_action(&self, time_running: f32, end_tick_time: f32) -> Option<f32> {
+	// the player's next event from its state: the action once the pre action time passes, the start of a hold after a slash, or the end of a hold
+		// actions can't happen before the current time
+	pub fn next_event(&self, time_running: f32) -> Option<Event> {
 		let state_time = self.state.time;
 		
-		// if the MIN_PRE_SLASH_TIME hasn't passed, return None
-		if end_tick_time - state_time < MIN_PRE_SLASH_TIME {
-			return None;
-		}
+		// on a group of notes wait for the max pre slash time before slashing or dashing (to allow SlashDash)
+		let pre_action_time = match &self.target {
+			Some(ti) if ti.brick_group.len() > 1 => self.config.max_pre_slash_time,
+			_ => self.config.min_pre_slash_time
+		};
 		
 		match self.state.state {
-			PlayerState::PreSlash => {
-				// check if the target is a group. If so, only slash if the MAX_PRE_SLASH_TIME would be completed by the end of the tick
-				match &self.target {
-					None => {
-						return Some(state_time + MIN_PRE_SLASH_TIME);
-					},
-					Some(ti) => {
-						if ti.brick_group.len() <= 1 {
-							return Some(state_time + MIN_PRE_SLASH_TIME);
-						} else {
-							if end_tick_time - state_time > MAX_PRE_SLASH_TIME {
-								let min_action_time = state_time + MIN_PRE_SLASH_TIME;
-				
-								if min_action_time < time_running {
-									return Some(time_running);
-								} else {
-									return Some(min_action_time);
-								}
-							}
-							
-							return None;
-						}
-					}
-				}
+			PlayerState::PreSlash | PlayerState::PreDash => {
+				return Some(Event { time: time_running.max(state_time + pre_action_time), kind: EventKind::Action });
 			},
 			PlayerState::PreSlashDash => {
-				let min_action_time = state_time + MIN_PRE_SLASH_TIME;
-				
-				if min_action_time < time_running {
-					return Some(time_running);
-				} else {
-					return Some(min_action_time);
-				}
+				return Some(Event { time: time_running.max(state_time + self.config.min_pre_slash_time), kind: EventKind::Action });
+			},
+			PlayerState::PostSlash if !self.dont_hold => {
+				return Some(Event { time: time_running.max(state_time + self.config.pre_hold_time), kind: EventKind::HoldStart });
+			},
+			PlayerState::PostHold => {
+				return Some(Event { time: state_time, kind: EventKind::HoldEnd });
 			},
 			_ => {
 				return None;
@@ -219,11 +204,18 @@ impl Player {
 		self.lingering_graphics = new_set;
 	}
 	
-	// perform a slash or slash dash if the state is correct (in preslash or preslashdash), returns hitbox and updates state
-		// only PreSlash and PreSlashDash are valid states for this function, and the state is updated to PostSlash
-	pub fn action_tick (&mut self, game_data: &GameData) -> HitBox {
+	// perform a slash, slash dash, or dash if the state is correct (in preslash, preslashdash, or predash), returns the hitbox of a slash and updates state
+		// only PreSlash, PreSlashDash, and PreDash are valid states for this function, and the state is updated to Slash, SlashDash, or Dash
+	pub fn action_tick (&mut self, game_data: &GameData) -> Option<HitBox> {
 		let hitbox;
 		let time_running = game_data.time_running;
+		let slash_width = self.playfield.scaled(SLASH_WIDTH);
+		
+		// dashes have no hitbox
+		if let PlayerState::PreDash = self.state.state {
+			self.dash_min_distance(time_running);
+			return None;
+		}
 
 		// either slash or slashdash, otherwise panic
 		match self.state.state {
@@ -250,16 +242,16 @@ impl Player {
 				let brick_type = if let Some(bt) = self.hit_type {bt} else {panic!()};
 				let hitbox_x = match self.hit_dir {
 					Direction::Right => self.bounds.right_x,
-					Direction::Left => self.bounds.left_x - SLASH_WIDTH as f32
+					Direction::Left => self.bounds.left_x - slash_width
 				};
 				let hitbox_bounds = ObjectBounds { 
 					left_x: hitbox_x, 
-					right_x: hitbox_x + SLASH_WIDTH as f32, 
+					right_x: hitbox_x + slash_width, 
 					top_y: self.bounds.top_y,
 					bottom_y: self.bounds.bottom_y
 				};
 				
-				hitbox = HitBox { bounds: hitbox_bounds, brick_type };
+				hitbox = HitBox { collider: slash_collider(hitbox_bounds, self.hit_dir), brick_type };
 					
 				self.face_dir = self.hit_dir;
 				self.state = TaggedState { state: PlayerState::Slash, time: time_running };
@@ -321,22 +313,18 @@ impl Player {
 					self.bounds.left_x = dest_x;
 					self.bounds.right_x = self.bounds.left_x + PLAYER_WIDTH as f32;
 					hitbox_left_x = dash_left_x;
-					hitbox_right_x = self.bounds.right_x + SLASH_WIDTH as f32;
+					hitbox_right_x = self.bounds.right_x + slash_width;
 				} else {
 					dash_right_x = self.bounds.right_x;
 					self.bounds.left_x = dest_x;
 					self.bounds.right_x = self.bounds.left_x + PLAYER_WIDTH as f32;
 					dash_left_x = self.bounds.right_x;
-					hitbox_left_x = self.bounds.left_x - SLASH_WIDTH as f32;
+					hitbox_left_x = self.bounds.left_x - slash_width;
 					hitbox_right_x = dash_right_x;
 				}
 				
 				// get graphic groups based on brick type
-				let dash_graphic_group = match brick_type {
-					BrickType::Type1 => { GraphicGroup::Dash1 },
-					BrickType::Type2 => { GraphicGroup::Dash2 },
-					BrickType::Type3 => { GraphicGroup::Dash3 }
-				};
+				let dash_graphic_group = DASH_GRAPHICS[brick_type as usize];
 				
 				// push dash to lingering graphics
 				let dash_graphic = Graphic { g: dash_graphic_group, frame: 0, flags: 0, arg: 0 };
@@ -359,12 +347,12 @@ impl Player {
 					bottom_y: self.bounds.bottom_y
 				};
 				
-				hitbox = HitBox { bounds: hitbox_bounds, brick_type };
+				hitbox = HitBox { collider: slash_collider(hitbox_bounds, self.hit_dir), brick_type };
 				
 				self.face_dir = self.hit_dir;
 				self.state = TaggedState { state: PlayerState::SlashDash, time: time_running };
 			},
-			_ => panic!() // action tick should only occur when player is in preslash or preslashdash
+			_ => panic!() // action tick should only occur when player is in preslash, preslashdash, or predash
 		}
 		
 		// if the target was hit (presumably, based on time_running and the player's x) , update in_post_hit_pos to true
@@ -374,7 +362,16 @@ impl Player {
 			}
 		}
 	
-		return hitbox;
+		return Some(hitbox);
+	}
+	
+	// starts holding once the pre hold time has passed after a slash, if the slash key is still down
+	pub fn start_hold(&mut self) {
+		if let PlayerState::PostSlash = self.state.state {
+			if !self.dont_hold {
+				self.state = TaggedState { state: PlayerState::Hold, time: self.state.time + self.config.pre_hold_time };
+			}
+		}
 	}
 	
 	pub fn update_target(&mut self, target: Option<TargetInfo>) {
@@ -418,11 +415,9 @@ impl Player {
 		if self.inputs_down[input as usize] == false {
 			self.inputs_down[input as usize] = true;
 			
-			match input {
-				Input::Dash => { self.input_dash(input_time); },
-				Input::Slash1 => { self.input_slash(BrickType::Type1, input_time); },
-				Input::Slash2 => { self.input_slash(BrickType::Type2, input_time); },
-				Input::Slash3 => { self.input_slash(BrickType::Type3, input_time); }
+			match input.brick_type() {
+				None => { self.input_dash(input_time); },
+				Some(brick_type) => { self.input_slash(brick_type, input_time); }
 			}
 		}
 	}
@@ -433,20 +428,7 @@ impl Player {
 		// check if the input ended is the same as the hit type, if so stop that input
 		let stop_input;
 		if let Some(hit_type) = self.hit_type {
-			match (input, hit_type) {
-				(Input::Slash1, BrickType::Type1) => {
-					stop_input = true;
-				},
-				(Input::Slash2, BrickType::Type2) => {
-					stop_input = true;
-				},
-				(Input::Slash3, BrickType::Type3) => {
-					stop_input = true;
-				}
-				_ => {
-					stop_input = false;
-				}
-			}
+			stop_input = input.brick_type() == Some(hit_type);
 		}
 		else {
 			stop_input = false;
@@ -537,12 +519,12 @@ impl Player {
 							bottom_y: GROUND_POS + HOLD_HITBOX_HEIGHT as f32
 						};
 						
-						hitboxes.push(HitBox { bounds, brick_type });
+						hitboxes.push(HitBox { collider: Collider::rounded_rect(bounds, HOLD_HITBOX_RADIUS), brick_type });
 					}
 				} else {
 					let hitbox_x = match self.hit_dir {
-						Direction::Left => {self.bounds.left_x - BRICK_WIDTH as f32 / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0},
-						Direction::Right => {self.bounds.right_x + BRICK_WIDTH as f32 / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0}
+						Direction::Left => {self.bounds.left_x - self.playfield.lane_width() / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0},
+						Direction::Right => {self.bounds.right_x + self.playfield.lane_width() / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0}
 					};
 					
 					let bounds = ObjectBounds { 
@@ -552,7 +534,7 @@ impl Player {
 						bottom_y: GROUND_POS + HOLD_HITBOX_HEIGHT as f32
 					};
 					
-					hitboxes.push(HitBox { bounds, brick_type });
+					hitboxes.push(HitBox { collider: Collider::rounded_rect(bounds, HOLD_HITBOX_RADIUS), brick_type });
 				}
 			},
 			_ => {}
@@ -596,6 +578,18 @@ impl Player {
 		}
 	}
 	
+	// dash player the minimum distance in the direction they face
+	fn dash_min_distance(&mut self, dash_time: f32) {
+		match self.face_dir {
+			Direction::Right => {
+				self.dash_player(self.bounds.left_x + MIN_DASH_WIDTH as f32, dash_time);
+			},
+			Direction::Left => {
+				self.dash_player(self.bounds.left_x - MIN_DASH_WIDTH as f32, dash_time);
+			}
+		}
+	}
+	
 	// dash the player to the destination x, pushing graphics to lingering_graphics and updating state
 	fn dash_player(&mut self, dest_x: f32, dash_time: f32) {
 		let dash_graphic_group = GraphicGroup::Dash0;
@@ -634,9 +628,32 @@ impl Player {
 		self.state = TaggedState { state: PlayerState::Dash, time: dash_time };
 	}
 	
-	// runs to the target, may boost
+	// runs to the target, may boost. The speed is chosen again whenever it would change during the time moved, and the boost happens
+		// as soon as the target is close enough, so moving the whole time at once or in parts ends in the same place
 	fn move_player(&mut self, seconds_passed: f32, start_t: f32) -> PlayerState {
 		let mut state;
+		let mut t = start_t;
+		let end_t = start_t + seconds_passed;
+		loop {
+			let (part_state, part_time) = self.move_player_part(end_t - t, t);
+			state = part_state;
+			t += part_time;
+			
+			if let PlayerState::Standing = state {
+				break;
+			}
+			if t >= end_t {
+				break;
+			}
+		}
+		
+		return state;
+	}
+	
+	// moves until the speed would change or the seconds pass. Returns the state and the time moved
+	fn move_player_part(&mut self, seconds_passed: f32, start_t: f32) -> (PlayerState, f32) {
+		let mut state;
+		let mut part_time = seconds_passed;
 
 		if self.in_post_hit_pos {
 			state = PlayerState::Standing;
@@ -660,6 +677,9 @@ impl Player {
 						target_dir = Direction::Right;
 					}
 					let move_speed;
+					let sprint_speed = self.config.sprint_speed;
+					let run_speed = self.config.run_speed;
+					let walk_speed = self.config.walk_speed;
 
 					// either boost, sprint, run, or walk
 					if !ti.dash_to_target && ti.hittable_time - self.early_arrival_margin <= start_t {
@@ -670,18 +690,27 @@ impl Player {
 						move_speed = 0.0;
 						state = PlayerState::Standing;
 					}
-					else if distance_to_target > time_to_target * RUN_SPEED {
-						move_speed = SPRINT_SPEED;
+					else if distance_to_target > time_to_target * run_speed {
+						move_speed = sprint_speed;
 						state = PlayerState::Sprinting; 
+						// the distance shrinks faster than the time to the target allows running, until running would be fast enough
+						part_time = part_time.min((distance_to_target - time_to_target * run_speed) / (sprint_speed - run_speed));
 					}
-					else if distance_to_target > time_to_target * WALK_SPEED {
-						move_speed = RUN_SPEED;
+					else if distance_to_target > time_to_target * walk_speed {
+						move_speed = run_speed;
 						state = PlayerState::Running;
+						part_time = part_time.min((distance_to_target - time_to_target * walk_speed) / (run_speed - walk_speed));
 					}
 					else {
-						move_speed = WALK_SPEED;
+						move_speed = walk_speed;
 						state = PlayerState::Walking;
 					}
+					
+					// stop when the target is close enough to boost
+					if !ti.dash_to_target && move_speed > 0.0 {
+						part_time = part_time.min(ti.hittable_time - self.early_arrival_margin - start_t);
+					}
+					part_time = part_time.max(MIN_MOVE_PART_TIME).min(seconds_passed);
 
 					// add boost graphics if the player is sprinting
 					if let PlayerState::Sprinting = state {
@@ -692,13 +721,13 @@ impl Player {
 							Direction::Left => {
 								graphic = Graphic{ g: GraphicGroup::Running, frame: frame_number(start_t - self.state.time), 
 									flags: GraphicFlags::HorizontalFlip as u8, arg: 0 };
-								graphic_start_x = self.bounds.left_x - move_speed * seconds_passed;
+								graphic_start_x = self.bounds.left_x - move_speed * part_time;
 								graphic_end_x = self.bounds.left_x;
 							},
 							Direction::Right => {
 								graphic = Graphic{ g: GraphicGroup::Running, frame: frame_number(start_t - self.state.time), flags: 0, arg: 0 };
 								graphic_start_x = self.bounds.left_x;
-								graphic_end_x = self.bounds.left_x + move_speed * seconds_passed;
+								graphic_end_x = self.bounds.left_x + move_speed * part_time;
 							}
 						}
 						
@@ -709,7 +738,7 @@ impl Player {
 					let mut end_x = self.bounds.left_x;
 					match target_dir {
 						Direction::Left => {
-							end_x -= move_speed * seconds_passed;
+							end_x -= move_speed * part_time;
 							if end_x <= ti.dest_x {
 								end_x = ti.dest_x;
 								self.face_dir = ti.hit_dir;
@@ -720,7 +749,7 @@ impl Player {
 							}
 						},
 						Direction::Right => {
-							end_x += move_speed * seconds_passed;
+							end_x += move_speed * part_time;
 							if end_x >= ti.dest_x {
 								end_x = ti.dest_x;
 								self.face_dir = ti.hit_dir;
@@ -742,7 +771,7 @@ impl Player {
 		if self.bounds.left_x > RIGHT_BOUNDARY { self.bounds.left_x = RIGHT_BOUNDARY - PLAYER_WIDTH as f32 };
 		self.bounds.right_x = self.bounds.left_x + PLAYER_WIDTH as f32;
 
-		return state;
+		return (state, part_time);
 	}
 	
 	// updates the state and performs any other consequent updates to player position or lingering graphics
@@ -771,38 +800,21 @@ impl Player {
 				return;
 			},
 			PlayerState::PreSlash => {
-				if time_running - t > MAX_PRE_SLASH_TIME {
+				if time_running - t > self.config.max_pre_slash_time {
 					panic!(); // pre slashes turning into slashes should be handled in action_tick
 				}
 				return;
 			},
 			PlayerState::PreSlashDash => {
-				if time_running - t > MAX_PRE_SLASH_TIME {
+				if time_running - t > self.config.max_pre_slash_time {
 					panic!(); // pre slash dash turning into slash dash should be handled in action_tick
 				}
 
 				return;
 			},
 			PlayerState::PreDash => {
-				if time_running - t <= MIN_PRE_SLASH_TIME {
-					return;
-				}
-				
-				// on a group of notes wait for MAX_PRE_SLASH_TIME before dashing (to allow SlashDash)
-				if let Some(ti) = &self.target {
-					if ti.brick_group.len() > 1 && time_running - t <= MAX_PRE_SLASH_TIME {
-						return;
-					}
-				}
-				
-				// dash player the minimum distance
-				match self.face_dir {
-					Direction::Right => {
-						self.dash_player(self.bounds.left_x + MIN_DASH_WIDTH as f32, time_running);
-					},
-					Direction::Left => {
-						self.dash_player(self.bounds.left_x - MIN_DASH_WIDTH as f32, time_running);
-					}
+				if time_running - t > self.config.max_pre_slash_time {
+					panic!(); // pre dash turning into dash should be handled in action_tick
 				}
 				
 				return;
@@ -817,7 +829,9 @@ impl Player {
 				return;
 			},
 			PlayerState::Dash => {	
-				self.state = TaggedState { state: PlayerState::Standing, time: time_running };
+				// the dash is instant, the player moves on for the rest of the time
+				let new_state = self.move_player(seconds_passed, time_running);
+				self.state = TaggedState { state: new_state, time: time_running };
 				return;
 			},
 			PlayerState::SlashDash => {
@@ -832,13 +846,13 @@ impl Player {
 			PlayerState::PostSlash => {
 				// check whether to manually enter hold state based on whether the hold key was lifted and if enough time has passed since the slash
 				let time_difference = time_running + seconds_passed - t;
-				if time_difference > POST_SLASH_TIME {
-					if !self.dont_hold  && time_difference > PRE_HOLD_TIME {
-						self.state = TaggedState {state:PlayerState::Hold, time: t + PRE_HOLD_TIME};
+				if time_difference > self.config.post_slash_time {
+					if !self.dont_hold  && time_difference > self.config.pre_hold_time {
+						self.state = TaggedState {state:PlayerState::Hold, time: t + self.config.pre_hold_time};
 						return;
 					} else if self.dont_hold {
-						let get_up_time = t + POST_SLASH_TIME;
-						let move_time = time_difference - POST_SLASH_TIME;
+						let get_up_time = t + self.config.post_slash_time;
+						let move_time = time_difference - self.config.post_slash_time;
 						let new_state = self.move_player(move_time, get_up_time);
 						self.state = TaggedState { state: new_state, time: time_running };
 						self.hit_type = None;				
@@ -868,9 +882,12 @@ impl Player {
 			},
 			PlayerState::Stunned => {
 				let time_difference = time_running + seconds_passed - t;
-				if time_difference > STUNNED_TIME {
-					self.state = TaggedState { state: PlayerState::Standing, time: t + STUNNED_TIME };
-					// TODO should possibly move once the stun time is over?
+				if time_difference > self.config.stunned_time {
+					// move for the time after the stun ends
+					let recover_time = t + self.config.stunned_time;
+					let move_time = time_running + seconds_passed - recover_time.max(time_running);
+					let new_state = self.move_player(move_time, recover_time.max(time_running));
+					self.state = TaggedState { state: new_state, time: recover_time };
 				}
 				return;
 			}
@@ -927,11 +944,7 @@ impl Player {
 			},
 			PlayerState::Slash | PlayerState::SlashDash => {
 				let brick_type = if let Some(bt) = self.hit_type { bt } else { panic!() };
-				let graphic_group = match brick_type {
-					BrickType::Type1 => GraphicGroup::Slashing1,
-					BrickType::Type2 => GraphicGroup::Slashing2,
-					BrickType::Type3 => GraphicGroup::Slashing3
-				};
+				let graphic_group = SLASHING_GRAPHICS[brick_type as usize];
 				let frame = frame_number(time_running - t);
 				
 				let graphic = Graphic { g: graphic_group, frame, flags, arg };
@@ -939,18 +952,14 @@ impl Player {
 			},
 			PlayerState::PostSlash => {
 				let brick_type = if let Some(bt) = self.hit_type { bt } else { panic!() };
-				let graphic_group = match brick_type {
-					BrickType::Type1 => GraphicGroup::Slashing1,
-					BrickType::Type2 => GraphicGroup::Slashing2,
-					BrickType::Type3 => GraphicGroup::Slashing3
-				};
+				let graphic_group = SLASHING_GRAPHICS[brick_type as usize];
 				
-				if time_running - t < POST_SLASH_TIME {
+				if time_running - t < self.config.post_slash_time {
 					let frame = frame_number(time_running - t);
 					let graphic = Graphic { g: graphic_group, frame, flags, arg };
 					positioned_graphics.push(PositionedGraphic::new(graphic, self.bounds.left_x, self.bounds.top_y));
 				} else {
-					let frame = frame_number(POST_SLASH_TIME);
+					let frame = frame_number(self.config.post_slash_time);
 					let graphic = Graphic { g: graphic_group, frame, flags, arg };
 					positioned_graphics.push(PositionedGraphic::new(graphic, self.bounds.left_x, self.bounds.top_y));
 				}
@@ -959,21 +968,13 @@ impl Player {
 				let brick_type = if let Some(bt) = self.hit_type { bt } else { panic!() };
 				
 				// push player graphic
-				let graphic_group = match brick_type {
-					BrickType::Type1 => GraphicGroup::Holding1,
-					BrickType::Type2 => GraphicGroup::Holding2,
-					BrickType::Type3 => GraphicGroup::Holding3
-				};
+				let graphic_group = HOLDING_GRAPHICS[brick_type as usize];
 				
 				let graphic = Graphic { g: graphic_group, frame: 0, flags, arg };
 				positioned_graphics.push(PositionedGraphic::new(graphic, self.bounds.left_x, self.bounds.top_y));
 				
 				// push hold hitbox graphics
-				let hitbox_graphic_group = match brick_type {
-					BrickType::Type1 => GraphicGroup::Hold1,
-					BrickType::Type2 => GraphicGroup::Hold2,
-					BrickType::Type3 => GraphicGroup::Hold3
-				};
+				let hitbox_graphic_group = HOLD_GRAPHICS[brick_type as usize];
 				
 				if self.hold_positions.len() > 0 {
 					for hp in &self.hold_positions {
@@ -982,8 +983,8 @@ impl Player {
 					}
 				} else {
 					let hitbox_graphic_x = match self.hit_dir {
-						Direction::Left => {self.bounds.left_x - BRICK_WIDTH as f32 / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0},
-						Direction::Right => {self.bounds.right_x + BRICK_WIDTH as f32 / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0}
+						Direction::Left => {self.bounds.left_x - self.playfield.lane_width() / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0},
+						Direction::Right => {self.bounds.right_x + self.playfield.lane_width() / 2.0 - HOLD_HITBOX_WIDTH as f32 / 2.0}
 					};
 					let hitbox_graphic = Graphic {g: hitbox_graphic_group, frame: 0, flags: 0, arg: 0};
 					positioned_graphics.push(PositionedGraphic::new(hitbox_graphic, hitbox_graphic_x, self.bounds.bottom_y));
@@ -1019,4 +1020,16 @@ impl Player {
 		}
 		return positioned_graphics;
 	}
-}
\ No newline at end of file
+}
+// a slash's hitbox follows the arc of the slash, so the far corners of its bounds in the hit direction are cut off.
+	// A slash dash's far end is the slash, its near end the dash
+fn slash_collider(bounds: ObjectBounds, hit_dir: Direction) -> Collider {
+	let width = bounds.right_x - bounds.left_x;
+	let height = bounds.bottom_y - bounds.top_y;
+	let cut = SLASH_CORNER_CUT.min(width).min(height / 2.0);
+	let vertices = match hit_dir {
+		Direction::Right => vec![(0.0, 0.0), (width - cut, 0.0), (width, cut), (width, height - cut), (width - cut, height), (0.0, height)],
+		Direction::Left => vec![(cut, 0.0), (width, 0.0), (width, height), (cut, height), (0.0, height - cut), (0.0, cut)]
+	};
+	return Collider::convex_polygon(bounds, vertices);
+}
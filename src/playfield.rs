@@ -0,0 +1,51 @@
+
+use crate::LEFT_BOUNDARY;
+use crate::RIGHT_BOUNDARY;
+use crate::objects::DEFAULT_LANES;
+use crate::objects::DEFAULT_BRICK_WIDTH;
+
+// a song's lanes, splitting the width of the game evenly. Bricks fill their lanes, so with fewer lanes bricks, hold segments, 
+	// and slashes are wider than their sizes in objects.rs (which are for the default number of lanes), and with more lanes narrower
+#[derive(Clone, Copy, Debug)]
+pub struct Playfield {
+	lanes: u8
+}
+
+impl Playfield {
+	pub fn new(lanes: u8) -> Playfield {
+		return Playfield { lanes: lanes.max(1) };
+	}
+	
+	pub fn lanes(&self) -> u8 {
+		return self.lanes;
+	}
+	
+	pub fn lane_width(&self) -> f32 {
+		return (RIGHT_BOUNDARY - LEFT_BOUNDARY) / self.lanes as f32;
+	}
+	
+	// the left of the lane (x_pos)
+	pub fn lane_x(&self, x_pos: i32) -> f32 {
+		return LEFT_BOUNDARY + x_pos as f32 * self.lane_width();
+	}
+	
+	// the lane that x is in, clamped to the lanes
+	pub fn lane_at(&self, x: f32) -> i32 {
+		return (((x - LEFT_BOUNDARY) / self.lane_width()).floor() as i32).clamp(0, self.lanes as i32 - 1);
+	}
+	
+	pub fn contains_lane(&self, x_pos: i32) -> bool {
+		return x_pos >= 0 && x_pos < self.lanes as i32;
+	}
+	
+	// a width given for the default number of lanes, scaled to these lanes
+	pub fn scaled(&self, default_width: i32) -> f32 {
+		return default_width as f32 * self.lane_width() / DEFAULT_BRICK_WIDTH as f32;
+	}
+}
+
+impl Default for Playfield {
+	fn default() -> Playfield {
+		return Playfield::new(DEFAULT_LANES);
+	}
+}
@@ -0,0 +1,124 @@
+
+use wasm_bindgen::prelude::*;
+
+use crate::PositionedGraphic;
+use crate::RenderingInstructions;
+
+// the layers graphics are drawn in, from back to front. Each graphic group's layer is in resources::GRAPHIC_LAYERS
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Layer {
+	Background,
+	Grid, // the editor's grid lines and lane separators
+	Effects, // effects over the whole game, like the miss effect
+	DashIndicators,
+	Player,
+	Bricks,
+	Path, // the editor's preview of the player's path
+	Selection, // the editor's highlights over selected bricks
+	Hud
+}
+
+pub const NUM_LAYERS: usize = Layer::Hud as usize + 1;
+
+// groups of layers javascript can draw separately, such as onto different canvases or only when they change
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub enum RenderPass {
+	Background,
+	Gameplay,
+	Hud
+}
+
+impl RenderPass {
+	pub fn first_layer(self) -> Layer {
+		return match self {
+			RenderPass::Background => Layer::Background,
+			RenderPass::Gameplay => Layer::Grid,
+			RenderPass::Hud => Layer::Path
+		};
+	}
+
+	pub fn last_layer(self) -> Layer {
+		return match self {
+			RenderPass::Background => Layer::Background,
+			RenderPass::Gameplay => Layer::Bricks,
+			RenderPass::Hud => Layer::Hud
+		};
+	}
+}
+
+// the graphics of a frame, bucketed by layer when the frame is finished so the order they're pushed in only matters within a layer
+pub struct RenderQueue {
+	graphics: Vec<PositionedGraphic>,
+	layer_starts: [usize; NUM_LAYERS + 1] // the index of the first graphic of each layer, then the number of graphics
+}
+
+impl RenderQueue {
+	pub fn with_capacity(capacity: usize) -> RenderQueue {
+		return RenderQueue {
+			graphics: Vec::with_capacity(capacity),
+			layer_starts: [0; NUM_LAYERS + 1]
+		};
+	}
+
+	pub fn clear(&mut self) {
+		self.graphics.clear();
+		self.layer_starts = [0; NUM_LAYERS + 1];
+	}
+
+	pub fn push(&mut self, graphic: PositionedGraphic) {
+		self.graphics.push(graphic);
+	}
+
+	pub fn append(&mut self, graphics: &mut Vec<PositionedGraphic>) {
+		self.graphics.append(graphics);
+	}
+
+	// sorts the graphics by layer, keeping the order they were pushed in within each layer, and finds where each layer starts
+	pub fn finish(&mut self) {
+		self.graphics.sort_by_key(|graphic| graphic.layer);
+
+		let mut idx = 0;
+		for layer in 0..NUM_LAYERS {
+			self.layer_starts[layer] = idx;
+			while idx < self.graphics.len() && self.graphics[idx].layer as usize == layer {
+				idx += 1;
+			}
+		}
+		self.layer_starts[NUM_LAYERS] = self.graphics.len();
+	}
+
+	pub fn graphics_mut(&mut self) -> &mut [PositionedGraphic] {
+		return &mut self.graphics;
+	}
+
+	pub fn instructions(&self) -> RenderingInstructions {
+		return RenderingInstructions {
+			num_graphics: self.graphics.len(),
+			graphics_ptr: self.graphics.as_ptr(),
+			layer_starts: self.layer_starts
+		};
+	}
+}
+
+#[wasm_bindgen]
+impl RenderingInstructions {
+	// the graphics of a layer are from the layer start up to (not including) the layer end
+	pub fn layer_start(&self, layer: Layer) -> usize {
+		return self.layer_starts[layer as usize];
+	}
+
+	pub fn layer_end(&self, layer: Layer) -> usize {
+		return self.layer_starts[layer as usize + 1];
+	}
+
+	pub fn pass_start(&self, pass: RenderPass) -> usize {
+		return self.layer_start(pass.first_layer());
+	}
+
+	pub fn pass_end(&self, pass: RenderPass) -> usize {
+		return self.layer_end(pass.last_layer());
+	}
+}
@@ -6,7 +6,11 @@ use macros::EnumVariantCount;
 use crate::game;
 use crate::player;
 use crate::objects;
+use crate::editor_render;
+use crate::path_preview;
 use crate::Position;
+use crate::render_queue::Layer;
+use crate::objects::MAX_BRICK_TYPES;
 
 #[wasm_bindgen]
 #[repr(u8)]
@@ -22,30 +26,65 @@ pub enum GraphicGroup {
 	Slashing1,
 	Slashing2,
 	Slashing3,
+	Slashing4,
+	Slashing5,
+	Slashing6,
 	Brick1,
 	Brick2,
 	Brick3,
+	Brick4,
+	Brick5,
+	Brick6,
 	Brick1Segment,
 	Brick2Segment,
 	Brick3Segment,
+	Brick4Segment,
+	Brick5Segment,
+	Brick6Segment,
 	Dash0,
 	Dash1,
 	Dash2,
 	Dash3,
+	Dash4,
+	Dash5,
+	Dash6,
 	PreHolding1,
 	PreHolding2,
 	PreHolding3,
+	PreHolding4,
+	PreHolding5,
+	PreHolding6,
 	Holding1,
 	Holding2,
 	Holding3,
+	Holding4,
+	Holding5,
+	Holding6,
 	Hold1,
 	Hold2,
-	Hold3
+	Hold3,
+	Hold4,
+	Hold5,
+	Hold6,
+	GridLine,
+	BeatLine,
+	MeasureLine,
+	LaneSeparator,
+	SelectionHighlight,
+	Playhead,
+	PathWalk,
+	PathRun,
+	PathSprint,
+	PathBoost,
+	PathDash,
+	PathDestination,
+	PathPostHit,
+	PathHitDirection
 }
 
 // !!! more robust way to determine the size of GraphicGroup than the (maybe) last enumeration + 1
 // graphic offsets allow decoration space around where the graphic is positioned
-pub const GRAPHIC_OFFSETS: [Position; GraphicGroup::Hold3 as usize + 1] = [
+pub const GRAPHIC_OFFSETS: [Position; GraphicGroup::PathHitDirection as usize + 1] = [
 	Position {x: 0.0, y: 0.0},// Background
 	Position {x: 0.0, y: 0.0}, // MissEffect
 	Position {x: 0.0, y: 0.0}, // DashIndicator
@@ -56,34 +95,137 @@ pub const GRAPHIC_OFFSETS: [Position; GraphicGroup::Hold3 as usize + 1] = [
 	Position {x: 80.0, y: 55.0},// Slashing1,
 	Position {x: 80.0, y: 55.0},// Slashing2,
 	Position {x: 80.0, y: 55.0},// Slashing3,
+	Position {x: 80.0, y: 55.0},// Slashing4,
+	Position {x: 80.0, y: 55.0},// Slashing5,
+	Position {x: 80.0, y: 55.0},// Slashing6,
 	Position {x: 40.0, y: 40.0},// Brick1,
 	Position {x: 40.0, y: 40.0},// Brick2,
 	Position {x: 40.0, y: 40.0},// Brick3,
+	Position {x: 40.0, y: 40.0},// Brick4,
+	Position {x: 40.0, y: 40.0},// Brick5,
+	Position {x: 40.0, y: 40.0},// Brick6,
 	Position {x: 0.0, y: 0.0},// Brick1Segment,
 	Position {x: 0.0, y: 0.0},// Brick2Segment,
 	Position {x: 0.0, y: 0.0},// Brick3Segment,
+	Position {x: 0.0, y: 0.0},// Brick4Segment,
+	Position {x: 0.0, y: 0.0},// Brick5Segment,
+	Position {x: 0.0, y: 0.0},// Brick6Segment,
 	Position {x: 0.0, y: 0.0},// Dash0,
 	Position {x: 0.0, y: 0.0},// Dash1,
 	Position {x: 0.0, y: 0.0},// Dash2,
 	Position {x: 0.0, y: 0.0},// Dash3,
+	Position {x: 0.0, y: 0.0},// Dash4,
+	Position {x: 0.0, y: 0.0},// Dash5,
+	Position {x: 0.0, y: 0.0},// Dash6,
 	Position {x: 0.0, y: 0.0},// PreHolding1,
 	Position {x: 0.0, y: 0.0},// PreHolding2,
 	Position {x: 0.0, y: 0.0},// PreHolding3,
+	Position {x: 0.0, y: 0.0},// PreHolding4,
+	Position {x: 0.0, y: 0.0},// PreHolding5,
+	Position {x: 0.0, y: 0.0},// PreHolding6,
 	Position {x: 50.0, y: 50.0},// Holding1,
 	Position {x: 50.0, y: 50.0},// Holding2,
 	Position {x: 50.0, y: 50.0},// Holding3,
+	Position {x: 50.0, y: 50.0},// Holding4,
+	Position {x: 50.0, y: 50.0},// Holding5,
+	Position {x: 50.0, y: 50.0},// Holding6,
 	Position {x: 50.0, y: 30.0},// Hold1,
 	Position {x: 50.0, y: 30.0},// Hold2,
-	Position {x: 50.0, y: 30.0},// Hold3
+	Position {x: 50.0, y: 30.0},// Hold3,
+	Position {x: 50.0, y: 30.0},// Hold4,
+	Position {x: 50.0, y: 30.0},// Hold5,
+	Position {x: 50.0, y: 30.0},// Hold6,
+	Position {x: 0.0, y: 0.0},// GridLine,
+	Position {x: 0.0, y: 0.0},// BeatLine,
+	Position {x: 0.0, y: 0.0},// MeasureLine,
+	Position {x: 0.0, y: 0.0},// LaneSeparator,
+	Position {x: 4.0, y: 4.0},// SelectionHighlight,
+	Position {x: 0.0, y: 0.0},// Playhead,
+	Position {x: 0.0, y: 0.0},// PathWalk,
+	Position {x: 0.0, y: 0.0},// PathRun,
+	Position {x: 0.0, y: 0.0},// PathSprint,
+	Position {x: 0.0, y: 0.0},// PathBoost,
+	Position {x: 0.0, y: 0.0},// PathDash,
+	Position {x: 0.0, y: 0.0},// PathDestination,
+	Position {x: 0.0, y: 0.0},// PathPostHit,
+	Position {x: 0.0, y: 0.0},// PathHitDirection
 ];
 
-pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
+// the layer each graphic group is drawn in
+pub const GRAPHIC_LAYERS: [Layer; GraphicGroup::PathHitDirection as usize + 1] = [
+	Layer::Background, // Background
+	Layer::Effects, // MissEffect
+	Layer::DashIndicators, // DashIndicator
+	Layer::Player, // Standing
+	Layer::Player, // Walking
+	Layer::Player, // Running
+	Layer::Player, // Stunned
+	Layer::Player, // Slashing1
+	Layer::Player, // Slashing2
+	Layer::Player, // Slashing3
+	Layer::Player, // Slashing4
+	Layer::Player, // Slashing5
+	Layer::Player, // Slashing6
+	Layer::Bricks, // Brick1
+	Layer::Bricks, // Brick2
+	Layer::Bricks, // Brick3
+	Layer::Bricks, // Brick4
+	Layer::Bricks, // Brick5
+	Layer::Bricks, // Brick6
+	Layer::Bricks, // Brick1Segment
+	Layer::Bricks, // Brick2Segment
+	Layer::Bricks, // Brick3Segment
+	Layer::Bricks, // Brick4Segment
+	Layer::Bricks, // Brick5Segment
+	Layer::Bricks, // Brick6Segment
+	Layer::Player, // Dash0
+	Layer::Player, // Dash1
+	Layer::Player, // Dash2
+	Layer::Player, // Dash3
+	Layer::Player, // Dash4
+	Layer::Player, // Dash5
+	Layer::Player, // Dash6
+	Layer::Player, // PreHolding1
+	Layer::Player, // PreHolding2
+	Layer::Player, // PreHolding3
+	Layer::Player, // PreHolding4
+	Layer::Player, // PreHolding5
+	Layer::Player, // PreHolding6
+	Layer::Player, // Holding1
+	Layer::Player, // Holding2
+	Layer::Player, // Holding3
+	Layer::Player, // Holding4
+	Layer::Player, // Holding5
+	Layer::Player, // Holding6
+	Layer::Player, // Hold1
+	Layer::Player, // Hold2
+	Layer::Player, // Hold3
+	Layer::Player, // Hold4
+	Layer::Player, // Hold5
+	Layer::Player, // Hold6
+	Layer::Grid, // GridLine
+	Layer::Grid, // BeatLine
+	Layer::Grid, // MeasureLine
+	Layer::Grid, // LaneSeparator
+	Layer::Selection, // SelectionHighlight
+	Layer::Hud, // Playhead
+	Layer::Path, // PathWalk
+	Layer::Path, // PathRun
+	Layer::Path, // PathSprint
+	Layer::Path, // PathBoost
+	Layer::Path, // PathDash
+	Layer::Path, // PathDestination
+	Layer::Path, // PathPostHit
+	Layer::Path, // PathHitDirection
+];
+
+pub const GRAPHIC_SIZES: [Position; GraphicGroup::PathHitDirection as usize + 1] = [
 	Position {
-		x: crate::GAME_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Background as usize].x, 
-		y: crate::GAME_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Background as usize].y},// Background,
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Background as usize].x, 
+		y: crate::GAME_HEIGHT + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Background as usize].y},// Background,
 	Position {
-		x: crate::GAME_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MissEffect as usize].x, 
-		y: crate::GAME_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MissEffect as usize].y},// Miss Effect,
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MissEffect as usize].x, 
+		y: crate::GAME_HEIGHT + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MissEffect as usize].y},// Miss Effect,
 	Position {
 		x: game::DASH_INDICATOR_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::DashIndicator as usize].x,
 		y: game::DASH_INDICATOR_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::DashIndicator as usize].y}, // Dash Indicator
@@ -109,14 +251,32 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 		x: objects::SLASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing3 as usize].x, 
 		y: objects::SLASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing3 as usize].y},// Slashing3,
 	Position {
-		x: objects::BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick1 as usize].x, 
+		x: objects::SLASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing4 as usize].x, 
+		y: objects::SLASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing4 as usize].y},// Slashing4,
+	Position {
+		x: objects::SLASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing5 as usize].x, 
+		y: objects::SLASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing5 as usize].y},// Slashing5,
+	Position {
+		x: objects::SLASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing6 as usize].x, 
+		y: objects::SLASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Slashing6 as usize].y},// Slashing6,
+	Position {
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick1 as usize].x, 
 		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick1 as usize].y},// Brick1,
 	Position {
-		x: objects::BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick2 as usize].x, 
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick2 as usize].x, 
 		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick2 as usize].y},// Brick2,
 	Position {
-		x: objects::BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick3 as usize].x, 
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick3 as usize].x, 
 		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick3 as usize].y},// Brick3,
+	Position {
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick4 as usize].x, 
+		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick4 as usize].y},// Brick4,
+	Position {
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick5 as usize].x, 
+		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick5 as usize].y},// Brick5,
+	Position {
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick6 as usize].x, 
+		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick6 as usize].y},// Brick6,
 	Position {
 		x: objects::BRICK_SEGMENT_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick1Segment as usize].x, 
 		y: objects::BRICK_SEGMENT_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick1Segment as usize].y},// Brick1Segment,
@@ -126,6 +286,15 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 	Position {
 		x: objects::BRICK_SEGMENT_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick3Segment as usize].x, 
 		y: objects::BRICK_SEGMENT_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick3Segment as usize].y},// Brick3Segment,
+	Position {
+		x: objects::BRICK_SEGMENT_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick4Segment as usize].x, 
+		y: objects::BRICK_SEGMENT_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick4Segment as usize].y},// Brick4Segment,
+	Position {
+		x: objects::BRICK_SEGMENT_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick5Segment as usize].x, 
+		y: objects::BRICK_SEGMENT_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick5Segment as usize].y},// Brick5Segment,
+	Position {
+		x: objects::BRICK_SEGMENT_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick6Segment as usize].x, 
+		y: objects::BRICK_SEGMENT_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Brick6Segment as usize].y},// Brick6Segment,
 	Position {
 		x: objects::MIN_DASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash0 as usize].x, 
 		y: objects::DASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash0 as usize].y},// Dash0,
@@ -138,6 +307,15 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 	Position {
 		x: objects::MIN_DASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash3 as usize].x, 
 		y: objects::DASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash3 as usize].y},// Dash3,
+	Position {
+		x: objects::MIN_DASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash4 as usize].x, 
+		y: objects::DASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash4 as usize].y},// Dash4,
+	Position {
+		x: objects::MIN_DASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash5 as usize].x, 
+		y: objects::DASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash5 as usize].y},// Dash5,
+	Position {
+		x: objects::MIN_DASH_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash6 as usize].x, 
+		y: objects::DASH_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Dash6 as usize].y},// Dash6,
 	Position {
 		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding1 as usize].x, 
 		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding1 as usize].y},// PreHolding1,
@@ -147,6 +325,15 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 	Position {
 		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding3 as usize].x, 
 		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding3 as usize].y},// PreHolding3,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding4 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding4 as usize].y},// PreHolding4,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding5 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding5 as usize].y},// PreHolding5,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding6 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PreHolding6 as usize].y},// PreHolding6,
 	Position {
 		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding1 as usize].x, 
 		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding1 as usize].y},// Holding1,
@@ -156,6 +343,15 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 	Position {
 		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding3 as usize].x, 
 		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding3 as usize].y},// Holding3,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding4 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding4 as usize].y},// Holding4,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding5 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding5 as usize].y},// Holding5,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding6 as usize].x, 
+		y: objects::PLAYER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Holding6 as usize].y},// Holding6,
 	Position {
 		x: objects::HOLD_HITBOX_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold1 as usize].x, 
 		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold1 as usize].y},// Hold1,
@@ -164,7 +360,58 @@ pub const GRAPHIC_SIZES: [Position; GraphicGroup::Hold3 as usize + 1] = [
 		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold2 as usize].y},// Hold2,
 	Position {
 		x: objects::HOLD_HITBOX_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold3 as usize].x, 
-		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold3 as usize].y},// Hold3
+		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold3 as usize].y},// Hold3,
+	Position {
+		x: objects::HOLD_HITBOX_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold4 as usize].x, 
+		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold4 as usize].y},// Hold4,
+	Position {
+		x: objects::HOLD_HITBOX_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold5 as usize].x, 
+		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold5 as usize].y},// Hold5,
+	Position {
+		x: objects::HOLD_HITBOX_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold6 as usize].x, 
+		y: objects::HOLD_HITBOX_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Hold6 as usize].y},// Hold6,
+	Position {
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::GridLine as usize].x, 
+		y: editor_render::GRID_LINE_THICKNESS as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::GridLine as usize].y},// GridLine,
+	Position {
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::BeatLine as usize].x, 
+		y: editor_render::BEAT_LINE_THICKNESS as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::BeatLine as usize].y},// BeatLine,
+	Position {
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MeasureLine as usize].x, 
+		y: editor_render::MEASURE_LINE_THICKNESS as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::MeasureLine as usize].y},// MeasureLine,
+	Position {
+		x: editor_render::LANE_SEPARATOR_THICKNESS as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::LaneSeparator as usize].x, 
+		y: crate::GAME_HEIGHT + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::LaneSeparator as usize].y},// LaneSeparator,
+	Position {
+		x: objects::DEFAULT_BRICK_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::SelectionHighlight as usize].x, 
+		y: objects::BRICK_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::SelectionHighlight as usize].y},// SelectionHighlight,
+	Position {
+		x: crate::GAME_WIDTH + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Playhead as usize].x, 
+		y: editor_render::PLAYHEAD_THICKNESS as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::Playhead as usize].y},// Playhead,
+	Position {
+		x: path_preview::PATH_TILE_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathWalk as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathWalk as usize].y},// PathWalk,
+	Position {
+		x: path_preview::PATH_TILE_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathRun as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathRun as usize].y},// PathRun,
+	Position {
+		x: path_preview::PATH_TILE_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathSprint as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathSprint as usize].y},// PathSprint,
+	Position {
+		x: path_preview::PATH_TILE_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathBoost as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathBoost as usize].y},// PathBoost,
+	Position {
+		x: path_preview::PATH_TILE_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathDash as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathDash as usize].y},// PathDash,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathDestination as usize].x, 
+		y: path_preview::PATH_MARKER_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathDestination as usize].y},// PathDestination,
+	Position {
+		x: objects::PLAYER_WIDTH as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathPostHit as usize].x, 
+		y: path_preview::PATH_TILE_HEIGHT as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathPostHit as usize].y},// PathPostHit,
+	Position {
+		x: path_preview::HIT_DIRECTION_SIZE as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathHitDirection as usize].x, 
+		y: path_preview::HIT_DIRECTION_SIZE as f32 + 2.0 * GRAPHIC_OFFSETS[GraphicGroup::PathHitDirection as usize].y}// PathHitDirection
 ];
 
 // the maximum amount of the graphic that can appear on screen
@@ -178,17 +425,46 @@ pub fn max_graphics(g: GraphicGroup) -> u32 {
 		GraphicGroup::Walking => 1,
 		GraphicGroup::Running => 1,
 		GraphicGroup::Stunned => 1,
-		GraphicGroup::Slashing1 | GraphicGroup::Slashing2 | GraphicGroup::Slashing3 => 1,
-		GraphicGroup::Brick1 | GraphicGroup::Brick2 | GraphicGroup::Brick3 => 32,
-		GraphicGroup::Brick1Segment | GraphicGroup::Brick2Segment | GraphicGroup::Brick3Segment => 128,
-		GraphicGroup::Dash0 | GraphicGroup::Dash1 | GraphicGroup::Dash2 | GraphicGroup::Dash3 => 10,
+		GraphicGroup::Slashing1 | GraphicGroup::Slashing2 | GraphicGroup::Slashing3
+		| GraphicGroup::Slashing4 | GraphicGroup::Slashing5 | GraphicGroup::Slashing6 => 1,
+		GraphicGroup::Brick1 | GraphicGroup::Brick2 | GraphicGroup::Brick3
+		| GraphicGroup::Brick4 | GraphicGroup::Brick5 | GraphicGroup::Brick6 => 32,
+		GraphicGroup::Brick1Segment | GraphicGroup::Brick2Segment | GraphicGroup::Brick3Segment
+		| GraphicGroup::Brick4Segment | GraphicGroup::Brick5Segment | GraphicGroup::Brick6Segment => 128,
+		GraphicGroup::Dash0 | GraphicGroup::Dash1 | GraphicGroup::Dash2 | GraphicGroup::Dash3
+		| GraphicGroup::Dash4 | GraphicGroup::Dash5 | GraphicGroup::Dash6 => 10,
 		GraphicGroup::PreHolding1 | GraphicGroup::PreHolding2 | GraphicGroup::PreHolding3
-		| GraphicGroup::Holding1 | GraphicGroup::Holding2 | GraphicGroup::Holding3 => 1,
-		GraphicGroup::Hold1 | GraphicGroup::Hold2 | GraphicGroup::Hold3 => 32
-		
+		| GraphicGroup::PreHolding4 | GraphicGroup::PreHolding5 | GraphicGroup::PreHolding6
+		| GraphicGroup::Holding1 | GraphicGroup::Holding2 | GraphicGroup::Holding3
+		| GraphicGroup::Holding4 | GraphicGroup::Holding5 | GraphicGroup::Holding6 => 1,
+		GraphicGroup::Hold1 | GraphicGroup::Hold2 | GraphicGroup::Hold3
+		| GraphicGroup::Hold4 | GraphicGroup::Hold5 | GraphicGroup::Hold6 => 32,
+		GraphicGroup::GridLine => 256,
+		GraphicGroup::BeatLine => 64,
+		GraphicGroup::MeasureLine => 16,
+		GraphicGroup::LaneSeparator => 32,
+		GraphicGroup::SelectionHighlight => 256,
+		GraphicGroup::Playhead => 1,
+		GraphicGroup::PathWalk | GraphicGroup::PathRun | GraphicGroup::PathSprint | GraphicGroup::PathBoost | GraphicGroup::PathDash => 1024,
+		GraphicGroup::PathDestination | GraphicGroup::PathPostHit | GraphicGroup::PathHitDirection => 32
+
 	}
 }
 
+// the graphics of each brick type, indexed by the brick type
+pub const BRICK_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Brick1, GraphicGroup::Brick2, GraphicGroup::Brick3, 
+	GraphicGroup::Brick4, GraphicGroup::Brick5, GraphicGroup::Brick6];
+pub const BRICK_SEGMENT_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Brick1Segment, GraphicGroup::Brick2Segment, GraphicGroup::Brick3Segment, 
+	GraphicGroup::Brick4Segment, GraphicGroup::Brick5Segment, GraphicGroup::Brick6Segment];
+pub const SLASHING_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Slashing1, GraphicGroup::Slashing2, GraphicGroup::Slashing3, 
+	GraphicGroup::Slashing4, GraphicGroup::Slashing5, GraphicGroup::Slashing6];
+pub const DASH_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Dash1, GraphicGroup::Dash2, GraphicGroup::Dash3, 
+	GraphicGroup::Dash4, GraphicGroup::Dash5, GraphicGroup::Dash6];
+pub const HOLDING_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Holding1, GraphicGroup::Holding2, GraphicGroup::Holding3, 
+	GraphicGroup::Holding4, GraphicGroup::Holding5, GraphicGroup::Holding6];
+pub const HOLD_GRAPHICS: [GraphicGroup; MAX_BRICK_TYPES] = [GraphicGroup::Hold1, GraphicGroup::Hold2, GraphicGroup::Hold3, 
+	GraphicGroup::Hold4, GraphicGroup::Hold5, GraphicGroup::Hold6];
+
 // returns the intended size of different graphics
 #[wasm_bindgen]
 pub fn graphic_size(g: GraphicGroup) -> Position {
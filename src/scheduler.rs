@@ -0,0 +1,78 @@
+
+use std::collections::VecDeque;
+
+use crate::Input;
+
+// the events that happen within a tick. Game::tick advances the game to each event in order of time and handles it
+#[derive(Clone, Copy, Debug)]
+pub enum EventKind {
+	Input(Input),
+	Release(Input),
+	TargetAppears, // the next target comes within the window the player runs to targets in
+	Action, // the player slashes, slash dashes, or dashes once the pre action time has passed
+	HoldStart, // the player starts holding after a slash
+	HoldEnd, // the held slash key was released
+	BrickCrossing // a brick of the current target reaches the top of the screen
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Event {
+	pub time: f32,
+	pub kind: EventKind
+}
+
+// holds the inputs and releases that haven't been handled yet, in order of time
+pub struct Scheduler {
+	inputs: VecDeque<Event>
+}
+
+impl Scheduler {
+	pub fn new() -> Scheduler {
+		return Scheduler { inputs: VecDeque::new() };
+	}
+
+	// adds an input or release, after any other inputs at the same time
+	pub fn push_input(&mut self, event: Event) {
+		let idx = self.inputs.iter().position(|e| e.time > event.time).unwrap_or(self.inputs.len());
+		self.inputs.insert(idx, event);
+	}
+
+	pub fn pop_input(&mut self) -> Option<Event> {
+		return self.inputs.pop_front();
+	}
+
+	pub fn inputs(&self) -> &VecDeque<Event> {
+		return &self.inputs;
+	}
+	
+	pub fn clear(&mut self) {
+		self.inputs.clear();
+	}
+
+	// the earliest of the next input and the game's own events that happens by the end time.
+		// events at the same time are ordered by kind, so inputs are handled before the actions they lead to
+	pub fn next_event(&self, game_events: &[Event], end_time: f32) -> Option<Event> {
+		let mut next: Option<Event> = None;
+		for event in self.inputs.front().iter().copied().chain(game_events.iter()) {
+			if event.time > end_time {
+				continue;
+			}
+			next = match next {
+				Some(n) if (n.time, rank(&n.kind)) <= (event.time, rank(&event.kind)) => Some(n),
+				_ => Some(*event)
+			};
+		}
+		return next;
+	}
+}
+
+fn rank(kind: &EventKind) -> u8 {
+	return match kind {
+		EventKind::Input(_) | EventKind::Release(_) => 0,
+		EventKind::TargetAppears => 1,
+		EventKind::Action => 2,
+		EventKind::HoldStart => 3,
+		EventKind::HoldEnd => 4,
+		EventKind::BrickCrossing => 5
+	};
+}
@@ -0,0 +1,287 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use wasm_bindgen::prelude::*;
+use js_sys::Array;
+
+use crate::BrickData;
+use crate::edit_history::EditHistory;
+use crate::edit_history::EditCommand;
+
+// the bricks selected in the editor, and the bricks last copied
+pub struct Selection {
+	selected: BTreeSet<BrickData>,
+	clipboard: Vec<BrickData> // with beat positions relative to the first copied brick
+}
+
+// the result of pasting, moving, or transforming bricks. Edits are all or nothing: if any brick would collide with an existing brick
+	// or leave the song's lanes, nothing is changed and the problems are reported
+#[wasm_bindgen]
+pub struct SelectionEdit {
+	pub applied: bool,
+	pub out_of_range: bool, // some brick would be placed outside the lanes or before the start of the song, or have a brick type the song doesn't use
+	collisions: Vec<BrickData> // existing bricks that placed bricks would overlap, and placed bricks that would overlap each other
+}
+
+impl SelectionEdit {
+	// an edit that wasn't applied because a brick would be out of range
+	pub fn out_of_range() -> SelectionEdit {
+		return SelectionEdit { applied: false, out_of_range: true, collisions: Vec::new() };
+	}
+}
+
+#[wasm_bindgen]
+impl SelectionEdit {
+	pub fn collisions(&self) -> Array {
+		let array = Array::new_with_length(self.collisions.len() as u32);
+		for (i, brick) in self.collisions.iter().enumerate() {
+			array.set(i as u32, JsValue::from(brick.clone()));
+		}
+		return array;
+	}
+}
+
+impl Selection {
+	pub fn new() -> Selection {
+		return Selection {
+			selected: BTreeSet::new(),
+			clipboard: Vec::new()
+		};
+	}
+
+	// selects the bricks starting within the beat range in the lane range (both inclusive). returns the number of bricks selected
+	pub fn select_range(&mut self, notes: &BTreeSet<BrickData>, start_beat_pos: i32, end_beat_pos: i32, left_x_pos: i32, right_x_pos: i32) -> usize {
+		let (start_beat_pos, end_beat_pos) = (start_beat_pos.min(end_beat_pos), start_beat_pos.max(end_beat_pos));
+		let (left_x_pos, right_x_pos) = (left_x_pos.min(right_x_pos), left_x_pos.max(right_x_pos));
+
+		self.selected = BTreeSet::new();
+		for brick_data in notes {
+			if brick_data.beat_pos > end_beat_pos {
+				break;
+			}
+			if brick_data.beat_pos >= start_beat_pos && brick_data.x_pos >= left_x_pos && brick_data.x_pos <= right_x_pos {
+				self.selected.insert(brick_data.clone());
+			}
+		}
+
+		return self.selected.len();
+	}
+
+	pub fn clear(&mut self) {
+		self.selected = BTreeSet::new();
+	}
+
+	pub fn selected(&self) -> &BTreeSet<BrickData> {
+		return &self.selected;
+	}
+
+	// unselects a brick that was removed from the notes
+	pub fn forget(&mut self, brick_data: &BrickData) {
+		self.selected.remove(brick_data);
+	}
+
+	// copies the selected bricks. returns the number of bricks copied
+	pub fn copy(&mut self) -> usize {
+		if let Some(first) = self.selected.iter().next() {
+			let base_beat_pos = first.beat_pos;
+			self.clipboard = self.selected.iter().map(|brick_data| shifted(brick_data, -base_beat_pos, 0)).collect();
+		}
+
+		return self.selected.len();
+	}
+
+	// removes the selected bricks from the notes as one edit. returns the number of bricks removed
+	pub fn delete(&mut self, notes: &mut BTreeSet<BrickData>, history: &mut EditHistory) -> usize {
+		let selected = std::mem::take(&mut self.selected);
+
+		history.begin_group();
+		for brick_data in &selected {
+			if let Some(removed) = notes.take(brick_data) {
+				history.record(EditCommand::Remove(removed));
+			}
+		}
+		history.end_group();
+
+		return selected.len();
+	}
+
+	// adds the copied bricks with the first copied brick at the beat position. The pasted bricks become the selection
+	pub fn paste(&mut self, notes: &mut BTreeSet<BrickData>, history: &mut EditHistory, num_brick_types: u8, lanes: u8, beat_pos: i32) -> SelectionEdit {
+		let placing: Vec<BrickData> = self.clipboard.iter().map(|brick_data| shifted(brick_data, beat_pos, 0)).collect();
+		let edit = replace_bricks(notes, history, num_brick_types, lanes, &BTreeSet::new(), &placing);
+		if edit.applied {
+			self.selected = placing.into_iter().collect();
+		}
+		return edit;
+	}
+
+	// moves the selected bricks by the beat and lane offsets
+	pub fn move_by(&mut self, notes: &mut BTreeSet<BrickData>, history: &mut EditHistory, num_brick_types: u8, lanes: u8,
+	beat_offset: i32, lane_offset: i32) -> SelectionEdit {
+		let placing: Vec<BrickData> = self.selected.iter().map(|brick_data| shifted(brick_data, beat_offset, lane_offset)).collect();
+		let edit = replace_bricks(notes, history, num_brick_types, lanes, &self.selected, &placing);
+		if edit.applied {
+			self.selected = placing.into_iter().collect();
+		}
+		return edit;
+	}
+}
+
+// replaces the removing bricks with the placing bricks as one edit, if no placing brick collides with a brick staying in the notes
+	// or with another placing brick, and all placing bricks are within the lanes, after the start of the song, and of a brick type the song uses
+pub fn replace_bricks(notes: &mut BTreeSet<BrickData>, history: &mut EditHistory, num_brick_types: u8, lanes: u8,
+removing: &BTreeSet<BrickData>, placing: &[BrickData]) -> SelectionEdit {
+	let lanes = lanes as i32;
+	let out_of_range = placing.iter().any(|brick_data| {
+		return brick_data.beat_pos < 0 || brick_data.x_pos < 0 || brick_data.x_pos >= lanes
+			|| brick_data.brick_type as u8 >= num_brick_types;
+	});
+
+	let mut collisions = Vec::new();
+	for brick_data in notes.iter() {
+		if removing.contains(brick_data) {
+			continue;
+		}
+		if placing.iter().any(|placed| overlaps(placed, brick_data)) {
+			collisions.push(brick_data.clone());
+		}
+	}
+	for (i, brick_data) in placing.iter().enumerate() {
+		if placing[i + 1 ..].iter().any(|placed| overlaps(placed, brick_data)) {
+			collisions.push(brick_data.clone());
+		}
+	}
+
+	if out_of_range || collisions.len() > 0 {
+		return SelectionEdit { applied: false, out_of_range, collisions };
+	}
+
+	history.begin_group();
+	for brick_data in removing {
+		if let Some(removed) = notes.take(brick_data) {
+			history.record(EditCommand::Remove(removed));
+		}
+	}
+	for brick_data in placing {
+		if notes.insert(brick_data.clone()) {
+			history.record(EditCommand::Add(brick_data.clone()));
+		}
+	}
+	history.end_group();
+
+	return SelectionEdit { applied: true, out_of_range, collisions };
+}
+
+// a copy of the brick moved by the beat and lane offsets
+fn shifted(brick_data: &BrickData, beat_offset: i32, lane_offset: i32) -> BrickData {
+	let mut shifted = brick_data.clone();
+	shifted.beat_pos += beat_offset;
+	shifted.end_beat_pos += beat_offset;
+	shifted.x_pos += lane_offset;
+	return shifted;
+}
+
+// whether two bricks are in the same lane at overlapping beat positions (including the beat positions of holds)
+pub fn overlaps(a: &BrickData, b: &BrickData) -> bool {
+	let a_end = if a.is_hold_note { a.end_beat_pos } else { a.beat_pos };
+	let b_end = if b.is_hold_note { b.end_beat_pos } else { b.beat_pos };
+	return a.x_pos == b.x_pos && a.beat_pos <= b_end && b.beat_pos <= a_end;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::objects::BrickType;
+
+	const BRICK_TYPES: u8 = 3;
+	const LANES: u8 = 8;
+
+	fn tap(beat_pos: i32, x_pos: i32) -> BrickData {
+		return BrickData::new(BrickType::Type1, beat_pos, beat_pos, x_pos, false, false, false, false);
+	}
+
+	fn hold(beat_pos: i32, end_beat_pos: i32, x_pos: i32) -> BrickData {
+		return BrickData::new(BrickType::Type1, beat_pos, end_beat_pos, x_pos, false, false, false, true);
+	}
+
+	// (beat pos, x_pos) of each note
+	fn positions(notes: &BTreeSet<BrickData>) -> Vec<(i32, i32)> {
+		return notes.iter().map(|brick_data| (brick_data.beat_pos, brick_data.x_pos)).collect();
+	}
+
+	// notes with a selection of all of them
+	fn selected_notes(bricks: &[BrickData]) -> (BTreeSet<BrickData>, Selection) {
+		let notes: BTreeSet<BrickData> = bricks.iter().cloned().collect();
+		let mut selection = Selection::new();
+		selection.select_range(&notes, 0, i32::MAX, 0, LANES as i32 - 1);
+		return (notes, selection);
+	}
+
+	#[test]
+	fn paste_onto_occupied_cells_changes_nothing() {
+		let (mut notes, mut selection) = selected_notes(&[tap(0, 1), tap(4, 2)]);
+		let mut history = EditHistory::new();
+		selection.copy();
+
+		// the copy of the tap at lane 2 lands inside the hold
+		notes.insert(hold(16, 24, 2));
+		let before = positions(&notes);
+		let edit = selection.paste(&mut notes, &mut history, BRICK_TYPES, LANES, 16);
+		assert!(!edit.applied);
+		assert!(!edit.out_of_range);
+		assert_eq!(positions(&edit.collisions.iter().cloned().collect()), vec![(16, 2)]);
+		assert_eq!(positions(&notes), before);
+		assert!(!history.can_undo());
+
+		let edit = selection.paste(&mut notes, &mut history, BRICK_TYPES, LANES, 32);
+		assert!(edit.applied);
+		assert_eq!(positions(selection.selected()), vec![(32, 1), (36, 2)]);
+	}
+
+	#[test]
+	fn moving_past_the_first_or_last_lane_changes_nothing() {
+		let (mut notes, mut selection) = selected_notes(&[tap(0, 0), tap(4, LANES as i32 - 1)]);
+		let mut history = EditHistory::new();
+		let before = positions(&notes);
+
+		for lane_offset in [-1, 1].iter() {
+			let edit = selection.move_by(&mut notes, &mut history, BRICK_TYPES, LANES, 0, *lane_offset);
+			assert!(!edit.applied && edit.out_of_range);
+			assert_eq!(positions(&notes), before);
+			assert_eq!(positions(selection.selected()), before);
+		}
+		let edit = selection.move_by(&mut notes, &mut history, BRICK_TYPES, LANES, -1, 0);
+		assert!(!edit.applied && edit.out_of_range);
+		assert!(!history.can_undo());
+
+		let edit = selection.move_by(&mut notes, &mut history, BRICK_TYPES, LANES, 2, 0);
+		assert!(edit.applied);
+		assert_eq!(positions(&notes), vec![(2, 0), (6, LANES as i32 - 1)]);
+	}
+
+	#[test]
+	fn placed_bricks_that_collide_with_each_other_change_nothing() {
+		let mut notes: BTreeSet<BrickData> = [tap(0, 3)].iter().cloned().collect();
+		let mut history = EditHistory::new();
+		let removing: BTreeSet<BrickData> = notes.clone();
+
+		// the removed brick's place is free, but the placed hold covers the placed tap
+		let placing = [hold(0, 8, 3), tap(4, 3), tap(4, 5)];
+		let edit = replace_bricks(&mut notes, &mut history, BRICK_TYPES, LANES, &removing, &placing);
+		assert!(!edit.applied && !edit.out_of_range);
+		assert_eq!(positions(&edit.collisions.iter().cloned().collect()), vec![(0, 3)]);
+		assert_eq!(positions(&notes), vec![(0, 3)]);
+		assert!(!history.can_undo());
+	}
+
+	#[test]
+	fn bricks_of_types_the_song_does_not_use_are_out_of_range() {
+		let mut notes = BTreeSet::new();
+		let mut history = EditHistory::new();
+		let mut brick_data = tap(0, 0);
+		brick_data.brick_type = BrickType::Type4;
+		let edit = replace_bricks(&mut notes, &mut history, BRICK_TYPES, LANES, &BTreeSet::new(), &[brick_data]);
+		assert!(!edit.applied && edit.out_of_range);
+		assert!(notes.is_empty());
+	}
+}
@@ -0,0 +1,348 @@
+
+use crate::BrickData;
+use crate::objects::BrickType;
+
+// a parsed json value. Objects keep their keys in order so a song written back out matches the file it was read from
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonValue {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<JsonValue>),
+	Object(Vec<(String, JsonValue)>)
+}
+
+impl JsonValue {
+	pub fn get(&self, key: &str) -> Option<&JsonValue> {
+		if let JsonValue::Object(entries) = self {
+			return entries.iter().find(|(k, _)| k == key).map(|(_, value)| value);
+		}
+		return None;
+	}
+
+	// sets the key of an object, adding it at the end if it isn't already there
+	pub fn set(&mut self, key: &str, value: JsonValue) {
+		if let JsonValue::Object(entries) = self {
+			match entries.iter_mut().find(|(k, _)| k == key) {
+				Some(entry) => entry.1 = value,
+				None => entries.push((key.to_string(), value))
+			}
+		}
+	}
+
+	pub fn as_f64(&self) -> Option<f64> {
+		if let JsonValue::Number(num) = self {
+			return Some(*num);
+		}
+		return None;
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		if let JsonValue::Bool(b) = self {
+			return Some(*b);
+		}
+		return None;
+	}
+
+	pub fn to_json(&self) -> String {
+		let mut text = String::new();
+		self.write(&mut text);
+		return text;
+	}
+
+	fn write(&self, text: &mut String) {
+		match self {
+			JsonValue::Null => text.push_str("null"),
+			JsonValue::Bool(b) => text.push_str(if *b { "true" } else { "false" }),
+			JsonValue::Number(num) => text.push_str(&num.to_string()),
+			JsonValue::String(s) => write_string(s, text),
+			JsonValue::Array(values) => {
+				text.push('[');
+				for (i, value) in values.iter().enumerate() {
+					if i > 0 {
+						text.push(',');
+					}
+					value.write(text);
+				}
+				text.push(']');
+			},
+			JsonValue::Object(entries) => {
+				text.push('{');
+				for (i, (key, value)) in entries.iter().enumerate() {
+					if i > 0 {
+						text.push(',');
+					}
+					write_string(key, text);
+					text.push(':');
+					value.write(text);
+				}
+				text.push('}');
+			}
+		}
+	}
+}
+
+pub fn parse(text: &str) -> Result<JsonValue, String> {
+	let mut parser = Parser { chars: text.chars().collect(), idx: 0 };
+	let value = parser.value()?;
+	parser.skip_whitespace();
+	if parser.idx < parser.chars.len() {
+		return Err(format!("Unexpected character after the json at {}", parser.idx));
+	}
+	return Ok(value);
+}
+
+// the bricks of a song object, stored as arrays of
+	// [brick_type, beat_pos, end_beat_pos, x_pos, is_triplet, is_trailing, is_leading, is_hold_note, approx_time]
+pub fn song_notes(song: &JsonValue) -> Result<Vec<BrickData>, String> {
+	let notes = match song.get("notes") {
+		Some(JsonValue::Array(notes)) => notes,
+		_ => return Err("The song has no notes array".to_string())
+	};
+
+	let mut bricks = Vec::with_capacity(notes.len());
+	for (i, note) in notes.iter().enumerate() {
+		let fields = match note {
+			JsonValue::Array(fields) if fields.len() >= 8 => fields,
+			_ => return Err(format!("Note {} isn't an array of at least 8 fields", i))
+		};
+		let int = |idx: usize| -> Result<i32, String> {
+			return fields[idx].as_f64().map(|num| num as i32).ok_or(format!("Field {} of note {} isn't a number", idx, i));
+		};
+		let flag = |idx: usize| -> Result<bool, String> {
+			return fields[idx].as_bool().ok_or(format!("Field {} of note {} isn't true or false", idx, i));
+		};
+		let type_idx = int(0)?;
+		let brick_type = if type_idx >= 0 { BrickType::from_index(type_idx as usize) } else { None };
+		let brick_type = match brick_type {
+			Some(brick_type) => brick_type,
+			None => return Err(format!("Note {} has an unknown brick type {}", i, type_idx))
+		};
+
+		bricks.push(BrickData::new(brick_type, int(1)?, int(2)?, int(3)?, flag(4)?, flag(5)?, flag(6)?, flag(7)?));
+	}
+
+	return Ok(bricks);
+}
+
+// the json array for the bricks, in the same form the editor saves them
+pub fn notes_json(bricks: &[BrickData], bpm: f32) -> JsonValue {
+	let notes = bricks.iter().map(|brick_data| {
+		return JsonValue::Array(vec![
+			JsonValue::Number(brick_data.brick_type as u8 as f64),
+			JsonValue::Number(brick_data.beat_pos as f64),
+			JsonValue::Number(brick_data.end_beat_pos as f64),
+			JsonValue::Number(brick_data.x_pos as f64),
+			JsonValue::Bool(brick_data.is_triplet),
+			JsonValue::Bool(brick_data.is_trailing),
+			JsonValue::Bool(brick_data.is_leading),
+			JsonValue::Bool(brick_data.is_hold_note),
+			JsonValue::Number(BrickData::approx_time(brick_data.beat_pos, bpm) as f64)
+		]);
+	}).collect();
+	return JsonValue::Array(notes);
+}
+
+fn write_string(s: &str, text: &mut String) {
+	text.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => text.push_str("\\\""),
+			'\\' => text.push_str("\\\\"),
+			'\n' => text.push_str("\\n"),
+			'\r' => text.push_str("\\r"),
+			'\t' => text.push_str("\\t"),
+			c if (c as u32) < 0x20 => text.push_str(&format!("\\u{:04x}", c as u32)),
+			c => text.push(c)
+		}
+	}
+	text.push('"');
+}
+
+struct Parser {
+	chars: Vec<char>,
+	idx: usize
+}
+
+impl Parser {
+	fn value(&mut self) -> Result<JsonValue, String> {
+		self.skip_whitespace();
+		match self.chars.get(self.idx) {
+			Some('{') => return self.object(),
+			Some('[') => return self.array(),
+			Some('"') => return Ok(JsonValue::String(self.string()?)),
+			Some('t') => return self.literal("true", JsonValue::Bool(true)),
+			Some('f') => return self.literal("false", JsonValue::Bool(false)),
+			Some('n') => return self.literal("null", JsonValue::Null),
+			Some(_) => return self.number(),
+			None => return Err("Unexpected end of the json".to_string())
+		}
+	}
+
+	fn object(&mut self) -> Result<JsonValue, String> {
+		let mut entries = Vec::new();
+		self.idx += 1;
+		self.skip_whitespace();
+		if self.chars.get(self.idx) == Some(&'}') {
+			self.idx += 1;
+			return Ok(JsonValue::Object(entries));
+		}
+
+		loop {
+			self.skip_whitespace();
+			if self.chars.get(self.idx) != Some(&'"') {
+				return Err(format!("Expected a key at {}", self.idx));
+			}
+			let key = self.string()?;
+			self.skip_whitespace();
+			self.expect(':')?;
+			let value = self.value()?;
+			entries.push((key, value));
+
+			self.skip_whitespace();
+			match self.chars.get(self.idx) {
+				Some(',') => self.idx += 1,
+				Some('}') => { self.idx += 1; return Ok(JsonValue::Object(entries)); },
+				_ => return Err(format!("Expected ',' or '}}' at {}", self.idx))
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<JsonValue, String> {
+		let mut values = Vec::new();
+		self.idx += 1;
+		self.skip_whitespace();
+		if self.chars.get(self.idx) == Some(&']') {
+			self.idx += 1;
+			return Ok(JsonValue::Array(values));
+		}
+
+		loop {
+			values.push(self.value()?);
+			self.skip_whitespace();
+			match self.chars.get(self.idx) {
+				Some(',') => self.idx += 1,
+				Some(']') => { self.idx += 1; return Ok(JsonValue::Array(values)); },
+				_ => return Err(format!("Expected ',' or ']' at {}", self.idx))
+			}
+		}
+	}
+
+	fn string(&mut self) -> Result<String, String> {
+		let mut s = String::new();
+		self.idx += 1;
+		loop {
+			let c = *self.chars.get(self.idx).ok_or("Unterminated string in the json")?;
+			self.idx += 1;
+			match c {
+				'"' => return Ok(s),
+				'\\' => {
+					let escaped = *self.chars.get(self.idx).ok_or("Unterminated string in the json")?;
+					self.idx += 1;
+					match escaped {
+						'n' => s.push('\n'),
+						't' => s.push('\t'),
+						'r' => s.push('\r'),
+						'b' => s.push('\u{8}'),
+						'f' => s.push('\u{c}'),
+						'u' => {
+							let mut code = self.hex_escape()?;
+							// characters outside the basic multilingual plane (like emoji) are escaped as a utf-16 surrogate pair
+							if (0xd800..0xdc00).contains(&code) && self.chars.get(self.idx) == Some(&'\\')
+								&& self.chars.get(self.idx + 1) == Some(&'u') {
+								let idx = self.idx;
+								self.idx += 2;
+								let low = self.hex_escape()?;
+								if (0xdc00..0xe000).contains(&low) {
+									code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+								} else {
+									self.idx = idx; // not a low surrogate, read it as its own escape
+								}
+							}
+							s.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+						},
+						other => s.push(other)
+					}
+				},
+				c => s.push(c)
+			}
+		}
+	}
+
+	// the 4 hex digits of a \u escape, as a utf-16 code unit
+	fn hex_escape(&mut self) -> Result<u32, String> {
+		let hex: String = self.chars.iter().skip(self.idx).take(4).collect();
+		let code = u32::from_str_radix(&hex, 16).map_err(|_| format!("Invalid unicode escape at {}", self.idx))?;
+		self.idx += 4;
+		return Ok(code);
+	}
+
+	fn number(&mut self) -> Result<JsonValue, String> {
+		let start = self.idx;
+		while let Some(c) = self.chars.get(self.idx) {
+			if c.is_ascii_digit() || *c == '-' || *c == '+' || *c == '.' || *c == 'e' || *c == 'E' {
+				self.idx += 1;
+			} else {
+				break;
+			}
+		}
+		let text: String = self.chars[start..self.idx].iter().collect();
+		// numbers too large for an f64 (like 1e400) parse as infinity, which can't be written back as json
+		return match text.parse::<f64>() {
+			Ok(num) if num.is_finite() => Ok(JsonValue::Number(num)),
+			_ => Err(format!("Invalid value at {}", start))
+		};
+	}
+
+	fn literal(&mut self, word: &str, value: JsonValue) -> Result<JsonValue, String> {
+		let end = self.idx + word.len();
+		if end <= self.chars.len() && self.chars[self.idx..end].iter().copied().eq(word.chars()) {
+			self.idx = end;
+			return Ok(value);
+		}
+		return Err(format!("Invalid value at {}", self.idx));
+	}
+
+	fn expect(&mut self, c: char) -> Result<(), String> {
+		if self.chars.get(self.idx) != Some(&c) {
+			return Err(format!("Expected '{}' at {}", c, self.idx));
+		}
+		self.idx += 1;
+		return Ok(());
+	}
+
+	fn skip_whitespace(&mut self) {
+		while let Some(c) = self.chars.get(self.idx) {
+			if !c.is_whitespace() {
+				break;
+			}
+			self.idx += 1;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn surrogate_pairs_are_combined() {
+		let value = parse(r#"{"name":"\ud83c\udfb5 song"}"#).unwrap();
+		assert_eq!(value.get("name"), Some(&JsonValue::String("\u{1f3b5} song".to_string())));
+		assert_eq!(parse(&value.to_json()).unwrap(), value);
+	}
+
+	#[test]
+	fn lone_surrogates_are_replaced() {
+		assert_eq!(parse(r#""\ud83c x""#).unwrap(), JsonValue::String("\u{fffd} x".to_string()));
+		assert_eq!(parse(r#""\ud83cA""#).unwrap(), JsonValue::String("\u{fffd}A".to_string()));
+	}
+
+	#[test]
+	fn numbers_out_of_range_are_rejected() {
+		assert!(parse("[1e400]").is_err());
+		assert!(parse("[-1e400]").is_err());
+		assert_eq!(parse("[1e300]").unwrap(), JsonValue::Array(vec![JsonValue::Number(1e300)]));
+	}
+}
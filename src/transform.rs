@@ -0,0 +1,192 @@
+
+use std::collections::btree_set::BTreeSet;
+
+use crate::BrickData;
+use crate::edit_history::EditHistory;
+use crate::selection;
+use crate::selection::SelectionEdit;
+use crate::objects::BrickType;
+
+// replaces each brick starting within the beat range (inclusive) with the transformed brick, as one edit
+	// like pastes and moves, nothing changes if any transformed brick would collide with another brick, leave the lanes,
+	// or have a brick type the song doesn't use
+pub fn transform_range<F>(notes: &mut BTreeSet<BrickData>, history: &mut EditHistory, num_brick_types: u8, lanes: u8,
+start_beat_pos: i32, end_beat_pos: i32, transform: F) -> SelectionEdit
+where F: Fn(&BrickData) -> BrickData {
+	let (start_beat_pos, end_beat_pos) = (start_beat_pos.min(end_beat_pos), start_beat_pos.max(end_beat_pos));
+
+	let mut removing = BTreeSet::new();
+	for brick_data in notes.iter() {
+		if brick_data.beat_pos > end_beat_pos {
+			break;
+		}
+		if brick_data.beat_pos >= start_beat_pos {
+			removing.insert(brick_data.clone());
+		}
+	}
+
+	let placing: Vec<BrickData> = removing.iter().map(transform).collect();
+	return selection::replace_bricks(notes, history, num_brick_types, lanes, &removing, &placing);
+}
+
+pub fn with_type(brick_data: &BrickData, brick_type: BrickType) -> BrickData {
+	let mut transformed = brick_data.clone();
+	transformed.brick_type = brick_type;
+	return transformed;
+}
+
+// Type1 -> Type2 -> ... -> the song's last brick type -> Type1
+pub fn cycled_type(brick_data: &BrickData, num_brick_types: u8) -> BrickData {
+	let brick_type = BrickType::from_index_wrapped(brick_data.brick_type as usize + 1, num_brick_types);
+	return with_type(brick_data, brick_type);
+}
+
+// flips the brick to the opposite side of the song's lanes
+pub fn mirrored(brick_data: &BrickData, lanes: u8) -> BrickData {
+	let mut transformed = brick_data.clone();
+	transformed.x_pos = lanes as i32 - 1 - brick_data.x_pos;
+	return transformed;
+}
+
+pub fn lane_shifted(brick_data: &BrickData, lane_offset: i32) -> BrickData {
+	let mut transformed = brick_data.clone();
+	transformed.x_pos += lane_offset;
+	return transformed;
+}
+
+// scales the brick's distance (and hold length) from the anchor beat pos by the ratio
+pub fn stretched(brick_data: &BrickData, anchor_beat_pos: i32, ratio: f32) -> BrickData {
+	let stretch = |beat_pos: i32| -> i32 {
+		return anchor_beat_pos + ((beat_pos - anchor_beat_pos) as f32 * ratio).round() as i32;
+	};
+
+	let mut transformed = brick_data.clone();
+	transformed.beat_pos = stretch(brick_data.beat_pos);
+	transformed.end_beat_pos = if brick_data.is_hold_note { stretch(brick_data.end_beat_pos) } else { transformed.beat_pos };
+	if transformed.is_hold_note && transformed.end_beat_pos <= transformed.beat_pos {
+		transformed.end_beat_pos = transformed.beat_pos + 1;
+	}
+	return transformed;
+}
+
+// makes a tap into a hold lasting the given number of beat positions. Holds are unchanged
+pub fn to_hold(brick_data: &BrickData, length: i32) -> BrickData {
+	let mut transformed = brick_data.clone();
+	if !brick_data.is_hold_note {
+		transformed.is_hold_note = true;
+		transformed.end_beat_pos = brick_data.beat_pos + length;
+	}
+	return transformed;
+}
+
+pub fn to_tap(brick_data: &BrickData) -> BrickData {
+	let mut transformed = brick_data.clone();
+	transformed.is_hold_note = false;
+	transformed.end_beat_pos = brick_data.beat_pos;
+	return transformed;
+}
+
+// moves the end of the brick to the end beat pos, making it a hold note, or a tap if the end is at or before its start
+pub fn with_end(brick_data: &BrickData, end_beat_pos: i32) -> BrickData {
+	if end_beat_pos <= brick_data.beat_pos {
+		return to_tap(brick_data);
+	}
+	let mut transformed = brick_data.clone();
+	transformed.is_hold_note = true;
+	transformed.end_beat_pos = end_beat_pos;
+	return transformed;
+}
+
+// snaps the brick (including leading/trailing offsets) and its hold end to the nearest multiple of the grid.
+	// the brick loses any leading, trailing, and triplet flags
+pub fn requantized(brick_data: &BrickData, grid: i32) -> BrickData {
+	let mut position = brick_data.beat_pos as f32;
+	if brick_data.is_leading {
+		position -= 0.5;
+	}
+	if brick_data.is_trailing {
+		position += 0.5;
+	}
+	let snap = |position: f32| -> i32 {
+		return (position / grid as f32).round() as i32 * grid;
+	};
+
+	let mut transformed = brick_data.clone();
+	transformed.beat_pos = snap(position);
+	transformed.end_beat_pos = if brick_data.is_hold_note { snap(brick_data.end_beat_pos as f32) } else { transformed.beat_pos };
+	if transformed.is_hold_note && transformed.end_beat_pos <= transformed.beat_pos {
+		transformed.end_beat_pos = transformed.beat_pos + grid;
+	}
+	transformed.is_leading = false;
+	transformed.is_trailing = false;
+	transformed.is_triplet = false;
+	return transformed;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const BRICK_TYPES: u8 = 3;
+	const LANES: u8 = 8;
+
+	fn tap(beat_pos: i32, x_pos: i32) -> BrickData {
+		return BrickData::new(BrickType::Type1, beat_pos, beat_pos, x_pos, false, false, false, false);
+	}
+
+	// every field of a note, since BrickData's equality ignores the brick type and end beat pos
+	type Fields = (u8, i32, i32, i32, bool, bool, bool, bool);
+
+	fn fields(notes: &BTreeSet<BrickData>) -> Vec<Fields> {
+		return notes.iter().map(|b| (b.brick_type as u8, b.beat_pos, b.end_beat_pos, b.x_pos,
+			b.is_triplet, b.is_trailing, b.is_leading, b.is_hold_note)).collect();
+	}
+
+	#[test]
+	fn requantize_snaps_leading_and_trailing_bricks_and_drops_their_flags() {
+		let leading = BrickData::new(BrickType::Type1, 5, 5, 0, false, false, true, false);
+		let trailing = BrickData::new(BrickType::Type1, 5, 5, 0, false, true, false, false);
+		let triplet = BrickData::new(BrickType::Type1, 6, 6, 0, true, false, false, false);
+		let hold = BrickData::new(BrickType::Type1, 3, 4, 0, false, false, false, true);
+
+		// the leading brick is at 4.5, which is nearest 4 on the 2 grid, and the trailing brick at 5.5, nearest 6
+		let snapped: Vec<(i32, i32, bool, bool, bool)> = [leading, trailing, triplet, hold].iter().map(|brick_data| {
+			let transformed = requantized(brick_data, 2);
+			return (transformed.beat_pos, transformed.end_beat_pos, transformed.is_leading, transformed.is_trailing, transformed.is_triplet);
+		}).collect();
+		assert_eq!(snapped, vec![(4, 4, false, false, false), (6, 6, false, false, false), (6, 6, false, false, false), (4, 6, false, false, false)]);
+	}
+
+	#[test]
+	fn stretch_can_collapse_bricks_onto_each_other() {
+		assert_eq!(stretched(&tap(9, 0), 8, 0.25).beat_pos, 8);
+		assert_eq!(stretched(&tap(10, 0), 8, 0.25).beat_pos, 9);
+		let hold = BrickData::new(BrickType::Type1, 8, 9, 0, false, false, false, true);
+		assert_eq!(stretched(&hold, 8, 0.25).end_beat_pos, 9); // a hold keeps at least one beat pos
+	}
+
+	#[test]
+	fn failed_transform_leaves_the_notes_unchanged() {
+		let mut notes: BTreeSet<BrickData> = [tap(8, 1), tap(9, 1), tap(12, 6), tap(40, 1)].iter().cloned().collect();
+		let mut history = EditHistory::new();
+		let before = fields(&notes);
+
+		// beat pos 8 and 9 both snap to 8
+		let edit = transform_range(&mut notes, &mut history, BRICK_TYPES, LANES, 0, 16, |b| stretched(b, 8, 0.25));
+		assert!(!edit.applied);
+		assert_eq!(fields(&notes), before);
+		// the brick at lane 6 would leave the lanes
+		let edit = transform_range(&mut notes, &mut history, BRICK_TYPES, LANES, 0, 16, |b| lane_shifted(b, 2));
+		assert!(!edit.applied && edit.out_of_range);
+		assert_eq!(fields(&notes), before);
+		// Type4 isn't one of the song's brick types
+		let edit = transform_range(&mut notes, &mut history, BRICK_TYPES, LANES, 0, 16, |b| with_type(b, BrickType::Type4));
+		assert!(!edit.applied && edit.out_of_range);
+		assert_eq!(fields(&notes), before);
+		assert!(!history.can_undo());
+
+		let edit = transform_range(&mut notes, &mut history, BRICK_TYPES, LANES, 0, 16, |b| cycled_type(b, BRICK_TYPES));
+		assert!(edit.applied);
+		assert_eq!(notes.iter().map(|b| b.brick_type as u8).collect::<Vec<u8>>(), vec![1, 1, 1, 0]);
+	}
+}
@@ -0,0 +1,100 @@
+
+use wasm_bindgen::prelude::*;
+
+use crate::PositionedGraphic;
+use crate::Position;
+
+use crate::GAME_WIDTH;
+use crate::GAME_HEIGHT;
+
+// maps the world (GAME_WIDTH by GAME_HEIGHT world units, which all gameplay distances are in) to the pixels of a screen of any aspect ratio.
+	// The world is scaled evenly to fit the screen and centered, so ultrawide screens get margins on the sides and portrait screens
+	// above and below, and gameplay plays out the same on every screen
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct Viewport {
+	screen_width: f32, // in pixels
+	screen_height: f32,
+	scale: f32, // pixels per world unit
+	offset_x: f32, // the screen position of the world's top left, in pixels
+	offset_y: f32
+}
+
+#[wasm_bindgen]
+impl Viewport {
+	// a viewport for a screen of the given size in pixels. The screen's aspect ratio is width / height
+	pub fn new(screen_width: f32, screen_height: f32) -> Viewport {
+		let screen_width = screen_width.max(1.0);
+		let screen_height = screen_height.max(1.0);
+		let scale = (screen_width / GAME_WIDTH).min(screen_height / GAME_HEIGHT);
+		return Viewport {
+			screen_width,
+			screen_height,
+			scale,
+			offset_x: (screen_width - GAME_WIDTH * scale) / 2.0,
+			offset_y: (screen_height - GAME_HEIGHT * scale) / 2.0
+		};
+	}
+
+	// a viewport for a screen of the given aspect ratio (width / height) that's as tall as the world
+	pub fn with_aspect_ratio(aspect_ratio: f32) -> Viewport {
+		return Viewport::new(GAME_HEIGHT * aspect_ratio, GAME_HEIGHT);
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn screen_width(&self) -> f32 {
+		return self.screen_width;
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn screen_height(&self) -> f32 {
+		return self.screen_height;
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn aspect_ratio(&self) -> f32 {
+		return self.screen_width / self.screen_height;
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn scale(&self) -> f32 {
+		return self.scale;
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn offset_x(&self) -> f32 {
+		return self.offset_x;
+	}
+
+	#[wasm_bindgen(getter)]
+	pub fn offset_y(&self) -> f32 {
+		return self.offset_y;
+	}
+
+	// the screen position (in pixels) of a world position
+	pub fn screen_position(&self, x: f32, y: f32) -> Position {
+		return Position {
+			x: self.offset_x + x * self.scale,
+			y: self.offset_y + y * self.scale
+		};
+	}
+
+	// the world position of a screen position (in pixels), such as that of a mouse event
+	pub fn world_position(&self, x: f32, y: f32) -> Position {
+		return Position {
+			x: (x - self.offset_x) / self.scale,
+			y: (y - self.offset_y) / self.scale
+		};
+	}
+}
+
+impl Viewport {
+	// moves and sizes a graphic positioned in the world to where it's drawn on the screen
+	pub fn apply(&self, graphic: &mut PositionedGraphic) {
+		let screen_pos = self.screen_position(graphic.x, graphic.y);
+		graphic.x = screen_pos.x;
+		graphic.y = screen_pos.y;
+		graphic.width *= self.scale;
+		graphic.height *= self.scale;
+	}
+}
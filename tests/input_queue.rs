@@ -0,0 +1,49 @@
+// checks how inputs are queued at their time in the song: late inputs are moved up to the current time or rejected,
+	// releases are never rejected, and seeking clears the queue
+
+use rhythm_warrior::Input;
+use rhythm_warrior::test_support::Game;
+use rhythm_warrior::test_support::GameConfig;
+use rhythm_warrior::test_support::MAX_INPUT_LATENESS;
+
+const NOW: f32 = 1.0;
+
+// a song without bricks, played up to NOW
+fn game() -> Game {
+	let mut game = Game::new(120.0, 600.0, 60.0, &GameConfig::new());
+	game.seek(0.0);
+	game.tick(NOW);
+	return game;
+}
+
+// (time, press) of each queued input
+fn queue(game: &Game) -> Vec<(f32, bool)> {
+	return game.queued().iter().map(|queued| (queued.time, queued.press)).collect();
+}
+
+#[test]
+fn inputs_older_than_the_max_lateness_are_rejected() {
+	let mut game = game();
+	assert!(!game.queue_input(Input::Slash1, NOW - MAX_INPUT_LATENESS - 0.01));
+	assert!(queue(&game).is_empty());
+	assert!(game.queue_input(Input::Slash1, NOW - MAX_INPUT_LATENESS + 0.01));
+	assert_eq!(queue(&game).len(), 1);
+}
+
+#[test]
+fn late_inputs_and_releases_are_moved_up_to_the_current_time() {
+	let mut game = game();
+	assert!(game.queue_input(Input::Slash2, NOW - MAX_INPUT_LATENESS / 2.0));
+	game.queue_release(Input::Slash2, 0.0); // never rejected, however late
+	assert!(game.queue_input(Input::Dash, NOW + 0.5));
+	assert_eq!(queue(&game), vec![(NOW, true), (NOW, false), (NOW + 0.5, true)]);
+}
+
+#[test]
+fn seek_clears_the_queue() {
+	let mut game = game();
+	game.queue_input(Input::Slash1, NOW + 0.5);
+	game.queue_release(Input::Slash1, NOW + 0.6);
+	game.seek(NOW + 0.2);
+	assert!(queue(&game).is_empty());
+}
@@ -0,0 +1,60 @@
+// checks that changes to a song's brick types and lanes are undone and redone with the edits around them,
+	// so undo never brings back a brick beyond the song's lanes or brick types
+
+use rhythm_warrior::BrickData;
+use rhythm_warrior::test_support::Game;
+use rhythm_warrior::test_support::GameConfig;
+use rhythm_warrior::test_support::BrickType;
+use rhythm_warrior::test_support::MidiImportOptions;
+
+const BPM: f32 = 120.0;
+
+fn new_game(num_brick_types: u8, lanes: u8) -> Game {
+	let mut game = Game::new(BPM, 600.0, 60.0, &GameConfig::new());
+	game.initial_load_song_settings(num_brick_types, lanes).unwrap();
+	return game;
+}
+
+fn tap(brick_type: BrickType, beat_pos: i32, x_pos: i32) -> BrickData {
+	return BrickData::new(brick_type, beat_pos, beat_pos, x_pos, false, false, false, false);
+}
+
+#[test]
+fn undoing_a_delete_restores_the_lanes_and_brick_types_it_was_made_with() {
+	let mut game = new_game(3, 8);
+	assert!(!game.game_data().is_modified);
+	game.add_brick(tap(BrickType::Type3, 8, 7)).unwrap();
+	game.remove_brick(tap(BrickType::Type3, 8, 7)).unwrap();
+	game.set_lanes(4).unwrap();
+	game.set_num_brick_types(2).unwrap();
+
+	game.undo();
+	game.undo();
+	assert_eq!((game.game_data().num_brick_types, game.game_data().lanes), (3, 8));
+	// the brick is back, so the song can't be narrowed again until it's removed
+	game.undo();
+	assert!(game.set_lanes(4).is_err());
+	assert!(game.set_num_brick_types(2).is_err());
+
+	game.redo();
+	game.redo();
+	game.redo();
+	assert_eq!((game.game_data().num_brick_types, game.game_data().lanes), (2, 4));
+	assert!(!game.can_redo());
+}
+
+#[test]
+fn undoing_a_midi_import_restores_the_lanes_and_brick_types_it_raised() {
+	let mut exported = new_game(6, 16);
+	exported.add_brick(tap(BrickType::Type5, 8, 12)).unwrap();
+	let bytes = exported.export_midi().unwrap();
+
+	let mut game = new_game(3, 8);
+	assert_eq!(game.load_midi(&bytes, &MidiImportOptions::chart()).unwrap().added, 1);
+	assert_eq!((game.game_data().num_brick_types, game.game_data().lanes), (5, 13));
+
+	game.undo();
+	assert_eq!((game.game_data().num_brick_types, game.game_data().lanes), (3, 8));
+	assert!(!game.game_data().is_modified);
+	assert!(!game.can_undo());
+}
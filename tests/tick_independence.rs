@@ -0,0 +1,159 @@
+
+// runs the included songs with an autoplayer under different tick partitions (fixed and random tick lengths)
+	// and checks that the score, hits, and player positions come out the same
+
+use std::fs;
+
+use rhythm_warrior::Input;
+use rhythm_warrior::test_support::Game;
+use rhythm_warrior::test_support::GameConfig;
+use rhythm_warrior::test_support::BrickType;
+use rhythm_warrior::test_support::parse_json;
+use rhythm_warrior::test_support::song_notes;
+
+const SONGS: [&str; 2] = ["song-data/ahri.json", "song-data/ivern.json"];
+const CHECKPOINT_INTERVAL: f32 = 1.0; // every partition ends a tick at each checkpoint, where player positions are compared
+const HIT_TIME_TOLERANCE: f32 = 0.001;
+const POSITION_TOLERANCE: f32 = 1.0;
+const RANDOM_PARTITIONS: u32 = 64;
+const MAX_RANDOM_TICK: f32 = 0.05;
+
+struct PlannedInput {
+	time: f32,
+	input: Input,
+	press: bool
+}
+
+struct Run {
+	score: i32,
+	max_score: i32,
+	hit_times: Vec<f32>,
+	player_xs: Vec<f32> // at each checkpoint
+}
+
+// xorshift, so every run of the tests uses the same partitions
+struct Rng {
+	state: u32
+}
+
+impl Rng {
+	fn next_f32(&mut self) -> f32 {
+		self.state ^= self.state << 13;
+		self.state ^= self.state >> 17;
+		self.state ^= self.state << 5;
+		return (self.state % 1_000_000) as f32 / 1_000_000.0;
+	}
+}
+
+fn load_song(path: &str) -> (Game, f32) {
+	let text = fs::read_to_string(path).unwrap();
+	let song = parse_json(&text).unwrap();
+	let number = |key: &str| song.get(key).and_then(|value| value.as_f64()).unwrap() as f32;
+	let duration = number("duration");
+
+	let mut game = Game::new(number("bpm"), number("brickSpeed"), duration, &GameConfig::new());
+	for brick_data in song_notes(&song).unwrap() {
+		game.initial_load_add_brick(brick_data);
+	}
+	game.seek(0.0);
+	return (game, duration);
+}
+
+// presses the slash for the first brick of each target so the action lands when the target becomes hittable, holding through
+	// hold notes. Groups of bricks and dash targets are slash dashed, with the dash pressed along with the slash
+fn autoplay(game: &Game) -> Vec<PlannedInput> {
+	let mut plan = Vec::new();
+	let config = game.config();
+	let targets = game.targets();
+	for (i, ti) in targets.iter().enumerate() {
+		let input = BrickType::to_input(ti.brick_group[0].brick_type());
+		let slash_dash = ti.brick_group.len() > 1 || ti.dash_to_target;
+		let press_time = ti.hittable_time - config.min_pre_slash_time;
+		let mut release_time = if ti.is_hold_note { ti.passed_time } else { press_time + 0.1 };
+		if let Some(next) = targets.get(i + 1) {
+			release_time = release_time.min(next.hittable_time - 0.08);
+		}
+
+		plan.push(PlannedInput { time: press_time, input, press: true });
+		if slash_dash {
+			plan.push(PlannedInput { time: press_time, input: Input::Dash, press: true });
+			plan.push(PlannedInput { time: press_time + 0.05, input: Input::Dash, press: false });
+		}
+		plan.push(PlannedInput { time: release_time.max(press_time + 0.001), input, press: false });
+	}
+
+	plan.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap());
+	return plan;
+}
+
+fn run(path: &str, tick_length: &mut dyn FnMut() -> f32) -> Run {
+	let (mut game, duration) = load_song(path);
+	let plan = autoplay(&game);
+	let mut next_input = 0;
+	let mut next_checkpoint = CHECKPOINT_INTERVAL;
+	let mut player_xs = Vec::new();
+
+	while game.game_data().time_running < duration {
+		let time_running = game.game_data().time_running;
+		let seconds_passed = tick_length().min(next_checkpoint - time_running);
+		let end_tick_time = time_running + seconds_passed;
+
+		// inputs are sent before the tick they happen in, like key events between frames
+		while next_input < plan.len() && plan[next_input].time <= end_tick_time {
+			let planned = &plan[next_input];
+			let time_since_tick = (planned.time - time_running).max(0.0);
+			if planned.press {
+				game.input_command(planned.input, time_since_tick);
+			} else {
+				game.stop_command(planned.input, time_since_tick);
+			}
+			next_input += 1;
+		}
+
+		game.tick(seconds_passed);
+		if game.game_data().time_running >= next_checkpoint - 0.0001 {
+			player_xs.push(game.player_x());
+			next_checkpoint += CHECKPOINT_INTERVAL;
+		}
+	}
+
+	return Run { score: game.game_data().score, max_score: game.game_data().max_score, hit_times: game.hit_times(), player_xs };
+}
+
+fn assert_same(path: &str, expected: &Run, actual: &Run, partition: &str) {
+	assert_eq!(expected.score, actual.score, "{}: score differs with {}", path, partition);
+	assert_eq!(expected.hit_times.len(), actual.hit_times.len(), "{}: number of hits differs with {}", path, partition);
+	for (i, (e, a)) in expected.hit_times.iter().zip(&actual.hit_times).enumerate() {
+		assert!((e - a).abs() <= HIT_TIME_TOLERANCE, "{}: hit {} at {} rather than {} with {}", path, i, a, e, partition);
+	}
+	for (i, (e, a)) in expected.player_xs.iter().zip(&actual.player_xs).enumerate() {
+		assert!((e - a).abs() <= POSITION_TOLERANCE, "{}: player at {} rather than {} after {} seconds with {}",
+			path, a, e, (i + 1) as f32 * CHECKPOINT_INTERVAL, partition);
+	}
+}
+
+#[test]
+fn same_results_for_fixed_tick_lengths() {
+	for path in SONGS {
+		let expected = run(path, &mut || 0.001);
+		assert_eq!(expected.score, expected.max_score, "{}: the autoplayer missed bricks", path);
+
+		for tick_length in [1.0 / 120.0, 0.025, 0.1] {
+			let actual = run(path, &mut || tick_length);
+			assert_same(path, &expected, &actual, &format!("{} second ticks", tick_length));
+		}
+	}
+}
+
+#[test]
+fn same_results_for_random_tick_lengths() {
+	for path in SONGS {
+		let expected = run(path, &mut || 0.001);
+
+		for seed in 1..=RANDOM_PARTITIONS {
+			let mut rng = Rng { state: seed.wrapping_mul(2654435761) };
+			let actual = run(path, &mut || 0.0001 + rng.next_f32() * MAX_RANDOM_TICK);
+			assert_same(path, &expected, &actual, &format!("random ticks (seed {})", seed));
+		}
+	}
+}